@@ -0,0 +1,13 @@
+//! The simply typed lambda calculus with booleans of Chapters 9 and 10,
+//! following Pierce's `simplebool` implementation.
+
+pub mod evaluator;
+pub mod parser;
+pub mod term;
+pub mod ty;
+pub mod type_checker;
+
+pub use evaluator::{eval, eval1, eval_count};
+pub use parser::parse;
+pub use ty::Type;
+pub use type_checker::type_of;
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod test;
+
+use super::term::unnamed::{App, If, Term};
+
+/// Takes a single call-by-value step, using the rules of Figure 9-1
+/// and E-IfTrue, E-IfFalse and E-If.
+pub fn eval1(term: Term) -> Option<Term> {
+    match term {
+        Term::App(app) => {
+            let App {
+                position,
+                callee,
+                arg,
+            } = *app;
+            if !callee.is_val() {
+                eval1(callee).map(|callee| {
+                    App {
+                        position,
+                        callee,
+                        arg,
+                    }
+                    .into()
+                })
+            } else if !arg.is_val() {
+                eval1(arg).map(|arg| {
+                    App {
+                        position,
+                        callee,
+                        arg,
+                    }
+                    .into()
+                })
+            } else if let Term::Abs(abs) = callee {
+                Some(abs.apply(&arg))
+            } else {
+                None
+            }
+        }
+        Term::If(if_) => {
+            let If {
+                position,
+                guard,
+                then,
+                else_,
+            } = *if_;
+            match guard {
+                Term::Bool(b) => Some(if b.value { then } else { else_ }),
+                guard => eval1(guard).map(|guard| {
+                    If {
+                        position,
+                        guard,
+                        then,
+                        else_,
+                    }
+                    .into()
+                }),
+            }
+        }
+        Term::Var(_) | Term::Abs(_) | Term::Bool(_) => None,
+    }
+}
+
+/// Evaluates `term` until no more rules apply.
+/// Well-typed closed terms always reach a value.
+pub fn eval(term: Term) -> Term {
+    eval_count(term).0
+}
+
+pub fn eval_count(mut term: Term) -> (Term, usize) {
+    let mut i = 0;
+    while let Some(evaluated) = eval1(term.clone()) {
+        i += 1;
+        term = evaluated;
+    }
+    (term, i)
+}
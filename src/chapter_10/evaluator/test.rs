@@ -0,0 +1,64 @@
+use super::{eval, eval1, eval_count};
+use crate::chapter_10::{
+    parse,
+    term::unnamed::Term,
+    type_checker::{type_of, TypeContext},
+};
+use crate::chapter_7::term::unnamed::Context;
+
+fn unnamed(src: &str) -> Term {
+    Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.")
+}
+
+fn evaluated(src: &str) -> String {
+    eval(unnamed(src)).into_unpositioned_named().to_string()
+}
+
+#[test]
+fn values_do_not_step() {
+    assert_eq!(eval1(unnamed("true")), None);
+    assert_eq!(eval1(unnamed(r"\x:Bool. (\y:Bool. y) x")), None);
+}
+
+#[test]
+fn call_by_value() {
+    assert_eq!(evaluated(r"(\x:Bool. x) true"), "true");
+    assert_eq!(
+        evaluated(r"(\f:Bool -> Bool. \x:Bool. f (f x)) (\b:Bool. if b then false else true)"),
+        r"(\x:Bool. ((\b:Bool. (if b then false else true)) ((\b:Bool. (if b then false else true)) x)))"
+    );
+    let (term, steps) = eval_count(unnamed(
+        r"(\f:Bool -> Bool. f (f true)) (\b:Bool. if b then false else true)",
+    ));
+    assert_eq!(term.into_unpositioned_named().to_string(), "true");
+    assert_eq!(steps, 5);
+}
+
+#[test]
+fn if_evaluates_the_guard_first() {
+    let term = unnamed(r"if (\x:Bool. x) false then true else false");
+    let stepped = eval1(term).expect("Expected a step.");
+    assert_eq!(
+        stepped.into_unpositioned_named().to_string(),
+        "(if false then true else false)"
+    );
+}
+
+#[test]
+fn evaluation_preserves_types() {
+    let examples = [
+        r"(\f:Bool -> Bool. f (f true)) (\b:Bool. if b then false else true)",
+        r"(\x:Bool. \y:Bool. x) true",
+        r"if (\x:Bool. x) true then \x:Bool. false else \x:Bool. x",
+    ];
+    for src in examples.iter() {
+        let mut term = unnamed(src);
+        let ty = type_of(&term, &TypeContext::empty()).expect("Expected a type.");
+        while let Some(next) = eval1(term.clone()) {
+            assert_eq!(type_of(&next, &TypeContext::empty()), Ok(ty.clone()));
+            term = next;
+        }
+        assert!(term.is_val(), "`{}` got stuck.", src);
+    }
+}
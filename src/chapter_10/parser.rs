@@ -0,0 +1,307 @@
+#[cfg(test)]
+mod test;
+
+pub mod err {
+    pub use crate::chapter_7::parser::err::TokenOrEof;
+    pub use crate::chapter_7::parser::lexer::{PositionedToken, Token, TokenizationErr};
+
+    use std::fmt;
+
+    #[derive(Clone, Debug)]
+    pub enum ParseErr {
+        Tokenization(TokenizationErr),
+        UnexpectedTokenOrEof(UnexpectedTokenOrEofErr),
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct UnexpectedTokenOrEofErr {
+        pub expected: Vec<Expected>,
+        pub actual: TokenOrEof,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    pub enum Expected {
+        Eof,
+        /// A name that is not a keyword.
+        Ident,
+        Term,
+        Type,
+        Token(Token),
+        Keyword(&'static str),
+    }
+
+    impl fmt::Display for ParseErr {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ParseErr::Tokenization(err) => write!(f, "{}", err),
+                ParseErr::UnexpectedTokenOrEof(err) => write!(f, "{}", err),
+            }
+        }
+    }
+
+    impl fmt::Display for UnexpectedTokenOrEofErr {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let expected: Vec<String> = self.expected.iter().map(ToString::to_string).collect();
+            match &self.actual {
+                TokenOrEof::Token(actual) => write!(
+                    f,
+                    "{}: Expected {}, but found `{}`.",
+                    actual.position.start,
+                    expected.join(" or "),
+                    actual.token
+                ),
+                TokenOrEof::Eof => write!(
+                    f,
+                    "Expected {}, but found end of input.",
+                    expected.join(" or ")
+                ),
+            }
+        }
+    }
+
+    impl fmt::Display for Expected {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Expected::Eof => write!(f, "end of input"),
+                Expected::Ident => write!(f, "identifier"),
+                Expected::Term => write!(f, "term"),
+                Expected::Type => write!(f, "type"),
+                Expected::Token(token) => write!(f, "`{}`", token),
+                Expected::Keyword(keyword) => write!(f, "`{}`", keyword),
+            }
+        }
+    }
+}
+
+use super::term::named::{Abs, App, Bool, If, Term, Var};
+use super::ty::{self, Type};
+use crate::chapter_7::parser::lexer::tokenize;
+use crate::file_position::{FilePositionRange, Position};
+use err::*;
+
+/// Names that cannot be used as variables.
+pub const KEYWORDS: [&str; 5] = ["if", "then", "else", "true", "false"];
+
+/// Parses a term such as `\x:Bool. if x then false else true`.
+///
+/// Abstractions and `if` extend as far to the right as possible,
+/// application associates to the left, and so does `->` to the right.
+pub fn parse(src: &str) -> Result<Term, ParseErr> {
+    let tokens = tokenize(src).map_err(ParseErr::Tokenization)?;
+    let mut parser = TokenParser { tokens: &tokens };
+    let term = parser.consume_term()?;
+    if parser.is_exhausted() {
+        Ok(term)
+    } else {
+        Err(parser.expected_err(vec![Expected::Eof]))
+    }
+}
+
+/// Parses a type such as `(Bool -> Bool) -> Bool`.
+pub fn parse_type(src: &str) -> Result<Type, ParseErr> {
+    let tokens = tokenize(src).map_err(ParseErr::Tokenization)?;
+    let mut parser = TokenParser { tokens: &tokens };
+    let ty = parser.consume_type()?;
+    if parser.is_exhausted() {
+        Ok(ty)
+    } else {
+        Err(parser.expected_err(vec![Expected::Eof]))
+    }
+}
+
+struct TokenParser<'a> {
+    tokens: &'a [PositionedToken],
+}
+
+impl TokenParser<'_> {
+    fn consume_term(&mut self) -> Result<Term, ParseErr> {
+        if let Some(term) = self.consume_opt_prefix_term()? {
+            return Ok(term);
+        }
+        let mut term = match self.consume_opt_atom()? {
+            Some(atom) => atom,
+            None => return Err(self.expected_err(vec![Expected::Term])),
+        };
+        loop {
+            let (arg, is_last) = if let Some(atom) = self.consume_opt_atom()? {
+                (atom, false)
+            } else if let Some(prefix_term) = self.consume_opt_prefix_term()? {
+                (prefix_term, true)
+            } else {
+                return Ok(term);
+            };
+            term = App {
+                position: span(&term, &arg),
+                callee: term,
+                arg,
+            }
+            .into();
+            if is_last {
+                return Ok(term);
+            }
+        }
+    }
+
+    /// Consumes a term that starts with a keyword or `\` and extends as far to the right as possible.
+    fn consume_opt_prefix_term(&mut self) -> Result<Option<Term>, ParseErr> {
+        if let Some(lambda) = self.consume_opt_token(&Token::Lambda) {
+            let param = self.consume_var()?;
+            self.consume_token(Token::Colon)?;
+            let param_type = self.consume_type()?;
+            self.consume_token(Token::Dot)?;
+            let body = self.consume_term()?;
+            Ok(Some(
+                Abs {
+                    position: FilePositionRange {
+                        start: lambda.start,
+                        end: body.position().end,
+                    },
+                    param,
+                    param_type,
+                    body,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("if") {
+            let guard = self.consume_term()?;
+            self.consume_keyword("then")?;
+            let then = self.consume_term()?;
+            self.consume_keyword("else")?;
+            let else_ = self.consume_term()?;
+            Ok(Some(
+                If {
+                    position: FilePositionRange {
+                        start: keyword.start,
+                        end: else_.position().end,
+                    },
+                    guard,
+                    then,
+                    else_,
+                }
+                .into(),
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn consume_opt_atom(&mut self) -> Result<Option<Term>, ParseErr> {
+        for &(keyword, value) in [("true", true), ("false", false)].iter() {
+            if let Some(position) = self.consume_opt_keyword(keyword) {
+                return Ok(Some(Bool { position, value }.into()));
+            }
+        }
+        if let Some(var) = self.consume_opt_var() {
+            Ok(Some(var.into()))
+        } else if self.consume_opt_token(&Token::LParen).is_some() {
+            let term = self.consume_term()?;
+            self.consume_token(Token::RParen)?;
+            Ok(Some(term))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn consume_type(&mut self) -> Result<Type, ParseErr> {
+        let param = self.consume_atomic_type()?;
+        if self.consume_opt_token(&Token::Arrow).is_some() {
+            Ok(ty::arrow(param, self.consume_type()?))
+        } else {
+            Ok(param)
+        }
+    }
+
+    fn consume_atomic_type(&mut self) -> Result<Type, ParseErr> {
+        if self.consume_opt_keyword("Bool").is_some() {
+            Ok(Type::Bool)
+        } else if self.consume_opt_token(&Token::LParen).is_some() {
+            let ty = self.consume_type()?;
+            self.consume_token(Token::RParen)?;
+            Ok(ty)
+        } else {
+            Err(self.expected_err(vec![Expected::Type]))
+        }
+    }
+
+    fn consume_var(&mut self) -> Result<Var, ParseErr> {
+        match self.consume_opt_var() {
+            Some(var) => Ok(var),
+            None => Err(self.expected_err(vec![Expected::Ident])),
+        }
+    }
+
+    fn consume_opt_var(&mut self) -> Option<Var> {
+        match self.tokens.first() {
+            Some(PositionedToken {
+                token: Token::Ident(name),
+                position,
+            }) if !KEYWORDS.contains(&name.as_str()) => {
+                let var = Var {
+                    position: *position,
+                    name: name.clone(),
+                };
+                self.tokens = &self.tokens[1..];
+                Some(var)
+            }
+            _ => None,
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: &'static str) -> Result<FilePositionRange, ParseErr> {
+        match self.consume_opt_keyword(keyword) {
+            Some(position) => Ok(position),
+            None => Err(self.expected_err(vec![Expected::Keyword(keyword)])),
+        }
+    }
+
+    fn consume_opt_keyword(&mut self, keyword: &str) -> Option<FilePositionRange> {
+        match self.tokens.first() {
+            Some(PositionedToken {
+                token: Token::Ident(name),
+                position,
+            }) if name == keyword => {
+                self.tokens = &self.tokens[1..];
+                Some(*position)
+            }
+            _ => None,
+        }
+    }
+
+    fn consume_token(&mut self, expected: Token) -> Result<FilePositionRange, ParseErr> {
+        match self.consume_opt_token(&expected) {
+            Some(position) => Ok(position),
+            None => Err(self.expected_err(vec![Expected::Token(expected)])),
+        }
+    }
+
+    fn consume_opt_token(&mut self, expected: &Token) -> Option<FilePositionRange> {
+        match self.tokens.first() {
+            Some(ptoken) if ptoken.token == *expected => {
+                self.tokens = &self.tokens[1..];
+                Some(ptoken.position)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn expected_err(&self, expected: Vec<Expected>) -> ParseErr {
+        ParseErr::UnexpectedTokenOrEof(UnexpectedTokenOrEofErr {
+            expected,
+            actual: match self.tokens.first() {
+                Some(token) => TokenOrEof::Token(token.clone()),
+                None => TokenOrEof::Eof,
+            },
+        })
+    }
+}
+
+fn span(first: &Term, last: &Term) -> FilePositionRange {
+    FilePositionRange {
+        start: first.position().start,
+        end: last.position().end,
+    }
+}
@@ -0,0 +1,95 @@
+use super::err::{Expected, ParseErr, Token, TokenOrEof};
+use super::{parse, parse_type};
+use crate::chapter_10::ty::{arrow, Type};
+use crate::file_position::Position;
+
+fn parsed(src: &str) -> String {
+    parse(src).expect("Cannot parse.").to_string()
+}
+
+#[test]
+fn abstractions_have_typed_params() {
+    assert_eq!(parsed(r"\x:Bool. x"), r"(\x:Bool. x)");
+    assert_eq!(
+        parsed(r"\f:Bool -> Bool. \x:Bool. f (f x)"),
+        r"(\f:Bool -> Bool. (\x:Bool. (f (f x))))"
+    );
+}
+
+#[test]
+fn if_and_booleans() {
+    assert_eq!(
+        parsed(r"\b:Bool. if b then false else true"),
+        r"(\b:Bool. (if b then false else true))"
+    );
+    assert_eq!(
+        parsed(r"if if true then false else true then x else y z"),
+        r"(if (if true then false else true) then x else (y z))"
+    );
+}
+
+#[test]
+fn application_associates_to_the_left() {
+    assert_eq!(parsed(r"f x y"), r"((f x) y)");
+    assert_eq!(parsed(r"f (x y)"), r"(f (x y))");
+    assert_eq!(parsed(r"f \x:Bool. x"), r"(f (\x:Bool. x))");
+    assert_eq!(parsed(r"f if x then y else z"), r"(f (if x then y else z))");
+}
+
+#[test]
+fn arrows_associate_to_the_right() {
+    assert_eq!(
+        parse_type("Bool -> Bool -> Bool").expect("Cannot parse."),
+        arrow(Type::Bool, arrow(Type::Bool, Type::Bool))
+    );
+    let ty = parse_type("(Bool -> Bool) -> Bool").expect("Cannot parse.");
+    assert_eq!(ty, arrow(arrow(Type::Bool, Type::Bool), Type::Bool));
+    assert_eq!(ty.to_string(), "(Bool -> Bool) -> Bool");
+}
+
+#[test]
+fn positions() {
+    let src = r"f (\x:Bool. x) true";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().start.index, 0);
+    assert_eq!(term.position().end.index, src.len());
+    // Parentheses are not part of the position of the term they enclose.
+    let term = parse(r"(\x:Bool. x)").expect("Cannot parse.");
+    assert_eq!(term.position().start.column, 1);
+    let src = r"  if true then x else y  ";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().start.column, 2);
+    assert_eq!(term.position().end.column, 23);
+}
+
+#[test]
+fn keywords_are_not_names() {
+    let err = parse(r"\then:Bool. x").expect_err("`then` is a keyword.");
+    match err {
+        ParseErr::UnexpectedTokenOrEof(err) => {
+            assert_eq!(err.expected, vec![Expected::Ident]);
+            match err.actual {
+                TokenOrEof::Token(token) => {
+                    assert_eq!(token.token, Token::Ident("then".to_string()))
+                }
+                TokenOrEof::Eof => panic!("Expected a token."),
+            }
+        }
+        other => panic!("Expected an unexpected token, but found {:?}.", other),
+    }
+}
+
+#[test]
+fn err_display() {
+    let err = parse(r"\x. x").expect_err("The parameter has no type.");
+    assert_eq!(err.to_string(), "1:3: Expected `:`, but found `.`.");
+    let err = parse(r"\x:Bool -> . x").expect_err("The type is incomplete.");
+    assert_eq!(err.to_string(), "1:12: Expected type, but found `.`.");
+    let err = parse(r"if x then y").expect_err("`else` is missing.");
+    assert_eq!(err.to_string(), "Expected `else`, but found end of input.");
+    let err = parse(r"x )").expect_err("Parsed unbalanced parentheses.");
+    assert_eq!(
+        err.to_string(),
+        "1:3: Expected end of input, but found `)`."
+    );
+}
@@ -0,0 +1,181 @@
+use super::super::ty::Type;
+use super::unnamed::{self, Term as UnnamedTerm};
+use crate::chapter_7::term::unnamed::Context;
+use crate::file_position::{FilePosition, FilePositionRange, Position};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Term {
+    Var(Var),
+    Abs(Box<Abs>),
+    App(Box<App>),
+    Bool(Bool),
+    If(Box<If>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Var {
+    pub position: FilePositionRange,
+    pub name: String,
+}
+
+/// `\param:param_type. body`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Abs {
+    pub position: FilePositionRange,
+    pub param: Var,
+    pub param_type: Type,
+    pub body: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct App {
+    pub position: FilePositionRange,
+    pub callee: Term,
+    pub arg: Term,
+}
+
+/// `true` or `false`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Bool {
+    pub position: FilePositionRange,
+    pub value: bool,
+}
+
+/// `if guard then then else else_`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct If {
+    pub position: FilePositionRange,
+    pub guard: Term,
+    pub then: Term,
+    pub else_: Term,
+}
+
+impl From<Var> for Term {
+    fn from(var: Var) -> Term {
+        Term::Var(var)
+    }
+}
+
+impl From<Abs> for Term {
+    fn from(abs: Abs) -> Term {
+        Term::Abs(Box::new(abs))
+    }
+}
+
+impl From<App> for Term {
+    fn from(app: App) -> Term {
+        Term::App(Box::new(app))
+    }
+}
+
+impl From<Bool> for Term {
+    fn from(b: Bool) -> Term {
+        Term::Bool(b)
+    }
+}
+
+impl From<If> for Term {
+    fn from(if_: If) -> Term {
+        Term::If(Box::new(if_))
+    }
+}
+
+impl Position for &Term {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Term::Var(var) => var.position,
+            Term::Abs(abs) => abs.position,
+            Term::App(app) => app.position,
+            Term::Bool(b) => b.position,
+            Term::If(if_) => if_.position,
+        }
+    }
+}
+
+impl Position for &Var {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl Term {
+    /// Names the variables of `un` using `ctx`, adding primes to parameter names
+    /// where they would shadow a name that is still used, like
+    /// `chapter_7::term::named::Term::unpositioned_from_unnamed`.
+    pub fn unpositioned_from_unnamed(un: UnnamedTerm, ctx: &Context) -> Term {
+        let position = dummy_position();
+        match un {
+            UnnamedTerm::Var(var) => Var {
+                position,
+                name: match ctx.name(var.index) {
+                    Some(name) => name.to_string(),
+                    None => format!("${}", var.index),
+                },
+            }
+            .into(),
+            UnnamedTerm::Abs(abs) => {
+                let unnamed::Abs {
+                    param_name,
+                    param_type,
+                    body,
+                    ..
+                } = *abs;
+                let mut name = param_name;
+                while ctx.index(&name).is_some() {
+                    name.push('\'');
+                }
+                let body_ctx = ctx.clone() + name.clone();
+                Abs {
+                    position,
+                    param: Var { position, name },
+                    param_type,
+                    body: Term::unpositioned_from_unnamed(body, &body_ctx),
+                }
+                .into()
+            }
+            UnnamedTerm::App(app) => App {
+                position,
+                callee: Term::unpositioned_from_unnamed(app.callee, ctx),
+                arg: Term::unpositioned_from_unnamed(app.arg, ctx),
+            }
+            .into(),
+            UnnamedTerm::Bool(b) => Bool {
+                position,
+                value: b.value,
+            }
+            .into(),
+            UnnamedTerm::If(if_) => If {
+                position,
+                guard: Term::unpositioned_from_unnamed(if_.guard, ctx),
+                then: Term::unpositioned_from_unnamed(if_.then, ctx),
+                else_: Term::unpositioned_from_unnamed(if_.else_, ctx),
+            }
+            .into(),
+        }
+    }
+}
+
+fn dummy_position() -> FilePositionRange {
+    let position = FilePosition {
+        index: 0,
+        column: 0,
+        line: 0,
+    };
+    FilePositionRange {
+        start: position,
+        end: position,
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(var) => write!(f, "{}", var.name),
+            Term::Abs(abs) => write!(f, "(\\{}:{}. {})", abs.param.name, abs.param_type, abs.body),
+            Term::App(app) => write!(f, "({} {})", app.callee, app.arg),
+            Term::Bool(b) => write!(f, "{}", b.value),
+            Term::If(if_) => write!(f, "(if {} then {} else {})", if_.guard, if_.then, if_.else_),
+        }
+    }
+}
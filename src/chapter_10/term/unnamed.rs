@@ -0,0 +1,222 @@
+use super::super::ty::Type;
+use super::named::{self, Term as NamedTerm};
+use crate::chapter_7::term::unnamed::Context;
+use crate::file_position::{FilePositionRange, Position};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Term {
+    Var(Var),
+    Abs(Box<Abs>),
+    App(Box<App>),
+    Bool(Bool),
+    If(Box<If>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Var {
+    pub position: FilePositionRange,
+    pub index: usize,
+    pub context_length: usize,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Abs {
+    pub position: FilePositionRange,
+    pub param_name: String,
+    pub param_type: Type,
+    pub body: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct App {
+    pub position: FilePositionRange,
+    pub callee: Term,
+    pub arg: Term,
+}
+
+pub use named::Bool;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct If {
+    pub position: FilePositionRange,
+    pub guard: Term,
+    pub then: Term,
+    pub else_: Term,
+}
+
+impl Term {
+    pub fn shift(self, amount: i32) -> Term {
+        self.map_vars(0, &|var, cutoff| {
+            Term::Var(Var {
+                index: if var.index >= cutoff {
+                    ((var.index as i32) + amount) as usize
+                } else {
+                    var.index
+                },
+                context_length: ((var.context_length as i32) + amount) as usize,
+                ..var
+            })
+        })
+    }
+
+    pub fn subst(self, replacee: usize, replacer: &Term) -> Term {
+        self.map_vars(0, &|var, depth| {
+            if var.index == replacee + depth {
+                replacer.clone().shift(depth as i32)
+            } else {
+                Term::Var(var)
+            }
+        })
+    }
+
+    /// Replaces every variable, passing the number of abstractions it is under.
+    fn map_vars(self, depth: usize, f: &dyn Fn(Var, usize) -> Term) -> Term {
+        match self {
+            Term::Var(var) => f(var, depth),
+            Term::Abs(abs) => Term::Abs(Box::new(Abs {
+                body: abs.body.map_vars(depth + 1, f),
+                ..*abs
+            })),
+            Term::App(app) => Term::App(Box::new(App {
+                callee: app.callee.map_vars(depth, f),
+                arg: app.arg.map_vars(depth, f),
+                ..*app
+            })),
+            Term::Bool(b) => Term::Bool(b),
+            Term::If(if_) => Term::If(Box::new(If {
+                guard: if_.guard.map_vars(depth, f),
+                then: if_.then.map_vars(depth, f),
+                else_: if_.else_.map_vars(depth, f),
+                ..*if_
+            })),
+        }
+    }
+
+    pub fn is_val(&self) -> bool {
+        matches!(self, Term::Abs(_) | Term::Bool(_))
+    }
+
+    pub fn into_unpositioned_named(self) -> NamedTerm {
+        NamedTerm::unpositioned_from_unnamed(self, &Context::empty())
+    }
+}
+
+impl Abs {
+    pub fn apply(self, argument: &Term) -> Term {
+        self.body.subst(0, &argument.clone().shift(1)).shift(-1)
+    }
+}
+
+impl From<Var> for Term {
+    fn from(var: Var) -> Term {
+        Term::Var(var)
+    }
+}
+
+impl From<Abs> for Term {
+    fn from(abs: Abs) -> Term {
+        Term::Abs(Box::new(abs))
+    }
+}
+
+impl From<App> for Term {
+    fn from(app: App) -> Term {
+        Term::App(Box::new(app))
+    }
+}
+
+impl From<Bool> for Term {
+    fn from(b: Bool) -> Term {
+        Term::Bool(b)
+    }
+}
+
+impl From<If> for Term {
+    fn from(if_: If) -> Term {
+        Term::If(Box::new(if_))
+    }
+}
+
+impl Position for &Term {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Term::Var(var) => var.position,
+            Term::Abs(abs) => abs.position,
+            Term::App(app) => app.position,
+            Term::Bool(b) => b.position,
+            Term::If(if_) => if_.position,
+        }
+    }
+}
+
+impl Term {
+    pub fn from_named(named: NamedTerm, ctx: &Context) -> Result<Term, CannotFindVarInCtxErr> {
+        Ok(match named {
+            NamedTerm::Var(var) => match ctx.index(&var.name) {
+                Some(index) => Var {
+                    position: var.position,
+                    index,
+                    context_length: ctx.len(),
+                }
+                .into(),
+                None => return Err(CannotFindVarInCtxErr(var)),
+            },
+            NamedTerm::Abs(abs) => {
+                let named::Abs {
+                    position,
+                    param,
+                    param_type,
+                    body,
+                } = *abs;
+                let body_ctx = ctx.clone() + param.name.clone();
+                Abs {
+                    position,
+                    param_name: param.name,
+                    param_type,
+                    body: Term::from_named(body, &body_ctx)?,
+                }
+                .into()
+            }
+            NamedTerm::App(app) => App {
+                position: app.position,
+                callee: Term::from_named(app.callee, ctx)?,
+                arg: Term::from_named(app.arg, ctx)?,
+            }
+            .into(),
+            NamedTerm::Bool(b) => Term::Bool(b),
+            NamedTerm::If(if_) => If {
+                position: if_.position,
+                guard: Term::from_named(if_.guard, ctx)?,
+                then: Term::from_named(if_.then, ctx)?,
+                else_: Term::from_named(if_.else_, ctx)?,
+            }
+            .into(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CannotFindVarInCtxErr(pub named::Var);
+
+impl fmt::Display for CannotFindVarInCtxErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: Cannot find `{}` in context.",
+            self.0.position.start, self.0.name
+        )
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(var) => write!(f, "{}", var.index),
+            Term::Abs(abs) => write!(f, "(\\:{}. {})", abs.param_type, abs.body),
+            Term::App(app) => write!(f, "({} {})", app.callee, app.arg),
+            Term::Bool(b) => write!(f, "{}", b.value),
+            Term::If(if_) => write!(f, "(if {} then {} else {})", if_.guard, if_.then, if_.else_),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Bool,
+    Arrow(Box<Type>, Box<Type>),
+}
+
+pub fn arrow(param: Type, result: Type) -> Type {
+    Type::Arrow(Box::new(param), Box::new(result))
+}
+
+impl fmt::Display for Type {
+    /// Arrows associate to the right, so only parameters that are arrows are parenthesized.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Bool => write!(f, "Bool"),
+            Type::Arrow(param, result) => match **param {
+                Type::Arrow(_, _) => write!(f, "({}) -> {}", param, result),
+                _ => write!(f, "{} -> {}", param, result),
+            },
+        }
+    }
+}
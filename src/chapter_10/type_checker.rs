@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod test;
+
+use super::term::unnamed::Term;
+use super::ty::{self, Type};
+use crate::file_position::{FilePositionRange, Position};
+use std::fmt;
+use std::ops::Add;
+
+/// The types of the variables in scope. Like in `chapter_7::term::unnamed::Context`,
+/// the most recently added type has index `0`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeContext {
+    types: Vec<Type>,
+}
+
+impl TypeContext {
+    pub fn empty() -> TypeContext {
+        TypeContext { types: vec![] }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Type> {
+        self.types.iter().rev().nth(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
+
+impl Add<Type> for TypeContext {
+    type Output = TypeContext;
+
+    fn add(mut self, ty: Type) -> TypeContext {
+        self.types.push(ty);
+        self
+    }
+}
+
+impl Add<Type> for &TypeContext {
+    type Output = TypeContext;
+
+    fn add(self, ty: Type) -> TypeContext {
+        self.clone() + ty
+    }
+}
+
+/// A term without a type. The position is that of the offending subterm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeErr {
+    pub position: FilePositionRange,
+    pub kind: TypeErrKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeErrKind {
+    GuardNotBool(Type),
+    BranchesDiffer {
+        then: Type,
+        else_: Type,
+    },
+    CalleeNotArrow(Type),
+    ArgMismatch {
+        param: Type,
+        arg: Type,
+    },
+    /// A variable whose index is not less than the length of the context.
+    UnboundVar(usize),
+}
+
+impl Position for &TypeErr {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl fmt::Display for TypeErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.position.start)?;
+        match &self.kind {
+            TypeErrKind::GuardNotBool(actual) => write!(
+                f,
+                "Expected the guard of `if` to have type `Bool`, but it has type `{}`.",
+                actual
+            ),
+            TypeErrKind::BranchesDiffer { then, else_ } => write!(
+                f,
+                "Expected the branches of `if` to have the same type, but they have types `{}` and `{}`.",
+                then, else_
+            ),
+            TypeErrKind::CalleeNotArrow(actual) => write!(
+                f,
+                "Expected a function, but found a term of type `{}`.",
+                actual
+            ),
+            TypeErrKind::ArgMismatch { param, arg } => write!(
+                f,
+                "Expected an argument of type `{}`, but found a term of type `{}`.",
+                param, arg
+            ),
+            TypeErrKind::UnboundVar(index) => write!(f, "Cannot find index {} in context.", index),
+        }
+    }
+}
+
+fn err(term: &Term, kind: TypeErrKind) -> TypeErr {
+    TypeErr {
+        position: term.position(),
+        kind,
+    }
+}
+
+/// The type of `term`, whose free variables have the types in `ctx`,
+/// using the rules of Figure 9-1 and T-True, T-False and T-If.
+pub fn type_of(term: &Term, ctx: &TypeContext) -> Result<Type, TypeErr> {
+    match term {
+        Term::Var(var) => ctx
+            .get(var.index)
+            .cloned()
+            .ok_or_else(|| err(term, TypeErrKind::UnboundVar(var.index))),
+        Term::Abs(abs) => {
+            let body_ctx = ctx + abs.param_type.clone();
+            let body_type = type_of(&abs.body, &body_ctx)?;
+            Ok(ty::arrow(abs.param_type.clone(), body_type))
+        }
+        Term::App(app) => {
+            let callee_type = type_of(&app.callee, ctx)?;
+            let arg_type = type_of(&app.arg, ctx)?;
+            match callee_type {
+                Type::Arrow(param, result) => {
+                    if *param == arg_type {
+                        Ok(*result)
+                    } else {
+                        Err(err(
+                            &app.arg,
+                            TypeErrKind::ArgMismatch {
+                                param: *param,
+                                arg: arg_type,
+                            },
+                        ))
+                    }
+                }
+                other => Err(err(&app.callee, TypeErrKind::CalleeNotArrow(other))),
+            }
+        }
+        Term::Bool(_) => Ok(Type::Bool),
+        Term::If(if_) => {
+            let guard_type = type_of(&if_.guard, ctx)?;
+            if guard_type != Type::Bool {
+                return Err(err(&if_.guard, TypeErrKind::GuardNotBool(guard_type)));
+            }
+            let then_type = type_of(&if_.then, ctx)?;
+            let else_type = type_of(&if_.else_, ctx)?;
+            if then_type == else_type {
+                Ok(then_type)
+            } else {
+                Err(err(
+                    &if_.else_,
+                    TypeErrKind::BranchesDiffer {
+                        then: then_type,
+                        else_: else_type,
+                    },
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::{type_of, TypeContext, TypeErr, TypeErrKind};
+use crate::chapter_10::{
+    parse,
+    parser::parse_type,
+    term::unnamed::Term,
+    ty::{arrow, Type},
+};
+use crate::chapter_7::term::unnamed::Context;
+
+fn checked(src: &str) -> Result<Type, TypeErr> {
+    let term = Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.");
+    type_of(&term, &TypeContext::empty())
+}
+
+fn ty(src: &str) -> Type {
+    parse_type(src).expect("Cannot parse.")
+}
+
+#[test]
+fn well_typed_terms() {
+    let examples = [
+        ("true", "Bool"),
+        (r"\x:Bool. x", "Bool -> Bool"),
+        (r"(\x:Bool. x) true", "Bool"),
+        (
+            r"\f:Bool -> Bool. \x:Bool. f (f x)",
+            "(Bool -> Bool) -> Bool -> Bool",
+        ),
+        (r"\b:Bool. if b then false else true", "Bool -> Bool"),
+        (
+            r"if true then \x:Bool. x else \y:Bool. false",
+            "Bool -> Bool",
+        ),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+}
+
+#[test]
+fn free_variables_take_their_types_from_the_context() {
+    let ctx = Context::from_strs(&["f", "b"]);
+    let term =
+        Term::from_named(parse("f b").expect("Cannot parse."), &ctx).expect("Cannot remove names.");
+    let types = TypeContext::empty() + arrow(Type::Bool, Type::Bool) + Type::Bool;
+    assert_eq!(type_of(&term, &types), Ok(Type::Bool));
+    let err = type_of(&term, &TypeContext::empty()).expect_err("The context is empty.");
+    assert_eq!(err.kind, TypeErrKind::UnboundVar(1));
+}
+
+#[test]
+fn type_errors_point_at_the_offending_subterm() {
+    let examples = [
+        (
+            r"if \x:Bool. x then true else false",
+            TypeErrKind::GuardNotBool(ty("Bool -> Bool")),
+            "1:4: Expected the guard of `if` to have type `Bool`, but it has type `Bool -> Bool`.",
+        ),
+        (
+            r"if true then true else \x:Bool. x",
+            TypeErrKind::BranchesDiffer {
+                then: Type::Bool,
+                else_: ty("Bool -> Bool"),
+            },
+            "1:24: Expected the branches of `if` to have the same type, but they have types `Bool` and `Bool -> Bool`.",
+        ),
+        (
+            r"true false",
+            TypeErrKind::CalleeNotArrow(Type::Bool),
+            "1:1: Expected a function, but found a term of type `Bool`.",
+        ),
+        (
+            r"(\f:Bool -> Bool. f) true",
+            TypeErrKind::ArgMismatch {
+                param: ty("Bool -> Bool"),
+                arg: Type::Bool,
+            },
+            "1:22: Expected an argument of type `Bool -> Bool`, but found a term of type `Bool`.",
+        ),
+    ];
+    for (src, kind, message) in examples.iter() {
+        let err = checked(src).expect_err("Expected a type error.");
+        assert_eq!(err.kind, *kind, "`{}`", src);
+        assert_eq!(err.to_string(), *message);
+    }
+}
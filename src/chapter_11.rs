@@ -0,0 +1,24 @@
+//! The simply typed lambda calculus with the extensions of Chapter 11,
+//! the references of Chapter 13 and the exceptions of Chapter 14,
+//! following Pierce's `fullsimple`, `fullref` and `fullerror` implementations.
+//!
+//! Besides `Bool`, the base types are `Nat`, with numerals such as `3`
+//! standing for `succ (succ (succ 0))`, and `Unit`.
+//! With `fix` from Section 11.11, well-typed terms may diverge,
+//! so the REPL evaluates them with `evaluator::eval_count_with_fuel`.
+//! Evaluation threads a `store::Store` of references through each step,
+//! and `error` and `raise t` have the type `Bot`, which can stand in for any type.
+//! `chapter_15` checks the same terms with subtyping.
+
+pub mod derived;
+pub mod evaluator;
+pub mod parser;
+pub mod store;
+pub mod term;
+pub mod ty;
+pub mod type_checker;
+
+pub use evaluator::{eval, eval1, eval_count, eval_count_with_fuel, Outcome};
+pub use parser::parse;
+pub use ty::Type;
+pub use type_checker::type_of;
@@ -0,0 +1,76 @@
+//! Derived forms, which are translated into the rest of the language.
+
+#[cfg(test)]
+mod test;
+
+use super::term::named::{Abs, App, Cons, Nil, Term, Var, WILDCARD};
+use super::ty::Type;
+use crate::file_position::{FilePositionRange, Position};
+
+/// Replaces every `t1; t2` with `(\_:Unit. t2) t1`, as in Definition 11.3.1.
+/// Both forms have the same type and evaluate to the same value,
+/// the latter taking one more step per sequence (Theorem 11.3.1).
+pub fn desugar_seqs(term: Term) -> Term {
+    match term.map_subterms(&mut desugar_seqs) {
+        Term::Seq(seq) => {
+            let position = seq.position;
+            App {
+                position,
+                callee: Abs {
+                    position,
+                    param: Var {
+                        position,
+                        name: WILDCARD.to_string(),
+                    },
+                    param_type: Type::Unit,
+                    body: seq.second,
+                }
+                .into(),
+                arg: seq.first,
+            }
+            .into()
+        }
+        term => term,
+    }
+}
+
+/// `Option T`, which is `<none:Unit, some:T>` (Section 11.10).
+pub fn option(ty: Type) -> Type {
+    Type::Variant(vec![
+        ("none".to_string(), Type::Unit),
+        ("some".to_string(), ty),
+    ])
+}
+
+/// An enumerated type, which is a variant whose fields are all `Unit`,
+/// such as `<monday:Unit, tuesday:Unit>` (Section 11.10).
+pub fn enumeration(labels: &[&str]) -> Type {
+    Type::Variant(
+        labels
+            .iter()
+            .map(|label| (label.to_string(), Type::Unit))
+            .collect(),
+    )
+}
+
+/// The list literal `[t1, ..., tn] as List ty` at `position`, which is
+/// `cons[ty] t1 (... (cons[ty] tn nil[ty]))`.
+/// Each `cons` spans from its head to the end of the literal.
+pub fn list(position: FilePositionRange, ty: Type, elements: Vec<Term>) -> Term {
+    let nil = Nil {
+        position,
+        ty: ty.clone(),
+    };
+    elements.into_iter().rev().fold(nil.into(), |tail, head| {
+        Cons {
+            position: FilePositionRange {
+                start: head.position().start,
+                end: position.end,
+            },
+            ty: ty.clone(),
+            head,
+            tail,
+        }
+        .into()
+    })
+}
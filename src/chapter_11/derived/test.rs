@@ -0,0 +1,76 @@
+use super::{desugar_seqs, enumeration, option};
+use crate::chapter_11::{
+    eval_count, parse,
+    store::Store,
+    term::unnamed::Term,
+    ty::{arrow, Type},
+    type_checker::{type_of, type_of_with_warnings, TypeContext},
+};
+use crate::chapter_7::term::unnamed::Context;
+
+#[test]
+fn seqs_become_wildcard_abstractions() {
+    let term = desugar_seqs(parse("a; b; c").expect("Cannot parse."));
+    assert_eq!(term.to_string(), r"((\_:Unit. ((\_:Unit. c) b)) a)");
+}
+
+/// Theorem 11.3.1: the direct and desugared forms have the same types and values.
+#[test]
+fn direct_and_desugared_seqs_agree() {
+    let examples = [
+        r"unit; 0",
+        r"(\x:Unit. x) unit; (\x:Unit. x; true) unit",
+        r"let u = unit in u; u; let _ = 1 in u; iszero 0",
+        r"(\x:Unit. x; \y:Nat. succ y) unit 2",
+        r"0; unit",
+    ];
+    for src in examples.iter() {
+        let direct = parse(src).expect("Cannot parse.");
+        let desugared = desugar_seqs(direct.clone());
+        let direct = Term::from_named(direct, &Context::empty()).expect("Cannot remove names.");
+        let desugared =
+            Term::from_named(desugared, &Context::empty()).expect("Cannot remove names.");
+
+        let direct_type = type_of(&direct, &TypeContext::empty()).ok();
+        let desugared_type = type_of(&desugared, &TypeContext::empty()).ok();
+        assert_eq!(direct_type, desugared_type, "`{}`", src);
+        if direct_type.is_none() {
+            continue;
+        }
+        let (direct, direct_steps) = eval_count(direct, &mut Store::new());
+        let (desugared, desugared_steps) = eval_count(desugared, &mut Store::new());
+        assert_eq!(
+            direct.into_unpositioned_named().to_string(),
+            desugared.into_unpositioned_named().to_string()
+        );
+        assert!(direct_steps <= desugared_steps, "`{}`", src);
+    }
+}
+
+#[test]
+fn options_and_enumerations() {
+    assert_eq!(option(Type::Nat).to_string(), "<none:Unit, some:Nat>");
+    let weekday = enumeration(&["monday", "tuesday", "wednesday", "thursday", "friday"]);
+    assert_eq!(
+        weekday.to_string(),
+        "<monday:Unit, tuesday:Unit, wednesday:Unit, thursday:Unit, friday:Unit>"
+    );
+
+    // `nextBusinessDay` from Section 11.10, without a branch for `friday`.
+    let src = format!(
+        r"\w:{weekday}. case w of
+            <monday=x> => <tuesday=unit> as {weekday}
+          | <tuesday=x> => <wednesday=unit> as {weekday}
+          | <wednesday=x> => <thursday=unit> as {weekday}
+          | <thursday=x> => <friday=unit> as {weekday}",
+        weekday = weekday
+    );
+    let term = Term::from_named(parse(&src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.");
+    let (ty, warnings) = type_of_with_warnings(&term, &TypeContext::empty()).expect("Cannot type.");
+    assert_eq!(ty, arrow(weekday.clone(), weekday));
+    assert_eq!(
+        warnings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        ["1:77: Warning: `case` has no branch for `friday`."]
+    );
+}
@@ -0,0 +1,553 @@
+#[cfg(test)]
+mod test;
+
+use super::store::Store;
+use super::term::unnamed::{
+    App, Ascription, Assign, Bool, Case, CasePattern, Cons, Deref, Fix, Head, If, IsNil, IsZero,
+    Let, Loc, Numeral, Pattern, Pred, Proj, Raise, Record, Ref, Seq, Succ, Tag, Tail, Term, Try,
+    Unit, Zero,
+};
+use crate::chapter_7::evaluator;
+use crate::file_position::FilePositionRange;
+
+/// Takes a single call-by-value step, using the rules of Figures 9-1 and 8-2
+/// and those of Sections 11.2 to 11.12 and Figure 13-1, which read and write `store`.
+/// A `case` without a branch for the label of its value is stuck,
+/// and so are `head` and `tail` of `nil`.
+/// `error` and `raise v` take the place of any term they are evaluated in,
+/// up to the nearest `try` that catches them (Chapter 14).
+pub fn eval1(term: Term, store: &mut Store) -> Option<Term> {
+    match term {
+        Term::App(app) => {
+            let App {
+                position,
+                callee,
+                arg,
+            } = *app;
+            if !callee.is_val() {
+                step_in(callee, store, |callee| {
+                    App {
+                        position,
+                        callee,
+                        arg,
+                    }
+                    .into()
+                })
+            } else if !arg.is_val() {
+                step_in(arg, store, |arg| {
+                    App {
+                        position,
+                        callee,
+                        arg,
+                    }
+                    .into()
+                })
+            } else if let Term::Abs(abs) = callee {
+                Some(abs.apply(&arg))
+            } else {
+                None
+            }
+        }
+        Term::If(if_) => {
+            let If {
+                position,
+                guard,
+                then,
+                else_,
+            } = *if_;
+            match guard {
+                Term::Bool(b) => Some(if b.value { then } else { else_ }),
+                guard => step_in(guard, store, |guard| {
+                    If {
+                        position,
+                        guard,
+                        then,
+                        else_,
+                    }
+                    .into()
+                }),
+            }
+        }
+        Term::Succ(succ) => {
+            let Succ { position, arg } = *succ;
+            step_in(arg, store, |arg| Succ { position, arg }.into())
+        }
+        Term::Pred(pred) => {
+            let Pred { position, arg } = *pred;
+            match arg {
+                Term::Zero(zero) => Some(zero.into()),
+                // E-PredSucc, with `n` standing for `succ` applied to `n - 1`.
+                Term::Numeral(Numeral { position, value }) if value > 1 => Some(
+                    Numeral {
+                        position,
+                        value: value - 1,
+                    }
+                    .into(),
+                ),
+                Term::Numeral(numeral) => Some(
+                    Zero {
+                        position: numeral.position,
+                    }
+                    .into(),
+                ),
+                Term::Succ(succ) if succ.arg.is_numeric_val() => Some(succ.arg),
+                arg => step_in(arg, store, |arg| Pred { position, arg }.into()),
+            }
+        }
+        Term::IsZero(is_zero) => {
+            let IsZero { position, arg } = *is_zero;
+            match arg {
+                Term::Zero(_) => Some(
+                    Bool {
+                        position,
+                        value: true,
+                    }
+                    .into(),
+                ),
+                Term::Numeral(numeral) => Some(
+                    Bool {
+                        position,
+                        value: numeral.value == 0,
+                    }
+                    .into(),
+                ),
+                Term::Succ(succ) if succ.arg.is_numeric_val() => Some(
+                    Bool {
+                        position,
+                        value: false,
+                    }
+                    .into(),
+                ),
+                arg => step_in(arg, store, |arg| IsZero { position, arg }.into()),
+            }
+        }
+        Term::Ascription(ascription) => {
+            let Ascription { position, term, ty } = *ascription;
+            if term.is_val() {
+                Some(term)
+            } else {
+                step_in(term, store, |term| Ascription { position, term, ty }.into())
+            }
+        }
+        Term::Seq(seq) => {
+            let Seq {
+                position,
+                first,
+                second,
+            } = *seq;
+            match first {
+                Term::Unit(_) => Some(second),
+                first => step_in(first, store, |first| {
+                    Seq {
+                        position,
+                        first,
+                        second,
+                    }
+                    .into()
+                }),
+            }
+        }
+        Term::Let(let_) => {
+            let Let {
+                position,
+                pattern,
+                value,
+                body,
+            } = *let_;
+            if value.is_val() {
+                // The `i`th value is substituted under the binders of the `i` variables before it.
+                let values = match_pattern(&pattern, &value)?;
+                Some(
+                    values
+                        .into_iter()
+                        .enumerate()
+                        .rev()
+                        .fold(body, |body, (i, value)| {
+                            body.subst_top(&value.clone().shift(i as i32))
+                        }),
+                )
+            } else {
+                step_in(value, store, |value| {
+                    Let {
+                        position,
+                        pattern,
+                        value,
+                        body,
+                    }
+                    .into()
+                })
+            }
+        }
+        Term::Record(record) => {
+            let Record {
+                position,
+                mut fields,
+            } = record;
+            let i = fields.iter().position(|(_, term)| !term.is_val())?;
+            let (label, term) = fields.remove(i);
+            if term.is_exception() {
+                return Some(term);
+            }
+            let term = eval1(term, store)?;
+            fields.insert(i, (label, term));
+            Some(Record { position, fields }.into())
+        }
+        Term::Proj(proj) => {
+            let Proj {
+                position,
+                term,
+                label,
+            } = *proj;
+            match term {
+                Term::Record(record) if record.fields.iter().all(|(_, term)| term.is_val()) => {
+                    record
+                        .fields
+                        .into_iter()
+                        .find(|(l, _)| l.name == label.name)
+                        .map(|(_, term)| term)
+                }
+                term => step_in(term, store, |term| {
+                    Proj {
+                        position,
+                        term,
+                        label,
+                    }
+                    .into()
+                }),
+            }
+        }
+        Term::Tag(tag) => {
+            let Tag {
+                position,
+                label,
+                term,
+                ty,
+            } = *tag;
+            step_in(term, store, |term| {
+                Tag {
+                    position,
+                    label,
+                    term,
+                    ty,
+                }
+                .into()
+            })
+        }
+        Term::Case(case) => {
+            let Case {
+                position,
+                scrutinee,
+                branches,
+            } = *case;
+            match scrutinee {
+                Term::Tag(tag) if tag.term.is_val() => {
+                    let branch = branches.into_iter().find(|branch| match &branch.pattern {
+                        CasePattern::Tag { label, .. } => label.name == tag.label.name,
+                        CasePattern::Wildcard(_) => true,
+                    })?;
+                    Some(match branch.pattern {
+                        CasePattern::Tag { .. } => branch.body.subst_top(&tag.term),
+                        CasePattern::Wildcard(_) => branch.body,
+                    })
+                }
+                scrutinee => step_in(scrutinee, store, |scrutinee| {
+                    Case {
+                        position,
+                        scrutinee,
+                        branches,
+                    }
+                    .into()
+                }),
+            }
+        }
+        Term::Fix(fix) => {
+            let Fix { position, term } = *fix;
+            match term {
+                Term::Abs(abs) => {
+                    let fix = Fix {
+                        position,
+                        term: Term::Abs(abs.clone()),
+                    };
+                    Some(abs.apply(&fix.into()))
+                }
+                term => step_in(term, store, |term| Fix { position, term }.into()),
+            }
+        }
+        Term::Cons(cons) => {
+            let Cons {
+                position,
+                ty,
+                head,
+                tail,
+            } = *cons;
+            if !head.is_val() {
+                step_in(head, store, |head| {
+                    Cons {
+                        position,
+                        ty,
+                        head,
+                        tail,
+                    }
+                    .into()
+                })
+            } else {
+                step_in(tail, store, |tail| {
+                    Cons {
+                        position,
+                        ty,
+                        head,
+                        tail,
+                    }
+                    .into()
+                })
+            }
+        }
+        Term::IsNil(is_nil) => {
+            let IsNil { position, ty, arg } = *is_nil;
+            match arg {
+                Term::Nil(_) => Some(
+                    Bool {
+                        position,
+                        value: true,
+                    }
+                    .into(),
+                ),
+                arg @ Term::Cons(_) if arg.is_val() => Some(
+                    Bool {
+                        position,
+                        value: false,
+                    }
+                    .into(),
+                ),
+                arg => step_in(arg, store, |arg| IsNil { position, ty, arg }.into()),
+            }
+        }
+        Term::Head(head) => {
+            let Head { position, ty, arg } = *head;
+            match arg {
+                Term::Cons(cons) if cons.head.is_val() && cons.tail.is_val() => Some(cons.head),
+                arg => step_in(arg, store, |arg| Head { position, ty, arg }.into()),
+            }
+        }
+        Term::Tail(tail) => {
+            let Tail { position, ty, arg } = *tail;
+            match arg {
+                Term::Cons(cons) if cons.head.is_val() && cons.tail.is_val() => Some(cons.tail),
+                arg => step_in(arg, store, |arg| Tail { position, ty, arg }.into()),
+            }
+        }
+        Term::Ref(ref_) => {
+            let Ref { position, term } = *ref_;
+            if term.is_val() {
+                let location = store.alloc(term);
+                Some(Loc { position, location }.into())
+            } else {
+                step_in(term, store, |term| Ref { position, term }.into())
+            }
+        }
+        Term::Deref(deref) => {
+            let Deref { position, term } = *deref;
+            match term {
+                Term::Loc(loc) => store.get(loc.location).cloned(),
+                term => step_in(term, store, |term| Deref { position, term }.into()),
+            }
+        }
+        Term::Assign(assign) => {
+            let Assign {
+                position,
+                target,
+                value,
+            } = *assign;
+            if !target.is_val() {
+                step_in(target, store, |target| {
+                    Assign {
+                        position,
+                        target,
+                        value,
+                    }
+                    .into()
+                })
+            } else if !value.is_val() {
+                step_in(value, store, |value| {
+                    Assign {
+                        position,
+                        target,
+                        value,
+                    }
+                    .into()
+                })
+            } else if let Term::Loc(loc) = target {
+                if store.set(loc.location, value) {
+                    Some(Unit { position }.into())
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        }
+        Term::Raise(raise) => {
+            let Raise { position, term } = *raise;
+            if term.is_val() {
+                None
+            } else {
+                step_in(term, store, |term| Raise { position, term }.into())
+            }
+        }
+        Term::Try(try_) => {
+            let Try {
+                position,
+                term,
+                var_name,
+                handler,
+            } = *try_;
+            match (term, var_name) {
+                (term, _) if term.is_val() => Some(term),
+                (Term::Error(_), None) => Some(handler),
+                (Term::Raise(raise), None) if raise.term.is_val() => Some(handler),
+                (Term::Raise(raise), Some(_)) if raise.term.is_val() => {
+                    Some(handler.subst_top(&raise.term))
+                }
+                // A handler that binds the raised value lets `error` through.
+                (error @ Term::Error(_), Some(_)) => Some(error),
+                (term, var_name) => eval1(term, store).map(|term| {
+                    Try {
+                        position,
+                        term,
+                        var_name,
+                        handler,
+                    }
+                    .into()
+                }),
+            }
+        }
+        Term::Var(_)
+        | Term::Error(_)
+        | Term::Loc(_)
+        | Term::Abs(_)
+        | Term::Bool(_)
+        | Term::Zero(_)
+        | Term::Numeral(_)
+        | Term::Unit(_)
+        | Term::Nil(_) => None,
+    }
+}
+
+/// Takes a step in `term`, a subterm of a term that is evaluated first,
+/// and puts the result back with `rebuild`.
+/// If `term` is `error` or `raise v`, it replaces the whole term instead,
+/// like E-AppErr1, E-AppErr2, E-AppRaise1 and E-AppRaise2 do for applications.
+fn step_in(term: Term, store: &mut Store, rebuild: impl FnOnce(Term) -> Term) -> Option<Term> {
+    if term.is_exception() {
+        Some(term)
+    } else {
+        eval1(term, store).map(rebuild)
+    }
+}
+
+/// The values bound to the variables of `pattern` when it is matched against `value`,
+/// as in Exercise 11.8.2, or `None` if `value` lacks one of the labels of `pattern`.
+fn match_pattern<'a>(pattern: &Pattern, value: &'a Term) -> Option<Vec<&'a Term>> {
+    match pattern {
+        Pattern::Var(_) => Some(vec![value]),
+        Pattern::Record(record_pattern) => match value {
+            Term::Record(record) => {
+                let mut values = vec![];
+                for (label, pattern) in &record_pattern.fields {
+                    let (_, field) = record.fields.iter().find(|(l, _)| l.name == label.name)?;
+                    values.extend(match_pattern(pattern, field)?);
+                }
+                Some(values)
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Evaluates `term` until no more rules apply, leaving the locations it allocates in `store`,
+/// which is collected after any step that brings it to its threshold.
+/// Well-typed closed terms without `fix` always reach a value,
+/// but `fix (\x:Nat. x)` evaluates forever, so use `eval_count_with_fuel` for those.
+pub fn eval(term: Term, store: &mut Store) -> Term {
+    eval_count(term, store).0
+}
+
+pub fn eval_count(mut term: Term, store: &mut Store) -> (Term, usize) {
+    let mut i = 0;
+    while let Some(evaluated) = eval1(term.clone(), store) {
+        i += 1;
+        term = evaluated;
+        store.collect_if_needed(&[&term]);
+    }
+    (term, i)
+}
+
+/// How the evaluation of a term ended, given the normal form it reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Value(Term),
+    /// `error`, or `raise v` with the value `v`, that no `try` caught.
+    /// The position is that of the `error` or `raise`.
+    Uncaught {
+        position: FilePositionRange,
+        value: Option<Term>,
+    },
+    /// A normal form that is neither a value nor an exception,
+    /// such as `head[T] nil[T]`.
+    Stuck(Term),
+}
+
+impl Outcome {
+    pub fn of(normal_form: Term) -> Outcome {
+        match normal_form {
+            Term::Error(error) => Outcome::Uncaught {
+                position: error.position,
+                value: None,
+            },
+            Term::Raise(raise) if raise.term.is_val() => Outcome::Uncaught {
+                position: raise.position,
+                value: Some(raise.term),
+            },
+            term if term.is_val() => Outcome::Value(term),
+            term => Outcome::Stuck(term),
+        }
+    }
+}
+
+pub type OutOfFuelErr = evaluator::OutOfFuelErr<Term>;
+
+/// Like `eval_count`, but gives up after `fuel` steps,
+/// like `chapter_7::evaluator::eval_count_with_fuel`.
+/// After each step, the store is collected if it has reached its threshold,
+/// with the term reached so far as the only root.
+/// Once the fuel is spent, the next step is only tried against a copy of the store,
+/// so running out of fuel leaves `store` as the last step left it.
+pub fn eval_count_with_fuel(
+    term: Term,
+    store: &mut Store,
+    fuel: usize,
+) -> Result<(Term, usize), OutOfFuelErr> {
+    eval_count_with_fuel_observing(term, store, fuel, |_, _, _| {})
+}
+
+/// Like `eval_count_with_fuel`, but calls `observe` after each step
+/// with the term reached, the store, and the locations freed
+/// if the store was collected.
+pub fn eval_count_with_fuel_observing(
+    term: Term,
+    store: &mut Store,
+    fuel: usize,
+    mut observe: impl FnMut(&Term, &Store, Option<&[usize]>),
+) -> Result<(Term, usize), OutOfFuelErr> {
+    let mut steps = 0;
+    evaluator::count_steps_with_fuel(term, fuel, |term| {
+        if steps == fuel {
+            return eval1(term.clone(), &mut store.clone());
+        }
+        steps += 1;
+        let evaluated = eval1(term.clone(), store)?;
+        let freed = store.collect_if_needed(&[&evaluated]);
+        observe(&evaluated, store, freed.as_deref());
+        Some(evaluated)
+    })
+}
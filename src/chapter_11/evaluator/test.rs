@@ -0,0 +1,420 @@
+use super::{eval, eval1, eval_count, eval_count_with_fuel, OutOfFuelErr, Outcome};
+use crate::chapter_11::{
+    parse,
+    store::Store,
+    term::unnamed::Term,
+    ty::fits,
+    type_checker::{type_of, type_of_in_store, StoreTyping, TypeContext},
+    Type,
+};
+use crate::chapter_7::term::unnamed::Context;
+use crate::repl::DEFAULT_FUEL;
+
+fn unnamed(src: &str) -> Term {
+    Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.")
+}
+
+fn evaluated(src: &str) -> String {
+    eval(unnamed(src), &mut Store::new())
+        .into_unpositioned_named()
+        .to_string()
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(evaluated("succ (pred 0)"), "1");
+    assert_eq!(evaluated("pred (succ (pred 2))"), "1");
+    assert_eq!(evaluated("iszero (pred 1)"), "true");
+    assert_eq!(evaluated("(\\x:Nat. iszero x) 3"), "false");
+}
+
+#[test]
+fn large_numerals_evaluate_without_deep_terms() {
+    assert_eq!(evaluated("pred 100000000"), "99999999");
+    assert_eq!(evaluated("succ 100000000"), "100000001");
+    assert_eq!(evaluated("iszero 100000000"), "false");
+    assert_eq!(evaluated("pred (pred 2)"), "0");
+    assert_eq!(
+        evaluated("succ 18446744073709551615"),
+        "(succ 18446744073709551615)"
+    );
+}
+
+#[test]
+fn sequencing_discards_unit() {
+    let (term, steps) = eval_count(unnamed(r"(\x:Unit. x) unit; 2"), &mut Store::new());
+    assert_eq!(term.into_unpositioned_named().to_string(), "2");
+    assert_eq!(steps, 2);
+}
+
+#[test]
+fn ascription_evaluates_its_term() {
+    let stepped =
+        eval1(unnamed(r"((\x:Nat. x) 0) as Nat"), &mut Store::new()).expect("Expected a step.");
+    assert_eq!(stepped.into_unpositioned_named().to_string(), "(0 as Nat)");
+    assert_eq!(evaluated(r"((\x:Nat. x) 0) as Nat"), "0");
+}
+
+#[test]
+fn let_substitutes_values() {
+    assert_eq!(
+        evaluated(r"let x = succ 1 in let y = succ x in \_:Unit. y"),
+        r"(\_:Unit. 3)"
+    );
+    let stepped =
+        eval1(unnamed(r"let x = pred 1 in x"), &mut Store::new()).expect("Expected a step.");
+    assert_eq!(
+        stepped.into_unpositioned_named().to_string(),
+        "(let x = 0 in x)"
+    );
+    assert_eq!(
+        evaluated(r"\y:Nat. let x = y in \y:Nat. x"),
+        r"(\y:Nat. (let x = y in (\y':Nat. x)))"
+    );
+}
+
+#[test]
+fn evaluation_preserves_types() {
+    let examples = [
+        r"let f = \x:Nat. succ x in iszero (f (f 0))",
+        r"(\_:Unit. \_:Unit. true) unit",
+        r"let x = (\x:Unit. x) unit in x; x; pred (1 as Nat)",
+    ];
+    for src in examples.iter() {
+        let mut term = unnamed(src);
+        let ty = type_of(&term, &TypeContext::empty()).expect("Expected a type.");
+        while let Some(next) = eval1(term.clone(), &mut Store::new()) {
+            assert_eq!(type_of(&next, &TypeContext::empty()), Ok(ty.clone()));
+            term = next;
+        }
+        assert!(term.is_val(), "`{}` got stuck.", src);
+    }
+}
+
+#[test]
+fn records_evaluate_their_fields_from_left_to_right() {
+    let stepped =
+        eval1(unnamed("{x=1, y=pred 1, z=pred 2}"), &mut Store::new()).expect("Expected a step.");
+    assert_eq!(
+        stepped.into_unpositioned_named().to_string(),
+        "{x=1, y=0, z=(pred 2)}"
+    );
+    assert_eq!(evaluated("{pred 1, {iszero 0}}"), "{0, {true}}");
+    assert_eq!(evaluated("{x=pred 3, y=unit}.x"), "2");
+    assert_eq!(evaluated(r"(\p:{Nat, Nat}. {p.2, p.1}) {1, 2}"), "{2, 1}");
+}
+
+#[test]
+fn record_patterns_bind_from_left_to_right() {
+    assert_eq!(
+        evaluated("let {x=a, y={b, _}} = {y={true, 0}, x=unit} in {b, a}"),
+        "{true, unit}"
+    );
+    // The values are substituted under the binders of the variables before them.
+    assert_eq!(
+        evaluated(r"let {f, x} = {\y:Nat. succ y, 1} in \z:Nat. f x"),
+        r"(\z:Nat. ((\y:Nat. (succ y)) 1))"
+    );
+    assert_eq!(
+        evaluated(r"\w:Nat. let {a, b} = {w, 0} in {b, a}"),
+        r"(\w:Nat. (let {a, b} = {w, 0} in {b, a}))"
+    );
+    assert_eq!(
+        evaluated(r"(\w:Nat. let {a, b} = {w, 0} in {b, a}) 3"),
+        "{0, 3}"
+    );
+}
+
+#[test]
+fn case_takes_the_first_matching_branch() {
+    assert_eq!(
+        evaluated("case inr (succ 0) as Bool + Nat of inl b => 0 | inr n => succ n"),
+        "2"
+    );
+    assert_eq!(
+        evaluated("case <b=pred 1> as <a:Nat, b:Nat> of <a=n> => n | _ => 5 | <b=n> => n"),
+        "5"
+    );
+    assert_eq!(
+        evaluated("<a={pred 1, iszero 0}> as <a:{Nat, Bool}>"),
+        "(<a={0, true}> as <a:{Nat, Bool}>)"
+    );
+}
+
+#[test]
+fn case_without_a_matching_branch_is_stuck() {
+    let term = unnamed("case <b=0> as <a:Nat, b:Nat> of <a=n> => n");
+    assert!(type_of(&term, &TypeContext::empty()).is_ok());
+    assert!(eval1(term, &mut Store::new()).is_none());
+}
+
+#[test]
+fn fix_unfolds_its_function() {
+    let stepped =
+        eval1(unnamed(r"fix (\x:Nat. succ x)"), &mut Store::new()).expect("Expected a step.");
+    assert_eq!(
+        stepped.into_unpositioned_named().to_string(),
+        r"(succ (fix (\x:Nat. (succ x))))"
+    );
+    assert_eq!(
+        evaluated(
+            r"letrec plus:Nat -> Nat -> Nat =
+                \m:Nat. \n:Nat. if iszero m then n else succ (plus (pred m) n)
+              in plus 2 3"
+        ),
+        "5"
+    );
+}
+
+#[test]
+fn divergent_terms_run_out_of_fuel() {
+    let term = unnamed(r"fix (\x:Nat. x)");
+    assert_eq!(type_of(&term, &TypeContext::empty()), Ok(Type::Nat));
+    let err =
+        eval_count_with_fuel(term, &mut Store::new(), DEFAULT_FUEL).expect_err("`fix` diverges.");
+    assert_eq!(err.steps, DEFAULT_FUEL);
+
+    let term = unnamed(r"letrec f:Nat -> Nat = \n:Nat. f (succ n) in iszero (f 0)");
+    let OutOfFuelErr { steps, .. } =
+        eval_count_with_fuel(term, &mut Store::new(), 5).expect_err("`f` diverges.");
+    assert_eq!(steps, 5);
+    assert_eq!(
+        eval_count_with_fuel(unnamed("pred 2"), &mut Store::new(), 1).map(|(_, steps)| steps),
+        Ok(1)
+    );
+}
+
+#[test]
+fn running_out_of_fuel_leaves_the_store_alone() {
+    let mut store = Store::new();
+    let err = eval_count_with_fuel(unnamed("ref 0"), &mut store, 0).expect_err("No fuel.");
+    assert_eq!(err.steps, 0);
+    assert!(store.is_empty());
+
+    let term = unnamed(r"(\r:Ref Nat. ref 1) (ref 0)");
+    let err = eval_count_with_fuel(term, &mut store, 1).expect_err("Two allocations.");
+    assert_eq!(err.steps, 1);
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn lists_evaluate_from_head_to_tail() {
+    assert_eq!(
+        evaluated("[pred 1, succ 1] as List Nat"),
+        "(cons[Nat] 0 (cons[Nat] 2 nil[Nat]))"
+    );
+    assert_eq!(evaluated("isnil[Nat] ([0] as List Nat)"), "false");
+    assert_eq!(evaluated("isnil[Nat] tail[Nat] ([0] as List Nat)"), "true");
+    assert_eq!(
+        evaluated("head[Nat] tail[Nat] ([1, 2, 3] as List Nat)"),
+        "2"
+    );
+    assert_eq!(
+        evaluated(
+            r"letrec sum:List Nat -> Nat -> Nat =
+                \l:List Nat. \acc:Nat.
+                  if isnil[Nat] l then acc else sum (tail[Nat] l) (succ acc)
+              in sum ([4, 5, 6] as List Nat) 0"
+        ),
+        "3"
+    );
+}
+
+#[test]
+fn head_of_nil_is_stuck() {
+    let term = unnamed("succ (head[Nat] (tail[Nat] ([1] as List Nat)))");
+    assert_eq!(type_of(&term, &TypeContext::empty()), Ok(Type::Nat));
+    let stuck = eval(term, &mut Store::new());
+    assert!(!stuck.is_val());
+    assert_eq!(
+        stuck.into_unpositioned_named().to_string(),
+        "(succ (head[Nat] nil[Nat]))"
+    );
+    assert!(eval1(unnamed("tail[Bool] nil[Bool]"), &mut Store::new()).is_none());
+}
+
+fn evaluated_with_store(src: &str) -> (String, String) {
+    let mut store = Store::new();
+    let value = eval(unnamed(src), &mut store);
+    (
+        value.into_unpositioned_named().to_string(),
+        store.to_string(),
+    )
+}
+
+#[test]
+fn references_allocate_read_and_update_the_store() {
+    assert_eq!(
+        evaluated_with_store("ref succ 0"),
+        ("#0".to_string(), "#0 = 1".to_string())
+    );
+    assert_eq!(
+        evaluated_with_store(r"(\r:Ref Nat. r := succ (!r); !r) (ref 0)"),
+        ("1".to_string(), "#0 = 1".to_string())
+    );
+    assert_eq!(
+        evaluated_with_store("let r = ref 0 in let s = ref unit in r := 2; s"),
+        ("#1".to_string(), "#0 = 2, #1 = unit".to_string())
+    );
+}
+
+/// The counter of Section 13.1, whose state lives in the store between calls.
+#[test]
+fn counters_keep_their_state_in_the_store() {
+    assert_eq!(
+        evaluated_with_store(
+            r"let c = ref 0 in
+              let inc = \_:Unit. (c := succ (!c); !c) in
+              let _ = inc unit in let _ = inc unit in {inc unit, !c}"
+        ),
+        ("{3, 3}".to_string(), "#0 = 3".to_string())
+    );
+}
+
+#[test]
+fn aliases_share_their_location() {
+    assert_eq!(
+        evaluated_with_store("let r = ref 5 in let s = r in s := 7; !r"),
+        ("7".to_string(), "#0 = 7".to_string())
+    );
+}
+
+/// The arrays of Section 13.1, as references to functions from indices to values.
+#[test]
+fn arrays_as_references_to_functions() {
+    let (value, store) = evaluated_with_store(
+        r"letrec equal:Nat -> Nat -> Bool = \m:Nat. \n:Nat.
+            if iszero m then iszero n
+            else if iszero n then false
+            else equal (pred m) (pred n)
+          in
+          let newarray = \_:Unit. ref (\n:Nat. 0) in
+          let lookup = \a:Ref (Nat -> Nat). \n:Nat. (!a) n in
+          let update = \a:Ref (Nat -> Nat). \m:Nat. \v:Nat.
+            let oldf = !a in a := (\n:Nat. if equal m n then v else oldf n)
+          in
+          let a = newarray unit in
+          update a 1 7; update a 2 9; update a 1 8;
+          {lookup a 0, lookup a 1, lookup a 2}",
+    );
+    assert_eq!(value, "{0, 8, 9}");
+    assert!(store.starts_with("#0 = "));
+}
+
+/// Theorem 13.5.3: each step preserves the type, given a store typing that grows
+/// with the store.
+#[test]
+fn evaluation_preserves_types_in_the_store() {
+    let examples = [
+        r"let c = ref 0 in (\_:Unit. c := succ (!c); !c) unit",
+        r"let r = ref (ref true) in !r := false; !(!r)",
+        r"(\f:Ref (Nat -> Nat). f := (\n:Nat. succ n); !f 1) (ref (\n:Nat. n))",
+    ];
+    for src in examples.iter() {
+        let mut term = unnamed(src);
+        let ty = type_of(&term, &TypeContext::empty()).expect("Expected a type.");
+        let mut store = Store::new();
+        let mut store_typing = StoreTyping::empty();
+        while let Some(next) = eval1(term.clone(), &mut store) {
+            if let Some(value) = store.get(store_typing.len()) {
+                let value_type = type_of_in_store(value, &TypeContext::empty(), &store_typing)
+                    .expect("Expected a type.");
+                store_typing.push(value_type);
+            }
+            assert_eq!(
+                type_of_in_store(&next, &TypeContext::empty(), &store_typing),
+                Ok(ty.clone()),
+                "`{}`",
+                src
+            );
+            term = next;
+        }
+        assert!(term.is_val(), "`{}` got stuck.", src);
+    }
+}
+
+fn outcome(src: &str) -> Outcome {
+    Outcome::of(eval(unnamed(src), &mut Store::new()))
+}
+
+#[test]
+fn errors_abort_the_terms_around_them() {
+    // E-AppErr1 and E-AppErr2.
+    let stepped = eval1(unnamed("error (succ 0)"), &mut Store::new()).expect("Expected a step.");
+    assert_eq!(stepped.into_unpositioned_named().to_string(), "error");
+    let stepped =
+        eval1(unnamed(r"(\x:Nat. x) error"), &mut Store::new()).expect("Expected a step.");
+    assert_eq!(stepped.into_unpositioned_named().to_string(), "error");
+
+    let src = r"{0, succ (pred error)}";
+    let mut store = Store::new();
+    let (term, steps) = eval_count(unnamed(src), &mut store);
+    assert_eq!(steps, 3);
+    match Outcome::of(term) {
+        Outcome::Uncaught { position, value } => {
+            assert_eq!(position.start.column, 15);
+            assert_eq!(value, None);
+        }
+        other => panic!("Expected an uncaught error, but found {:?}.", other),
+    }
+    assert!(eval1(unnamed("error"), &mut Store::new()).is_none());
+}
+
+#[test]
+fn try_catches_errors_and_raised_values() {
+    assert_eq!(evaluated("try succ error with 5"), "5");
+    assert_eq!(evaluated("try succ 0 with error"), "1");
+    assert_eq!(evaluated("try raise 3 with 5"), "5");
+    assert_eq!(evaluated("try raise (succ 2) with e => pred e"), "2");
+    assert_eq!(
+        evaluated("try (try raise 3 with e => raise succ e) with e => {e}"),
+        "{4}"
+    );
+    assert_eq!(evaluated("try raise raise 2 with e => e"), "2");
+    assert_eq!(
+        evaluated("let r = ref 0 in try (r := 1; error; r := 2) with !r"),
+        "1"
+    );
+    // A handler that binds the raised value does not catch `error`.
+    assert!(matches!(
+        outcome("try error with e => e"),
+        Outcome::Uncaught { value: None, .. }
+    ));
+}
+
+#[test]
+fn uncaught_exceptions_are_distinct_from_stuck_terms() {
+    match outcome(r"(\n:Nat. raise succ n) 2") {
+        Outcome::Uncaught {
+            position,
+            value: Some(value),
+        } => {
+            assert_eq!(position.start.column, 9);
+            assert_eq!(value.into_unpositioned_named().to_string(), "3");
+        }
+        other => panic!("Expected an uncaught exception, but found {:?}.", other),
+    }
+    assert!(matches!(outcome("head[Nat] nil[Nat]"), Outcome::Stuck(_)));
+    assert!(matches!(outcome("succ 0"), Outcome::Value(_)));
+}
+
+/// Exceptions keep the type of a term, since `error` and `raise v` have type `Bot`,
+/// which fits wherever they end up.
+#[test]
+fn exceptions_preserve_types() {
+    let examples = [
+        r"(\x:Nat. iszero x) (pred error)",
+        r"try (\x:Nat. {x, raise x}) 4 with e => {e, 0}",
+        r"if iszero (try error with 1) then true else false",
+    ];
+    for src in examples.iter() {
+        let mut term = unnamed(src);
+        let ty = type_of(&term, &TypeContext::empty()).expect("Expected a type.");
+        while let Some(next) = eval1(term.clone(), &mut Store::new()) {
+            let next_type = type_of(&next, &TypeContext::empty()).expect("Expected a type.");
+            assert!(fits(&next_type, &ty), "`{}`: `{}`", src, next_type);
+            term = next;
+        }
+    }
+}
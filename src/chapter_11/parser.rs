@@ -0,0 +1,959 @@
+#[cfg(test)]
+mod test;
+
+pub mod err {
+    pub use crate::chapter_7::parser::err::TokenOrEof;
+    pub use crate::chapter_7::parser::lexer::{PositionedToken, Token, TokenizationErr};
+
+    use super::super::term::named::Label;
+    use std::fmt;
+
+    #[derive(Clone, Debug)]
+    pub enum ParseErr {
+        Tokenization(TokenizationErr),
+        UnexpectedTokenOrEof(UnexpectedTokenOrEofErr),
+        /// A label that appears twice in the same record, record type, record pattern
+        /// or variant type.
+        /// The position is that of the second occurrence.
+        DuplicateLabel(Label),
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct UnexpectedTokenOrEofErr {
+        pub expected: Vec<Expected>,
+        pub actual: TokenOrEof,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    pub enum Expected {
+        Eof,
+        /// A name that is not a keyword.
+        Ident,
+        Term,
+        Type,
+        /// The label of a record field, which is a name or a number.
+        Label,
+        /// The pattern of a `case` branch.
+        Pattern,
+        Token(Token),
+        Keyword(&'static str),
+    }
+
+    impl fmt::Display for ParseErr {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ParseErr::Tokenization(err) => write!(f, "{}", err),
+                ParseErr::UnexpectedTokenOrEof(err) => write!(f, "{}", err),
+                ParseErr::DuplicateLabel(label) => write!(
+                    f,
+                    "{}: Duplicate label `{}`.",
+                    label.position.start, label.name
+                ),
+            }
+        }
+    }
+
+    impl fmt::Display for UnexpectedTokenOrEofErr {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let expected: Vec<String> = self.expected.iter().map(ToString::to_string).collect();
+            match &self.actual {
+                TokenOrEof::Token(actual) => write!(
+                    f,
+                    "{}: Expected {}, but found `{}`.",
+                    actual.position.start,
+                    expected.join(" or "),
+                    actual.token
+                ),
+                TokenOrEof::Eof => write!(
+                    f,
+                    "Expected {}, but found end of input.",
+                    expected.join(" or ")
+                ),
+            }
+        }
+    }
+
+    impl fmt::Display for Expected {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Expected::Eof => write!(f, "end of input"),
+                Expected::Ident => write!(f, "identifier"),
+                Expected::Term => write!(f, "term"),
+                Expected::Type => write!(f, "type"),
+                Expected::Label => write!(f, "label"),
+                Expected::Pattern => write!(f, "pattern"),
+                Expected::Token(token) => write!(f, "`{}`", token),
+                Expected::Keyword(keyword) => write!(f, "`{}`", keyword),
+            }
+        }
+    }
+}
+
+use super::derived;
+use super::term::named::{
+    Abs, App, Ascription, Assign, Bool, Branch, Case, CasePattern, Cons, Deref, Error, Fix, Head,
+    If, IsNil, IsZero, Label, Let, Nil, Numeral, Pattern, Pred, Proj, Raise, Record, RecordPattern,
+    Ref, Seq, Succ, Tag, Tail, Term, Try, Unit, Var, Zero, WILDCARD,
+};
+use super::ty::{self, Type};
+use crate::chapter_7::parser::lexer::tokenize;
+use crate::file_position::{FilePosition, FilePositionRange, Position};
+use err::*;
+
+/// Names that cannot be used as variables.
+pub const KEYWORDS: [&str; 26] = [
+    "if", "then", "else", "true", "false", "succ", "pred", "iszero", "unit", "as", "let", "inl",
+    "inr", "case", "of", "fix", "nil", "cons", "isnil", "head", "tail", "ref", "error", "raise",
+    "try", "with",
+];
+
+/// Parses a term such as `let {x=a, y=b} = {x=1, y=\_:Unit. 0} in b unit; succ a.1 as Nat`.
+///
+/// From loosest to tightest, the forms are `;`, which associates to the right,
+/// assignment `t1 := t2`, which does not associate,
+/// abstractions, `if`, `let`, `letrec`, `case` and `try`, which extend as far to the right
+/// as possible,
+/// application, which associates to the left, and ascription and projection,
+/// which apply from left to right.
+pub fn parse(src: &str) -> Result<Term, ParseErr> {
+    let tokens = tokenize(src).map_err(ParseErr::Tokenization)?;
+    let mut parser = TokenParser::new(&tokens);
+    let term = parser.consume_term()?;
+    if parser.is_exhausted() {
+        Ok(term)
+    } else {
+        Err(parser.expected_err(vec![Expected::Eof]))
+    }
+}
+
+/// Parses a type such as `(Nat -> Bool) -> {x:Unit, y:Nat + <a:Nat, b:List {}>}`.
+///
+/// `+` binds tighter than `->` and associates to the left, and `List` and `Ref` tighter still.
+pub fn parse_type(src: &str) -> Result<Type, ParseErr> {
+    let tokens = tokenize(src).map_err(ParseErr::Tokenization)?;
+    let mut parser = TokenParser::new(&tokens);
+    let ty = parser.consume_type()?;
+    if parser.is_exhausted() {
+        Ok(ty)
+    } else {
+        Err(parser.expected_err(vec![Expected::Eof]))
+    }
+}
+
+struct TokenParser<'a> {
+    tokens: &'a [PositionedToken],
+    /// The position of the last consumed token.
+    previous: Option<FilePositionRange>,
+}
+
+impl<'a> TokenParser<'a> {
+    fn new(tokens: &'a [PositionedToken]) -> TokenParser<'a> {
+        TokenParser {
+            tokens,
+            previous: None,
+        }
+    }
+
+    fn consume_term(&mut self) -> Result<Term, ParseErr> {
+        let first = self.consume_seq_operand()?;
+        if self.consume_opt_token(&Token::Semicolon).is_some() {
+            let second = self.consume_term()?;
+            Ok(Seq {
+                position: span(&first, &second),
+                first,
+                second,
+            }
+            .into())
+        } else {
+            Ok(first)
+        }
+    }
+
+    /// Consumes an application, followed by `:= t` if it is the target of an assignment.
+    fn consume_seq_operand(&mut self) -> Result<Term, ParseErr> {
+        let target = self.consume_app()?;
+        if self.consume_opt_token(&Token::ColonEquals).is_some() {
+            let value = self.consume_app()?;
+            Ok(Assign {
+                position: span(&target, &value),
+                target,
+                value,
+            }
+            .into())
+        } else {
+            Ok(target)
+        }
+    }
+
+    fn consume_app(&mut self) -> Result<Term, ParseErr> {
+        if let Some(term) = self.consume_opt_prefix_term()? {
+            return Ok(term);
+        }
+        let mut term = match self.consume_opt_app_head()? {
+            Some(head) => head,
+            None => return Err(self.expected_err(vec![Expected::Term])),
+        };
+        loop {
+            let (arg, is_last) = if let Some(arg) = self.consume_opt_postfixed(true)? {
+                (arg, false)
+            } else if let Some(prefix_term) = self.consume_opt_prefix_term()? {
+                (prefix_term, true)
+            } else {
+                return Ok(term);
+            };
+            term = App {
+                position: span(&term, &arg),
+                callee: term,
+                arg,
+            }
+            .into();
+            if is_last {
+                return Ok(term);
+            }
+        }
+    }
+
+    /// Consumes a term that starts with a keyword or `\` and extends as far to the right as possible.
+    fn consume_opt_prefix_term(&mut self) -> Result<Option<Term>, ParseErr> {
+        if let Some(lambda) = self.consume_opt_token(&Token::Lambda) {
+            let param = self.consume_binder()?;
+            self.consume_token(Token::Colon)?;
+            let param_type = self.consume_type()?;
+            self.consume_token(Token::Dot)?;
+            let body = self.consume_term()?;
+            Ok(Some(
+                Abs {
+                    position: FilePositionRange {
+                        start: lambda.start,
+                        end: body.position().end,
+                    },
+                    param,
+                    param_type,
+                    body,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("if") {
+            let guard = self.consume_term()?;
+            self.consume_keyword("then")?;
+            let then = self.consume_term()?;
+            self.consume_keyword("else")?;
+            let else_ = self.consume_term()?;
+            Ok(Some(
+                If {
+                    position: FilePositionRange {
+                        start: keyword.start,
+                        end: else_.position().end,
+                    },
+                    guard,
+                    then,
+                    else_,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("let") {
+            let pattern = self.consume_pattern()?;
+            self.consume_token(Token::Equals)?;
+            let value = self.consume_term()?;
+            self.consume_token(Token::In)?;
+            let body = self.consume_term()?;
+            Ok(Some(
+                Let {
+                    position: FilePositionRange {
+                        start: keyword.start,
+                        end: body.position().end,
+                    },
+                    pattern,
+                    value,
+                    body,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_token(&Token::Letrec) {
+            let var = self.consume_binder()?;
+            self.consume_token(Token::Colon)?;
+            let param_type = self.consume_type()?;
+            self.consume_token(Token::Equals)?;
+            let value = self.consume_term()?;
+            self.consume_token(Token::In)?;
+            let body = self.consume_term()?;
+            let definition = span_from(var.position, &value);
+            Ok(Some(
+                Let {
+                    position: FilePositionRange {
+                        start: keyword.start,
+                        end: body.position().end,
+                    },
+                    pattern: Pattern::Var(var.clone()),
+                    value: Fix {
+                        position: definition,
+                        term: Abs {
+                            position: definition,
+                            param: var,
+                            param_type,
+                            body: value,
+                        }
+                        .into(),
+                    }
+                    .into(),
+                    body,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("try") {
+            let term = self.consume_term()?;
+            self.consume_keyword("with")?;
+            let var = self.consume_opt_handler_binder();
+            let handler = self.consume_term()?;
+            Ok(Some(
+                Try {
+                    position: span_from(keyword, &handler),
+                    term,
+                    var,
+                    handler,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("case") {
+            let scrutinee = self.consume_term()?;
+            self.consume_keyword("of")?;
+            let mut branches = vec![self.consume_branch()?];
+            while self.consume_opt_token(&Token::Pipe).is_some() {
+                branches.push(self.consume_branch()?);
+            }
+            let end = branches[branches.len() - 1].position.end;
+            Ok(Some(
+                Case {
+                    position: FilePositionRange {
+                        start: keyword.start,
+                        end,
+                    },
+                    scrutinee,
+                    branches,
+                }
+                .into(),
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn consume_branch(&mut self) -> Result<Branch, ParseErr> {
+        let start = match self.tokens.first() {
+            Some(token) => token.position.start,
+            None => return Err(self.expected_err(vec![Expected::Pattern])),
+        };
+        let pattern = if self.consume_opt_token(&Token::LAngle).is_some() {
+            let label = match self.consume_opt_label() {
+                Some(label) => label,
+                None => return Err(self.expected_err(vec![Expected::Label])),
+            };
+            self.consume_token(Token::Equals)?;
+            let var = self.consume_binder()?;
+            self.consume_token(Token::RAngle)?;
+            CasePattern::Tag { label, var }
+        } else if let Some(label) = self.consume_opt_sum_label() {
+            let var = self.consume_binder()?;
+            CasePattern::Tag { label, var }
+        } else if let Some(position) = self.consume_opt_keyword(WILDCARD) {
+            CasePattern::Wildcard(position)
+        } else {
+            return Err(self.expected_err(vec![Expected::Pattern]));
+        };
+        self.consume_token(Token::FatArrow)?;
+        let body = self.consume_term()?;
+        Ok(Branch {
+            position: FilePositionRange {
+                start,
+                end: body.position().end,
+            },
+            pattern,
+            body,
+        })
+    }
+
+    /// Consumes the `x =>` of `try t with x => t'`, if there is one.
+    fn consume_opt_handler_binder(&mut self) -> Option<Var> {
+        match self.tokens {
+            [PositionedToken {
+                token: Token::Ident(_),
+                ..
+            }, PositionedToken {
+                token: Token::FatArrow,
+                ..
+            }, ..] => {
+                let var = self.consume_binder().ok()?;
+                self.advance();
+                Some(var)
+            }
+            _ => None,
+        }
+    }
+
+    /// Consumes `inl` or `inr` as a label.
+    fn consume_opt_sum_label(&mut self) -> Option<Label> {
+        for &name in ["inl", "inr"].iter() {
+            if let Some(position) = self.consume_opt_keyword(name) {
+                return Some(Label {
+                    position,
+                    name: name.to_string(),
+                });
+            }
+        }
+        None
+    }
+
+    /// Consumes `as ty` after a tag.
+    fn consume_tag_type(&mut self) -> Result<Type, ParseErr> {
+        self.consume_keyword("as")?;
+        self.consume_type()
+    }
+
+    /// Consumes the first term of an application, which may be `succ`, `pred`, `iszero`,
+    /// `fix`, `ref`, `!`, `raise`, `isnil[T]`, `head[T]` or `tail[T]` followed by its argument,
+    /// or `cons[T]` followed by its two arguments.
+    fn consume_opt_app_head(&mut self) -> Result<Option<Term>, ParseErr> {
+        if let Some(keyword) = self.consume_opt_keyword("cons") {
+            let ty = self.consume_element_type()?;
+            let head = self.consume_arith_arg()?;
+            let tail = self.consume_arith_arg()?;
+            Ok(Some(
+                Cons {
+                    position: span_from(keyword, &tail),
+                    ty,
+                    head,
+                    tail,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("isnil") {
+            let ty = self.consume_element_type()?;
+            let arg = self.consume_arith_arg()?;
+            Ok(Some(
+                IsNil {
+                    position: span_from(keyword, &arg),
+                    ty,
+                    arg,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("head") {
+            let ty = self.consume_element_type()?;
+            let arg = self.consume_arith_arg()?;
+            Ok(Some(
+                Head {
+                    position: span_from(keyword, &arg),
+                    ty,
+                    arg,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("tail") {
+            let ty = self.consume_element_type()?;
+            let arg = self.consume_arith_arg()?;
+            Ok(Some(
+                Tail {
+                    position: span_from(keyword, &arg),
+                    ty,
+                    arg,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("fix") {
+            let term = self.consume_arith_arg()?;
+            Ok(Some(
+                Fix {
+                    position: span_from(keyword, &term),
+                    term,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("ref") {
+            let term = self.consume_arith_arg()?;
+            Ok(Some(
+                Ref {
+                    position: span_from(keyword, &term),
+                    term,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("raise") {
+            let term = self.consume_arith_arg()?;
+            Ok(Some(
+                Raise {
+                    position: span_from(keyword, &term),
+                    term,
+                }
+                .into(),
+            ))
+        } else if let Some(bang) = self.consume_opt_token(&Token::Bang) {
+            let term = self.consume_arith_arg()?;
+            Ok(Some(
+                Deref {
+                    position: span_from(bang, &term),
+                    term,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("succ") {
+            let arg = self.consume_arith_arg()?;
+            Ok(Some(
+                Succ {
+                    position: span_from(keyword, &arg),
+                    arg,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("pred") {
+            let arg = self.consume_arith_arg()?;
+            Ok(Some(
+                Pred {
+                    position: span_from(keyword, &arg),
+                    arg,
+                }
+                .into(),
+            ))
+        } else if let Some(keyword) = self.consume_opt_keyword("iszero") {
+            let arg = self.consume_arith_arg()?;
+            Ok(Some(
+                IsZero {
+                    position: span_from(keyword, &arg),
+                    arg,
+                }
+                .into(),
+            ))
+        } else if let Some(label) = self.consume_opt_sum_label() {
+            let term = match self.consume_opt_postfixed(false)? {
+                Some(term) => term,
+                None => return Err(self.expected_err(vec![Expected::Term])),
+            };
+            let ty = self.consume_tag_type()?;
+            Ok(Some(
+                Tag {
+                    position: FilePositionRange {
+                        start: label.position.start,
+                        end: self.previous_end(),
+                    },
+                    label,
+                    term,
+                    ty,
+                }
+                .into(),
+            ))
+        } else {
+            self.consume_opt_postfixed(true)
+        }
+    }
+
+    fn consume_arith_arg(&mut self) -> Result<Term, ParseErr> {
+        match self.consume_opt_app_head()? {
+            Some(arg) => Ok(arg),
+            None => Err(self.expected_err(vec![Expected::Term])),
+        }
+    }
+
+    /// Consumes an atom followed by any number of projections `.l`
+    /// and, if `ascribe`, ascriptions `as T`.
+    fn consume_opt_postfixed(&mut self, ascribe: bool) -> Result<Option<Term>, ParseErr> {
+        let mut term = match self.consume_opt_atom()? {
+            Some(atom) => atom,
+            None => return Ok(None),
+        };
+        loop {
+            if ascribe && self.consume_opt_keyword("as").is_some() {
+                let ty = self.consume_type()?;
+                term = Ascription {
+                    position: FilePositionRange {
+                        start: term.position().start,
+                        end: self.previous_end(),
+                    },
+                    term,
+                    ty,
+                }
+                .into();
+            } else if self.consume_opt_token(&Token::Dot).is_some() {
+                let label = match self.consume_opt_label() {
+                    Some(label) => label,
+                    None => return Err(self.expected_err(vec![Expected::Label])),
+                };
+                term = Proj {
+                    position: FilePositionRange {
+                        start: term.position().start,
+                        end: label.position.end,
+                    },
+                    term,
+                    label,
+                }
+                .into();
+            } else {
+                return Ok(Some(term));
+            }
+        }
+    }
+
+    fn consume_opt_atom(&mut self) -> Result<Option<Term>, ParseErr> {
+        for &(keyword, value) in [("true", true), ("false", false)].iter() {
+            if let Some(position) = self.consume_opt_keyword(keyword) {
+                return Ok(Some(Bool { position, value }.into()));
+            }
+        }
+        if let Some(position) = self.consume_opt_keyword("unit") {
+            Ok(Some(Unit { position }.into()))
+        } else if let Some(position) = self.consume_opt_keyword("error") {
+            Ok(Some(Error { position }.into()))
+        } else if let Some(keyword) = self.consume_opt_keyword("nil") {
+            let ty = self.consume_element_type()?;
+            Ok(Some(
+                Nil {
+                    position: FilePositionRange {
+                        start: keyword.start,
+                        end: self.previous_end(),
+                    },
+                    ty,
+                }
+                .into(),
+            ))
+        } else if let Some(l_bracket) = self.consume_opt_token(&Token::LBracket) {
+            let mut elements = vec![];
+            if self.consume_opt_token(&Token::RBracket).is_none() {
+                loop {
+                    elements.push(self.consume_term()?);
+                    if self.consume_opt_token(&Token::RBracket).is_some() {
+                        break;
+                    }
+                    if self.consume_opt_token(&Token::Comma).is_none() {
+                        return Err(self.expected_err(vec![
+                            Expected::Token(Token::Comma),
+                            Expected::Token(Token::RBracket),
+                        ]));
+                    }
+                }
+            }
+            self.consume_keyword("as")?;
+            self.consume_keyword("List")?;
+            let ty = self.consume_atomic_type()?;
+            let position = FilePositionRange {
+                start: l_bracket.start,
+                end: self.previous_end(),
+            };
+            Ok(Some(derived::list(position, ty, elements)))
+        } else if let Some((position, value)) = self.consume_opt_number() {
+            Ok(Some(if value == 0 {
+                Zero { position }.into()
+            } else {
+                Numeral { position, value }.into()
+            }))
+        } else if let Some(var) = self.consume_opt_var() {
+            Ok(Some(var.into()))
+        } else if self.consume_opt_token(&Token::LParen).is_some() {
+            let term = self.consume_term()?;
+            self.consume_token(Token::RParen)?;
+            Ok(Some(term))
+        } else if let Some(l_angle) = self.consume_opt_token(&Token::LAngle) {
+            let label = match self.consume_opt_label() {
+                Some(label) => label,
+                None => return Err(self.expected_err(vec![Expected::Label])),
+            };
+            self.consume_token(Token::Equals)?;
+            let term = self.consume_term()?;
+            self.consume_token(Token::RAngle)?;
+            let ty = self.consume_tag_type()?;
+            Ok(Some(
+                Tag {
+                    position: FilePositionRange {
+                        start: l_angle.start,
+                        end: self.previous_end(),
+                    },
+                    label,
+                    term,
+                    ty,
+                }
+                .into(),
+            ))
+        } else if let Some(l_brace) = self.consume_opt_token(&Token::LBrace) {
+            let fields = self.consume_fields(Token::Equals, Token::RBrace, Self::consume_term)?;
+            Ok(Some(
+                Record {
+                    position: FilePositionRange {
+                        start: l_brace.start,
+                        end: self.previous_end(),
+                    },
+                    fields,
+                }
+                .into(),
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Consumes the fields of a record, record type, record pattern or variant type
+    /// up to and including `closing`. A field is `label<separator>x` or just `x`,
+    /// in which case its label is its position, counting from `1`.
+    fn consume_fields<T>(
+        &mut self,
+        separator: Token,
+        closing: Token,
+        consume_field: fn(&mut Self) -> Result<T, ParseErr>,
+    ) -> Result<Vec<(Label, T)>, ParseErr> {
+        let mut fields: Vec<(Label, T)> = vec![];
+        if self.consume_opt_token(&closing).is_some() {
+            return Ok(fields);
+        }
+        loop {
+            let is_labeled = self.tokens.len() >= 2
+                && matches!(self.tokens[0].token, Token::Ident(_) | Token::Number(_))
+                && self.tokens[1].token == separator;
+            let label = if is_labeled {
+                let label = self
+                    .consume_opt_label()
+                    .expect("Checked that the next token is a label.");
+                self.advance();
+                Some(label)
+            } else {
+                None
+            };
+            let start = self.tokens.first().map(|token| token.position.start);
+            let field = consume_field(self)?;
+            let label = match (label, start) {
+                (Some(label), _) => label,
+                (None, Some(start)) => Label {
+                    position: FilePositionRange {
+                        start,
+                        end: self.previous_end(),
+                    },
+                    name: (fields.len() + 1).to_string(),
+                },
+                (None, None) => unreachable!("Consumed a field without tokens."),
+            };
+            if fields.iter().any(|(l, _)| l.name == label.name) {
+                return Err(ParseErr::DuplicateLabel(label));
+            }
+            fields.push((label, field));
+            if self.consume_opt_token(&closing).is_some() {
+                return Ok(fields);
+            }
+            if self.consume_opt_token(&Token::Comma).is_none() {
+                return Err(self.expected_err(vec![
+                    Expected::Token(Token::Comma),
+                    Expected::Token(closing),
+                ]));
+            }
+        }
+    }
+
+    fn consume_opt_label(&mut self) -> Option<Label> {
+        let name = match self.tokens.first().map(|token| &token.token) {
+            Some(Token::Ident(name)) => name.clone(),
+            Some(Token::Number(n)) => n.to_string(),
+            _ => return None,
+        };
+        let position = self.advance();
+        Some(Label { position, name })
+    }
+
+    /// Consumes a binder or a record pattern such as `{x=a, y={b, _}}`.
+    fn consume_pattern(&mut self) -> Result<Pattern, ParseErr> {
+        if let Some(l_brace) = self.consume_opt_token(&Token::LBrace) {
+            let fields =
+                self.consume_fields(Token::Equals, Token::RBrace, Self::consume_pattern)?;
+            Ok(Pattern::Record(RecordPattern {
+                position: FilePositionRange {
+                    start: l_brace.start,
+                    end: self.previous_end(),
+                },
+                fields,
+            }))
+        } else {
+            match self.consume_binder() {
+                Ok(var) => Ok(Pattern::Var(var)),
+                Err(_) => {
+                    Err(self.expected_err(vec![Expected::Ident, Expected::Token(Token::LBrace)]))
+                }
+            }
+        }
+    }
+
+    fn consume_type(&mut self) -> Result<Type, ParseErr> {
+        let mut param = self.consume_atomic_type()?;
+        while self.consume_opt_token(&Token::Plus).is_some() {
+            param = ty::sum(param, self.consume_atomic_type()?);
+        }
+        if self.consume_opt_token(&Token::Arrow).is_some() {
+            Ok(ty::arrow(param, self.consume_type()?))
+        } else {
+            Ok(param)
+        }
+    }
+
+    fn consume_atomic_type(&mut self) -> Result<Type, ParseErr> {
+        for &(name, ref ty) in [
+            ("Bool", Type::Bool),
+            ("Nat", Type::Nat),
+            ("Unit", Type::Unit),
+            ("Top", Type::Top),
+            ("Bot", Type::Bot),
+        ]
+        .iter()
+        {
+            if self.consume_opt_keyword(name).is_some() {
+                return Ok(ty.clone());
+            }
+        }
+        if self.consume_opt_keyword("List").is_some() {
+            Ok(ty::list(self.consume_atomic_type()?))
+        } else if self.consume_opt_keyword("Ref").is_some() {
+            Ok(ty::reference(self.consume_atomic_type()?))
+        } else if self.consume_opt_token(&Token::LParen).is_some() {
+            let ty = self.consume_type()?;
+            self.consume_token(Token::RParen)?;
+            Ok(ty)
+        } else if self.consume_opt_token(&Token::LBrace).is_some() {
+            let fields = self.consume_fields(Token::Colon, Token::RBrace, Self::consume_type)?;
+            Ok(Type::Record(
+                fields
+                    .into_iter()
+                    .map(|(label, ty)| (label.name, ty))
+                    .collect(),
+            ))
+        } else if self.consume_opt_token(&Token::LAngle).is_some() {
+            let fields = self.consume_fields(Token::Colon, Token::RAngle, Self::consume_type)?;
+            Ok(Type::Variant(
+                fields
+                    .into_iter()
+                    .map(|(label, ty)| (label.name, ty))
+                    .collect(),
+            ))
+        } else {
+            Err(self.expected_err(vec![Expected::Type]))
+        }
+    }
+
+    /// Consumes the `[T]` after `nil`, `cons`, `isnil`, `head` and `tail`.
+    fn consume_element_type(&mut self) -> Result<Type, ParseErr> {
+        self.consume_token(Token::LBracket)?;
+        let ty = self.consume_type()?;
+        self.consume_token(Token::RBracket)?;
+        Ok(ty)
+    }
+
+    /// Consumes the name bound by an abstraction or `let`, which may be the wildcard `_`.
+    fn consume_binder(&mut self) -> Result<Var, ParseErr> {
+        if let Some(position) = self.consume_opt_keyword(WILDCARD) {
+            return Ok(Var {
+                position,
+                name: WILDCARD.to_string(),
+            });
+        }
+        match self.consume_opt_var() {
+            Some(var) => Ok(var),
+            None => Err(self.expected_err(vec![Expected::Ident])),
+        }
+    }
+
+    fn consume_opt_var(&mut self) -> Option<Var> {
+        match self.tokens.first() {
+            Some(PositionedToken {
+                token: Token::Ident(name),
+                position,
+            }) if !KEYWORDS.contains(&name.as_str()) && name != WILDCARD => {
+                let var = Var {
+                    position: *position,
+                    name: name.clone(),
+                };
+                self.advance();
+                Some(var)
+            }
+            _ => None,
+        }
+    }
+
+    fn consume_opt_number(&mut self) -> Option<(FilePositionRange, u64)> {
+        match self.tokens.first() {
+            Some(PositionedToken {
+                token: Token::Number(n),
+                position,
+            }) => {
+                let n = *n;
+                let position = *position;
+                self.advance();
+                Some((position, n))
+            }
+            _ => None,
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: &'static str) -> Result<FilePositionRange, ParseErr> {
+        match self.consume_opt_keyword(keyword) {
+            Some(position) => Ok(position),
+            None => Err(self.expected_err(vec![Expected::Keyword(keyword)])),
+        }
+    }
+
+    fn consume_opt_keyword(&mut self, keyword: &str) -> Option<FilePositionRange> {
+        match self.tokens.first() {
+            Some(PositionedToken {
+                token: Token::Ident(name),
+                ..
+            }) if name == keyword => Some(self.advance()),
+            _ => None,
+        }
+    }
+
+    fn consume_token(&mut self, expected: Token) -> Result<FilePositionRange, ParseErr> {
+        match self.consume_opt_token(&expected) {
+            Some(position) => Ok(position),
+            None => Err(self.expected_err(vec![Expected::Token(expected)])),
+        }
+    }
+
+    fn consume_opt_token(&mut self, expected: &Token) -> Option<FilePositionRange> {
+        match self.tokens.first() {
+            Some(ptoken) if ptoken.token == *expected => Some(self.advance()),
+            _ => None,
+        }
+    }
+
+    /// Consumes the next token, which must exist, and returns its position.
+    fn advance(&mut self) -> FilePositionRange {
+        let position = self.tokens[0].position;
+        self.tokens = &self.tokens[1..];
+        self.previous = Some(position);
+        position
+    }
+
+    /// The end of the last consumed token.
+    fn previous_end(&self) -> FilePosition {
+        self.previous
+            .expect("Called `previous_end` before consuming a token.")
+            .end
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn expected_err(&self, expected: Vec<Expected>) -> ParseErr {
+        ParseErr::UnexpectedTokenOrEof(UnexpectedTokenOrEofErr {
+            expected,
+            actual: match self.tokens.first() {
+                Some(token) => TokenOrEof::Token(token.clone()),
+                None => TokenOrEof::Eof,
+            },
+        })
+    }
+}
+
+fn span(first: &Term, last: &Term) -> FilePositionRange {
+    FilePositionRange {
+        start: first.position().start,
+        end: last.position().end,
+    }
+}
+
+fn span_from(keyword: FilePositionRange, last: &Term) -> FilePositionRange {
+    FilePositionRange {
+        start: keyword.start,
+        end: last.position().end,
+    }
+}
@@ -0,0 +1,226 @@
+use super::{parse, parse_type};
+use crate::chapter_11::term::named::{Numeral, Term};
+use crate::chapter_11::ty::{arrow, list, reference, sum, tuple, Type};
+use crate::file_position::Position;
+
+fn parsed(src: &str) -> String {
+    parse(src).expect("Cannot parse.").to_string()
+}
+
+#[test]
+fn base_types() {
+    assert_eq!(
+        parse_type("Nat -> Unit -> Bool").expect("Cannot parse."),
+        arrow(Type::Nat, arrow(Type::Unit, Type::Bool))
+    );
+    assert_eq!(parsed("unit"), "unit");
+    assert_eq!(parsed("3"), "3");
+    assert_eq!(parsed("succ succ 0"), "2");
+    assert_eq!(parsed("succ pred 0"), "(succ (pred 0))");
+    assert_eq!(parsed("iszero pred x"), "(iszero (pred x))");
+    assert_eq!(parsed("f (succ x) 0"), "((f (succ x)) 0)");
+}
+
+#[test]
+fn numerals_are_single_nodes() {
+    let term = parse("2").expect("Cannot parse.");
+    assert!(matches!(term, Term::Numeral(Numeral { value: 2, .. })));
+    assert_eq!(term.as_numeral(), Some(2));
+    assert_eq!(term.position().end.column, 1);
+    assert!(matches!(parse("0"), Ok(Term::Zero(_))));
+    assert_eq!(parsed("succ 100000000"), "100000001");
+}
+
+#[test]
+fn sequences_associate_to_the_right() {
+    assert_eq!(parsed("a; b; c"), "(a; (b; c))");
+    assert_eq!(parsed(r"\x:Unit. x; x"), r"(\x:Unit. (x; x))");
+    assert_eq!(parsed(r"(\x:Unit. x); x"), r"((\x:Unit. x); x)");
+}
+
+#[test]
+fn ascription_binds_tighter_than_application() {
+    assert_eq!(parsed("f x as Nat"), "(f (x as Nat))");
+    assert_eq!(parsed("(f x) as Nat -> Nat"), "((f x) as Nat -> Nat)");
+    assert_eq!(parsed("x as Nat as Nat"), "((x as Nat) as Nat)");
+    let src = "f (x) as Nat";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().end.index, src.len());
+}
+
+#[test]
+fn let_and_wildcards() {
+    assert_eq!(
+        parsed(r"let x = 1 in let _ = x in succ x"),
+        r"(let x = 1 in (let _ = x in (succ x)))"
+    );
+    assert_eq!(parsed(r"\_:Unit. 0"), r"(\_:Unit. 0)");
+    assert_eq!(
+        parse(r"\_:Unit. _")
+            .expect_err("`_` is not a term.")
+            .to_string(),
+        "1:10: Expected term, but found `_`."
+    );
+    assert_eq!(
+        parse(r"let x 1 in x")
+            .expect_err("`=` is missing.")
+            .to_string(),
+        "1:7: Expected `=`, but found `1`."
+    );
+}
+
+#[test]
+fn records_tuples_and_projections() {
+    assert_eq!(parsed("{x=1, y=true}"), "{x=1, y=true}");
+    assert_eq!(parsed("{1, {unit}}"), "{1, {unit}}");
+    assert_eq!(parsed("{2=a, 1=b}"), "{2=a, 1=b}");
+    assert_eq!(parsed("{}"), "{}");
+    assert_eq!(parsed("f p.1.x"), "(f p.1.x)");
+    assert_eq!(parsed("p.1 as Nat"), "(p.1 as Nat)");
+    assert_eq!(parsed("p as {Nat, Nat}.2"), "(p as {Nat, Nat}).2");
+    assert_eq!(
+        parse_type("{x:Nat, y:{Bool, Unit}} -> {}").expect("Cannot parse."),
+        arrow(
+            Type::Record(vec![
+                ("x".to_string(), Type::Nat),
+                ("y".to_string(), tuple(vec![Type::Bool, Type::Unit])),
+            ]),
+            Type::Record(vec![])
+        )
+    );
+}
+
+#[test]
+fn record_patterns() {
+    assert_eq!(
+        parsed("let {x=a, y={b, _}} = r in a"),
+        "(let {x=a, y={b, _}} = r in a)"
+    );
+    let err = parse("let {x=a b} = r in a").expect_err("Expected `,` or `}`.");
+    assert_eq!(err.to_string(), "1:10: Expected `,` or `}`, but found `b`.");
+}
+
+#[test]
+fn labels_must_be_distinct() {
+    let err = parse("{x=1, y=2, x=3}").expect_err("`x` appears twice.");
+    assert_eq!(err.to_string(), "1:12: Duplicate label `x`.");
+    let err = parse("{a, 1=b}").expect_err("`1` appears twice.");
+    assert_eq!(err.to_string(), "1:5: Duplicate label `1`.");
+    let err = parse_type("{x:Nat, x:Nat}").expect_err("`x` appears twice.");
+    assert_eq!(err.to_string(), "1:9: Duplicate label `x`.");
+    let err = parse("p.").expect_err("The label is missing.");
+    assert_eq!(err.to_string(), "Expected label, but found end of input.");
+}
+
+#[test]
+fn sums_and_variants() {
+    assert_eq!(
+        parse_type("Nat + Bool + Unit -> <a:Nat, b:{}>").expect("Cannot parse."),
+        arrow(
+            sum(sum(Type::Nat, Type::Bool), Type::Unit),
+            Type::Variant(vec![
+                ("a".to_string(), Type::Nat),
+                ("b".to_string(), tuple(vec![])),
+            ])
+        )
+    );
+    assert_eq!(parsed("inl 0 as Nat + Bool"), "(inl 0 as Nat + Bool)");
+    assert_eq!(
+        parsed("f (inl x.1 as Nat + Bool)"),
+        "(f (inl x.1 as Nat + Bool))"
+    );
+    assert_eq!(
+        parsed("<some=succ x> as <none:Unit, some:Nat>"),
+        "(<some=(succ x)> as <none:Unit, some:Nat>)"
+    );
+    assert!(parse("inl 0").is_err());
+}
+
+#[test]
+fn case_extends_to_the_right() {
+    assert_eq!(
+        parsed(r"case x of inl a => a | inr b => \y:Nat. b y"),
+        r"(case x of inl a => a | inr b => (\y:Nat. (b y)))"
+    );
+    assert_eq!(
+        parsed("case f x of <a=_> => 0 | _ => 1"),
+        "(case (f x) of <a=_> => 0 | _ => 1)"
+    );
+    let src = "case x of <a=y> => y";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().end.index, src.len());
+    let err = parse("case x of y => y").expect_err("`y` is not a pattern.");
+    assert_eq!(err.to_string(), "1:11: Expected pattern, but found `y`.");
+}
+
+#[test]
+fn letrec_is_let_with_fix() {
+    assert_eq!(
+        parsed(r"letrec f:Nat -> Nat = \n:Nat. f n in f 0"),
+        r"(let f = (fix (\f:Nat -> Nat. (\n:Nat. (f n)))) in (f 0))"
+    );
+    assert_eq!(parsed(r"fix (\x:Nat. x)"), r"(fix (\x:Nat. x))");
+    assert_eq!(parsed("fix f 0"), "((fix f) 0)");
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        parse_type("List Nat -> List (Nat -> Bool)").expect("Cannot parse."),
+        arrow(list(Type::Nat), list(arrow(Type::Nat, Type::Bool)))
+    );
+    assert_eq!(
+        parsed("cons[Nat] 1 (tail[Nat] l)"),
+        "(cons[Nat] 1 (tail[Nat] l))"
+    );
+    assert_eq!(parsed("isnil[Bool] nil[Bool]"), "(isnil[Bool] nil[Bool])");
+    assert_eq!(
+        parsed("[1, succ x, head[Nat] l] as List Nat"),
+        "(cons[Nat] 1 (cons[Nat] (succ x) (cons[Nat] (head[Nat] l) nil[Nat])))"
+    );
+    assert_eq!(parsed("[] as List Unit"), "nil[Unit]");
+    let src = "f [a, b] as List Nat";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().end.index, src.len());
+    assert!(parse("[1, 2]").is_err());
+    assert!(parse("[1, 2] as Nat").is_err());
+}
+
+#[test]
+fn references() {
+    assert_eq!(
+        parse_type("Ref Nat -> Ref (Nat -> Nat)").expect("Cannot parse."),
+        arrow(reference(Type::Nat), reference(arrow(Type::Nat, Type::Nat)))
+    );
+    assert_eq!(parsed("ref succ 0"), "(ref 1)");
+    assert_eq!(parsed("!r"), "(!r)");
+    assert_eq!(parsed("!f x"), "((!f) x)");
+    assert_eq!(parsed("r := succ (!r); !r"), "((r := (succ (!r))); (!r))");
+    assert_eq!(parsed(r"f x := \y:Nat. y"), r"((f x) := (\y:Nat. y))");
+    let src = "r := !s";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().end.index, src.len());
+    assert!(parse("r := s := 0").is_err());
+}
+
+#[test]
+fn exceptions() {
+    assert_eq!(parsed("succ error"), "(succ error)");
+    assert_eq!(parsed("raise succ 0 "), "(raise 1)");
+    assert_eq!(parsed("f (raise x) y"), "((f (raise x)) y)");
+    assert_eq!(parsed("try f 0 with 1; unit"), "(try (f 0) with (1; unit))");
+    assert_eq!(
+        parsed("try raise 0 with e => succ e"),
+        "(try (raise 0) with e => (succ e))"
+    );
+    assert_eq!(
+        parsed("try try error with _ => 0 with 1"),
+        "(try (try error with _ => 0) with 1)"
+    );
+    let src = "try x with e => e";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().end.index, src.len());
+    let err = parse("try x").expect_err("`with` is missing.");
+    assert_eq!(err.to_string(), "Expected `with`, but found end of input.");
+    assert!(parse(r"\with:Nat. with").is_err());
+}
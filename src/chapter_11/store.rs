@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod test;
+
+use super::term::unnamed::Term;
+use std::fmt;
+
+/// The store of Section 13.3, mapping each location to the value it holds.
+/// Locations are allocated in order, starting from `0`.
+///
+/// Section 13.2 leaves deallocation to a garbage collector,
+/// so locations that can no longer be reached may be freed by `collect`.
+/// A freed location is never reused, so the numbers of the others stay the same.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Store {
+    /// `None` for the locations freed by `collect`.
+    values: Vec<Option<Term>>,
+    /// The number of live locations at which `collect_if_needed` collects, if any.
+    gc_threshold: Option<usize>,
+    /// Grows with the number of locations that survive a collection,
+    /// so that a store whose locations are all reachable is not traced after every step.
+    next_collection: usize,
+    gc_stats: GcStats,
+}
+
+/// What the collections of a store have done so far.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GcStats {
+    pub collections: usize,
+    pub freed: usize,
+}
+
+impl Store {
+    /// A store that is never collected unless `collect` is called.
+    pub fn new() -> Store {
+        Store::default()
+    }
+
+    /// A store that `collect_if_needed` collects once it has `threshold` live locations.
+    pub fn with_gc_threshold(threshold: usize) -> Store {
+        Store {
+            gc_threshold: Some(threshold),
+            next_collection: threshold,
+            ..Store::default()
+        }
+    }
+
+    pub fn gc_threshold(&self) -> Option<usize> {
+        self.gc_threshold
+    }
+
+    pub fn gc_stats(&self) -> GcStats {
+        self.gc_stats
+    }
+
+    /// Stores `value` at a fresh location, as in E-RefV, and returns the location.
+    pub fn alloc(&mut self, value: Term) -> usize {
+        self.values.push(Some(value));
+        self.values.len() - 1
+    }
+
+    /// The value at `location`, or `None` if it was never allocated or has been freed.
+    pub fn get(&self, location: usize) -> Option<&Term> {
+        self.values.get(location).and_then(Option::as_ref)
+    }
+
+    /// Replaces the value at `location`, as in E-Assign,
+    /// returning `false` if there is no such location.
+    pub fn set(&mut self, location: usize, value: Term) -> bool {
+        match self.values.get_mut(location) {
+            Some(Some(old)) => {
+                *old = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The number of live locations.
+    pub fn len(&self) -> usize {
+        self.values.iter().filter(|value| value.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+
+    /// The live locations and their values, in the order they were allocated.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Term)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(location, value)| value.as_ref().map(|value| (location, value)))
+    }
+
+    /// Frees every location that cannot be reached from `roots`,
+    /// either directly or through the values of reachable locations,
+    /// and returns the freed locations in order.
+    ///
+    /// The evaluator substitutes values for variables rather than keeping an environment,
+    /// so its only root is the term being evaluated.
+    pub fn collect(&mut self, roots: &[&Term]) -> Vec<usize> {
+        let mut is_reachable = vec![false; self.values.len()];
+        let mut pending: Vec<usize> = roots.iter().flat_map(|root| root.locations()).collect();
+        while let Some(location) = pending.pop() {
+            if is_reachable[location] {
+                continue;
+            }
+            is_reachable[location] = true;
+            if let Some(value) = &self.values[location] {
+                pending.extend(value.locations());
+            }
+        }
+
+        let mut freed = vec![];
+        for (location, value) in self.values.iter_mut().enumerate() {
+            if value.is_some() && !is_reachable[location] {
+                *value = None;
+                freed.push(location);
+            }
+        }
+        self.gc_stats.collections += 1;
+        self.gc_stats.freed += freed.len();
+        if let Some(threshold) = self.gc_threshold {
+            self.next_collection = threshold.max(2 * self.len());
+        }
+        freed
+    }
+
+    /// Like `collect`, but only once the store has reached its threshold.
+    /// Returns `None` if it has not.
+    pub fn collect_if_needed(&mut self, roots: &[&Term]) -> Option<Vec<usize>> {
+        match self.gc_threshold {
+            Some(_) if self.len() >= self.next_collection => Some(self.collect(roots)),
+            _ => None,
+        }
+    }
+}
+
+/// Writes `#0 = v0, #1 = v1, ...`, naming the variables of each value
+/// and leaving out freed locations.
+impl fmt::Display for Store {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (location, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "#{} = {}",
+                location,
+                value.clone().into_unpositioned_named()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `2 collections freed 5 locations`.
+impl fmt::Display for GcStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} collection{} freed {} location{}",
+            self.collections,
+            if self.collections == 1 { "" } else { "s" },
+            self.freed,
+            if self.freed == 1 { "" } else { "s" }
+        )
+    }
+}
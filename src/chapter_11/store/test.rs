@@ -0,0 +1,100 @@
+use super::{GcStats, Store};
+use crate::chapter_11::{eval, eval_count, parse, term::unnamed::Term};
+use crate::chapter_7::term::unnamed::Context;
+
+fn unnamed(src: &str) -> Term {
+    Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.")
+}
+
+#[test]
+fn unreachable_locations_are_freed() {
+    let mut store = Store::new();
+    let value = eval(
+        unnamed("let a = ref 0 in let b = ref (ref 1) in let c = ref 2 in {a, !b}"),
+        &mut store,
+    );
+    assert_eq!(store.to_string(), "#0 = 0, #1 = 1, #2 = #1, #3 = 2");
+
+    // `b` itself is garbage, but the location it holds is not.
+    assert_eq!(store.collect(&[&value]), [2, 3]);
+    assert_eq!(store.to_string(), "#0 = 0, #1 = 1");
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.get(2), None);
+    assert!(!store.set(3, value.clone()));
+    assert_eq!(
+        store.gc_stats(),
+        GcStats {
+            collections: 1,
+            freed: 2
+        }
+    );
+
+    // Freed locations are not reused.
+    let location = store.alloc(value);
+    assert_eq!(location, 4);
+    assert_eq!(store.collect(&[]), [0, 1, 4]);
+    assert!(store.is_empty());
+    assert_eq!(
+        store.gc_stats().to_string(),
+        "2 collections freed 5 locations"
+    );
+}
+
+#[test]
+fn cycles_are_freed_once_unreachable() {
+    let mut store = Store::new();
+    let value = eval(
+        unnamed(r"let r = ref (\n:Nat. n) in r := (\n:Nat. (!r) n); r"),
+        &mut store,
+    );
+    assert_eq!(store.to_string(), r"#0 = (\n:Nat. ((!#0) n))");
+    assert!(store.collect(&[&value]).is_empty());
+    assert_eq!(store.collect(&[]), [0]);
+}
+
+#[test]
+fn stores_are_collected_at_their_threshold() {
+    let mut store = Store::with_gc_threshold(3);
+    assert_eq!(store.gc_threshold(), Some(3));
+    let (value, steps) = eval_count(
+        unnamed(
+            r"letrec loop:Nat -> Nat = \n:Nat.
+                if iszero n then 0 else let _ = ref n in loop (pred n)
+              in loop 10",
+        ),
+        &mut store,
+    );
+    assert_eq!(value.into_unpositioned_named().to_string(), "0");
+    assert!(steps > 0);
+    assert!(store.len() < 3);
+    assert_eq!(store.gc_stats().freed + store.len(), 10);
+    assert!(store.gc_stats().collections >= 3);
+
+    // Without a threshold, nothing is collected.
+    let mut store = Store::new();
+    store.alloc(value_of("unit"));
+    assert_eq!(store.collect_if_needed(&[]), None);
+    assert_eq!(store.len(), 1);
+}
+
+/// Locations that survive a collection raise the threshold,
+/// so that live locations are not traced after every step.
+#[test]
+fn live_locations_raise_the_threshold() {
+    let mut store = Store::with_gc_threshold(2);
+    // The roots hold the locations `#0` and `#1` of `store`.
+    let a = value_of("ref 0");
+    let b = value_of("let _ = ref 0 in ref 1");
+    store.alloc(value_of("0"));
+    store.alloc(value_of("1"));
+    assert_eq!(store.collect_if_needed(&[&a, &b]), Some(vec![]));
+    store.alloc(value_of("2"));
+    assert_eq!(store.collect_if_needed(&[&a, &b]), None);
+    store.alloc(value_of("3"));
+    assert_eq!(store.collect_if_needed(&[&a, &b]), Some(vec![2, 3]));
+}
+
+fn value_of(src: &str) -> Term {
+    eval(unnamed(src), &mut Store::new())
+}
@@ -0,0 +1,1169 @@
+use super::super::ty::{self, Type};
+use super::unnamed::{self, Term as UnnamedTerm};
+use crate::chapter_7::term::unnamed::Context;
+use crate::file_position::{FilePosition, FilePositionRange, Position};
+use std::fmt;
+
+/// The name of a binder whose variable cannot be referred to, as in `\_:Unit. t`.
+pub const WILDCARD: &str = "_";
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Term {
+    Var(Var),
+    Abs(Box<Abs>),
+    App(Box<App>),
+    Bool(Bool),
+    If(Box<If>),
+    Zero(Zero),
+    Numeral(Numeral),
+    Succ(Box<Succ>),
+    Pred(Box<Pred>),
+    IsZero(Box<IsZero>),
+    Unit(Unit),
+    Ascription(Box<Ascription>),
+    Seq(Box<Seq>),
+    Let(Box<Let>),
+    Record(Record),
+    Proj(Box<Proj>),
+    Tag(Box<Tag>),
+    Case(Box<Case>),
+    Fix(Box<Fix>),
+    Nil(Nil),
+    Cons(Box<Cons>),
+    IsNil(Box<IsNil>),
+    Head(Box<Head>),
+    Tail(Box<Tail>),
+    Ref(Box<Ref>),
+    Deref(Box<Deref>),
+    Assign(Box<Assign>),
+    Loc(Loc),
+    Error(Error),
+    Raise(Box<Raise>),
+    Try(Box<Try>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Var {
+    pub position: FilePositionRange,
+    pub name: String,
+}
+
+/// `\param:param_type. body`, where `param` may be the wildcard `_`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Abs {
+    pub position: FilePositionRange,
+    pub param: Var,
+    pub param_type: Type,
+    pub body: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct App {
+    pub position: FilePositionRange,
+    pub callee: Term,
+    pub arg: Term,
+}
+
+/// `true` or `false`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Bool {
+    pub position: FilePositionRange,
+    pub value: bool,
+}
+
+/// `if guard then then else else_`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct If {
+    pub position: FilePositionRange,
+    pub guard: Term,
+    pub then: Term,
+    pub else_: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Zero {
+    pub position: FilePositionRange,
+}
+
+/// A numeral other than `0`, which stands for `succ` applied `value` times to `0`.
+/// It is a single node, so that a large numeral neither builds a term too deep
+/// to check or evaluate nor takes long to parse.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Numeral {
+    pub position: FilePositionRange,
+    pub value: u64,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Succ {
+    pub position: FilePositionRange,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Pred {
+    pub position: FilePositionRange,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IsZero {
+    pub position: FilePositionRange,
+    pub arg: Term,
+}
+
+/// `unit`, the only value of type `Unit`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Unit {
+    pub position: FilePositionRange,
+}
+
+/// `term as ty`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Ascription {
+    pub position: FilePositionRange,
+    pub term: Term,
+    pub ty: Type,
+}
+
+/// `first; second`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Seq {
+    pub position: FilePositionRange,
+    pub first: Term,
+    pub second: Term,
+}
+
+/// `let pattern = value in body`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Let {
+    pub position: FilePositionRange,
+    pub pattern: Pattern,
+    pub value: Term,
+    pub body: Term,
+}
+
+/// The label of a record field, which is a name or, in tuples, a number.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Label {
+    pub position: FilePositionRange,
+    pub name: String,
+}
+
+/// `{l1=t1, ..., ln=tn}`. The fields of a tuple `{t1, ..., tn}` are labeled `1` to `n`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Record {
+    pub position: FilePositionRange,
+    pub fields: Vec<(Label, Term)>,
+}
+
+/// `term.label`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Proj {
+    pub position: FilePositionRange,
+    pub term: Term,
+    pub label: Label,
+}
+
+/// `<label=term> as ty`, or `inl term as ty` and `inr term as ty` when `ty` is a sum.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Tag {
+    pub position: FilePositionRange,
+    pub label: Label,
+    pub term: Term,
+    pub ty: Type,
+}
+
+/// `case scrutinee of p1 => t1 | ... | pn => tn`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Case {
+    pub position: FilePositionRange,
+    pub scrutinee: Term,
+    pub branches: Vec<Branch>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Branch {
+    pub position: FilePositionRange,
+    pub pattern: CasePattern,
+    pub body: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CasePattern {
+    /// `<label=var>`, or `inl var` and `inr var`. The body is under the binder of `var`.
+    Tag { label: Label, var: Var },
+    /// `_`, which matches every value and binds nothing.
+    Wildcard(FilePositionRange),
+}
+
+impl CasePattern {
+    /// The number of variables bound by `self`.
+    pub fn binders(&self) -> usize {
+        match self {
+            CasePattern::Tag { .. } => 1,
+            CasePattern::Wildcard(_) => 0,
+        }
+    }
+}
+
+impl fmt::Display for CasePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CasePattern::Tag { label, var } if label.name == "inl" || label.name == "inr" => {
+                write!(f, "{} {}", label.name, var.name)
+            }
+            CasePattern::Tag { label, var } => write!(f, "<{}={}>", label.name, var.name),
+            CasePattern::Wildcard(_) => write!(f, "{}", WILDCARD),
+        }
+    }
+}
+
+/// Writes `<label=term> as ty`, using `inl` and `inr` for sums.
+pub(crate) fn fmt_tag(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    term: &dyn fmt::Display,
+    ty: &Type,
+) -> fmt::Result {
+    if ty.as_sum().is_some() && (label == "inl" || label == "inr") {
+        write!(f, "({} {} as {})", label, term, ty)
+    } else {
+        write!(f, "(<{}={}> as {})", label, term, ty)
+    }
+}
+
+/// Writes `(case scrutinee of p1 => t1 | ... | pn => tn)`.
+pub(crate) fn fmt_case<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    scrutinee: &T,
+    branches: &[(&CasePattern, &T)],
+) -> fmt::Result {
+    write!(f, "(case {} of", scrutinee)?;
+    for (i, (pattern, body)) in branches.iter().enumerate() {
+        let separator = if i == 0 { "" } else { " |" };
+        write!(f, "{} {} => {}", separator, pattern, body)?;
+    }
+    write!(f, ")")
+}
+
+/// `fix term`, the fixed point of a function from a type to itself (Section 11.11).
+/// `letrec x:T = t1 in t2` is parsed as `let x = fix (\x:T. t1) in t2`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Fix {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+/// `nil[ty]`, the empty list of elements of type `ty`.
+/// A literal `[t1, ..., tn] as List T` is parsed as `cons[T] t1 (... (cons[T] tn nil[T]))`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Nil {
+    pub position: FilePositionRange,
+    pub ty: Type,
+}
+
+/// `cons[ty] head tail`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Cons {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub head: Term,
+    pub tail: Term,
+}
+
+/// `isnil[ty] arg`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IsNil {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub arg: Term,
+}
+
+/// `head[ty] arg`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Head {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub arg: Term,
+}
+
+/// `tail[ty] arg`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Tail {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub arg: Term,
+}
+
+/// `ref term`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Ref {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+/// `!term`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Deref {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+/// `target := value`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Assign {
+    pub position: FilePositionRange,
+    pub target: Term,
+    pub value: Term,
+}
+
+/// A location in the store, which only appears during evaluation, written `#location`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Loc {
+    pub position: FilePositionRange,
+    pub location: usize,
+}
+
+impl fmt::Display for Loc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.location)
+    }
+}
+
+/// `error`, which aborts the evaluation up to the nearest `try` (Section 14.1).
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Error {
+    pub position: FilePositionRange,
+}
+
+/// `raise term`, which aborts the evaluation with the value of `term` (Section 14.3).
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Raise {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+/// `try term with handler`, which catches `error` and `raise`, as in Figure 14-2,
+/// or `try term with var => handler`, which only catches `raise` and binds `var`
+/// to the raised value, as in Figure 14-3. `var` may be the wildcard `_`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Try {
+    pub position: FilePositionRange,
+    pub term: Term,
+    pub var: Option<Var>,
+    pub handler: Term,
+}
+
+/// What `let` binds its value to, as in Exercise 11.8.2.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Pattern {
+    /// A name, which may be the wildcard `_`.
+    Var(Var),
+    /// `{l1=p1, ..., ln=pn}`, which matches a record with at least the labels `l1` to `ln`.
+    Record(RecordPattern),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct RecordPattern {
+    pub position: FilePositionRange,
+    pub fields: Vec<(Label, Pattern)>,
+}
+
+impl Pattern {
+    /// The variables bound by `self`, from left to right.
+    /// The last one is the innermost binder of the body of `let`.
+    pub fn vars(&self) -> Vec<&Var> {
+        match self {
+            Pattern::Var(var) => vec![var],
+            Pattern::Record(record) => record
+                .fields
+                .iter()
+                .flat_map(|(_, pattern)| pattern.vars())
+                .collect(),
+        }
+    }
+
+    /// Replaces the name of each variable with `f(name)`, from left to right.
+    pub fn map_names(self, f: &mut dyn FnMut(String) -> String) -> Pattern {
+        match self {
+            Pattern::Var(var) => Pattern::Var(Var {
+                name: f(var.name),
+                ..var
+            }),
+            Pattern::Record(record) => Pattern::Record(RecordPattern {
+                fields: record
+                    .fields
+                    .into_iter()
+                    .map(|(label, pattern)| (label, pattern.map_names(f)))
+                    .collect(),
+                ..record
+            }),
+        }
+    }
+}
+
+impl Position for &Pattern {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Pattern::Var(var) => var.position,
+            Pattern::Record(record) => record.position,
+        }
+    }
+}
+
+impl Position for &Label {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Var(var) => write!(f, "{}", var.name),
+            Pattern::Record(record) => {
+                let fields: Vec<_> = record
+                    .fields
+                    .iter()
+                    .map(|(label, pattern)| (label.name.as_str(), pattern))
+                    .collect();
+                ty::fmt_fields(f, &fields, "=")
+            }
+        }
+    }
+}
+
+impl Term {
+    /// `n` if `self` is `succ` applied `n` times to `0` or to a numeral.
+    pub fn as_numeral(&self) -> Option<u64> {
+        match self {
+            Term::Zero(_) => Some(0),
+            Term::Numeral(numeral) => Some(numeral.value),
+            Term::Succ(succ) => succ.arg.as_numeral().and_then(|n| n.checked_add(1)),
+            _ => None,
+        }
+    }
+
+    /// Replaces each immediate subterm `t` with `f(t)`.
+    pub fn map_subterms(self, f: &mut dyn FnMut(Term) -> Term) -> Term {
+        match self {
+            Term::Var(_)
+            | Term::Bool(_)
+            | Term::Zero(_)
+            | Term::Numeral(_)
+            | Term::Unit(_)
+            | Term::Nil(_)
+            | Term::Loc(_)
+            | Term::Error(_) => self,
+            Term::Abs(abs) => Abs {
+                body: f(abs.body),
+                ..*abs
+            }
+            .into(),
+            Term::App(app) => App {
+                callee: f(app.callee),
+                arg: f(app.arg),
+                ..*app
+            }
+            .into(),
+            Term::If(if_) => If {
+                guard: f(if_.guard),
+                then: f(if_.then),
+                else_: f(if_.else_),
+                ..*if_
+            }
+            .into(),
+            Term::Succ(succ) => Succ {
+                arg: f(succ.arg),
+                ..*succ
+            }
+            .into(),
+            Term::Pred(pred) => Pred {
+                arg: f(pred.arg),
+                ..*pred
+            }
+            .into(),
+            Term::IsZero(is_zero) => IsZero {
+                arg: f(is_zero.arg),
+                ..*is_zero
+            }
+            .into(),
+            Term::Ascription(ascription) => Ascription {
+                term: f(ascription.term),
+                ..*ascription
+            }
+            .into(),
+            Term::Seq(seq) => Seq {
+                first: f(seq.first),
+                second: f(seq.second),
+                ..*seq
+            }
+            .into(),
+            Term::Let(let_) => Let {
+                value: f(let_.value),
+                body: f(let_.body),
+                ..*let_
+            }
+            .into(),
+            Term::Record(record) => Record {
+                fields: record
+                    .fields
+                    .into_iter()
+                    .map(|(label, term)| (label, f(term)))
+                    .collect(),
+                ..record
+            }
+            .into(),
+            Term::Proj(proj) => Proj {
+                term: f(proj.term),
+                ..*proj
+            }
+            .into(),
+            Term::Tag(tag) => Tag {
+                term: f(tag.term),
+                ..*tag
+            }
+            .into(),
+            Term::Case(case) => Case {
+                scrutinee: f(case.scrutinee),
+                branches: case
+                    .branches
+                    .into_iter()
+                    .map(|branch| Branch {
+                        body: f(branch.body),
+                        ..branch
+                    })
+                    .collect(),
+                ..*case
+            }
+            .into(),
+            Term::Fix(fix) => Fix {
+                term: f(fix.term),
+                ..*fix
+            }
+            .into(),
+            Term::Cons(cons) => Cons {
+                head: f(cons.head),
+                tail: f(cons.tail),
+                ..*cons
+            }
+            .into(),
+            Term::IsNil(is_nil) => IsNil {
+                arg: f(is_nil.arg),
+                ..*is_nil
+            }
+            .into(),
+            Term::Head(head) => Head {
+                arg: f(head.arg),
+                ..*head
+            }
+            .into(),
+            Term::Tail(tail) => Tail {
+                arg: f(tail.arg),
+                ..*tail
+            }
+            .into(),
+            Term::Ref(ref_) => Ref {
+                term: f(ref_.term),
+                ..*ref_
+            }
+            .into(),
+            Term::Deref(deref) => Deref {
+                term: f(deref.term),
+                ..*deref
+            }
+            .into(),
+            Term::Assign(assign) => Assign {
+                target: f(assign.target),
+                value: f(assign.value),
+                ..*assign
+            }
+            .into(),
+            Term::Raise(raise) => Raise {
+                term: f(raise.term),
+                ..*raise
+            }
+            .into(),
+            Term::Try(try_) => Try {
+                term: f(try_.term),
+                handler: f(try_.handler),
+                ..*try_
+            }
+            .into(),
+        }
+    }
+}
+
+impl From<Var> for Term {
+    fn from(var: Var) -> Term {
+        Term::Var(var)
+    }
+}
+
+impl From<Abs> for Term {
+    fn from(abs: Abs) -> Term {
+        Term::Abs(Box::new(abs))
+    }
+}
+
+impl From<App> for Term {
+    fn from(app: App) -> Term {
+        Term::App(Box::new(app))
+    }
+}
+
+impl From<Bool> for Term {
+    fn from(b: Bool) -> Term {
+        Term::Bool(b)
+    }
+}
+
+impl From<If> for Term {
+    fn from(if_: If) -> Term {
+        Term::If(Box::new(if_))
+    }
+}
+
+impl From<Zero> for Term {
+    fn from(zero: Zero) -> Term {
+        Term::Zero(zero)
+    }
+}
+
+impl From<Numeral> for Term {
+    fn from(numeral: Numeral) -> Term {
+        Term::Numeral(numeral)
+    }
+}
+
+impl From<Succ> for Term {
+    fn from(succ: Succ) -> Term {
+        Term::Succ(Box::new(succ))
+    }
+}
+
+impl From<Pred> for Term {
+    fn from(pred: Pred) -> Term {
+        Term::Pred(Box::new(pred))
+    }
+}
+
+impl From<IsZero> for Term {
+    fn from(is_zero: IsZero) -> Term {
+        Term::IsZero(Box::new(is_zero))
+    }
+}
+
+impl From<Unit> for Term {
+    fn from(unit: Unit) -> Term {
+        Term::Unit(unit)
+    }
+}
+
+impl From<Ascription> for Term {
+    fn from(ascription: Ascription) -> Term {
+        Term::Ascription(Box::new(ascription))
+    }
+}
+
+impl From<Seq> for Term {
+    fn from(seq: Seq) -> Term {
+        Term::Seq(Box::new(seq))
+    }
+}
+
+impl From<Let> for Term {
+    fn from(let_: Let) -> Term {
+        Term::Let(Box::new(let_))
+    }
+}
+
+impl From<Record> for Term {
+    fn from(record: Record) -> Term {
+        Term::Record(record)
+    }
+}
+
+impl From<Proj> for Term {
+    fn from(proj: Proj) -> Term {
+        Term::Proj(Box::new(proj))
+    }
+}
+
+impl From<Tag> for Term {
+    fn from(tag: Tag) -> Term {
+        Term::Tag(Box::new(tag))
+    }
+}
+
+impl From<Case> for Term {
+    fn from(case: Case) -> Term {
+        Term::Case(Box::new(case))
+    }
+}
+
+impl From<Fix> for Term {
+    fn from(fix: Fix) -> Term {
+        Term::Fix(Box::new(fix))
+    }
+}
+
+impl From<Nil> for Term {
+    fn from(nil: Nil) -> Term {
+        Term::Nil(nil)
+    }
+}
+
+impl From<Cons> for Term {
+    fn from(cons: Cons) -> Term {
+        Term::Cons(Box::new(cons))
+    }
+}
+
+impl From<IsNil> for Term {
+    fn from(is_nil: IsNil) -> Term {
+        Term::IsNil(Box::new(is_nil))
+    }
+}
+
+impl From<Head> for Term {
+    fn from(head: Head) -> Term {
+        Term::Head(Box::new(head))
+    }
+}
+
+impl From<Tail> for Term {
+    fn from(tail: Tail) -> Term {
+        Term::Tail(Box::new(tail))
+    }
+}
+
+impl From<Ref> for Term {
+    fn from(ref_: Ref) -> Term {
+        Term::Ref(Box::new(ref_))
+    }
+}
+
+impl From<Deref> for Term {
+    fn from(deref: Deref) -> Term {
+        Term::Deref(Box::new(deref))
+    }
+}
+
+impl From<Assign> for Term {
+    fn from(assign: Assign) -> Term {
+        Term::Assign(Box::new(assign))
+    }
+}
+
+impl From<Loc> for Term {
+    fn from(loc: Loc) -> Term {
+        Term::Loc(loc)
+    }
+}
+
+impl From<Error> for Term {
+    fn from(error: Error) -> Term {
+        Term::Error(error)
+    }
+}
+
+impl From<Raise> for Term {
+    fn from(raise: Raise) -> Term {
+        Term::Raise(Box::new(raise))
+    }
+}
+
+impl From<Try> for Term {
+    fn from(try_: Try) -> Term {
+        Term::Try(Box::new(try_))
+    }
+}
+
+impl Position for &Term {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Term::Var(var) => var.position,
+            Term::Abs(abs) => abs.position,
+            Term::App(app) => app.position,
+            Term::Bool(b) => b.position,
+            Term::If(if_) => if_.position,
+            Term::Zero(zero) => zero.position,
+            Term::Numeral(numeral) => numeral.position,
+            Term::Succ(succ) => succ.position,
+            Term::Pred(pred) => pred.position,
+            Term::IsZero(is_zero) => is_zero.position,
+            Term::Unit(unit) => unit.position,
+            Term::Ascription(ascription) => ascription.position,
+            Term::Seq(seq) => seq.position,
+            Term::Let(let_) => let_.position,
+            Term::Record(record) => record.position,
+            Term::Proj(proj) => proj.position,
+            Term::Tag(tag) => tag.position,
+            Term::Case(case) => case.position,
+            Term::Fix(fix) => fix.position,
+            Term::Nil(nil) => nil.position,
+            Term::Cons(cons) => cons.position,
+            Term::IsNil(is_nil) => is_nil.position,
+            Term::Head(head) => head.position,
+            Term::Tail(tail) => tail.position,
+            Term::Ref(ref_) => ref_.position,
+            Term::Deref(deref) => deref.position,
+            Term::Assign(assign) => assign.position,
+            Term::Loc(loc) => loc.position,
+            Term::Error(error) => error.position,
+            Term::Raise(raise) => raise.position,
+            Term::Try(try_) => try_.position,
+        }
+    }
+}
+
+impl Position for &Var {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl Term {
+    /// Names the variables of `un` using `ctx`, adding primes to binder names
+    /// where they would shadow a name that is still used, like
+    /// `chapter_10::term::named::Term::unpositioned_from_unnamed`.
+    pub fn unpositioned_from_unnamed(un: UnnamedTerm, ctx: &Context) -> Term {
+        let position = dummy_position();
+        let from = |term| Term::unpositioned_from_unnamed(term, ctx);
+        match un {
+            UnnamedTerm::Var(var) => Var {
+                position,
+                name: match ctx.name(var.index) {
+                    Some(name) => name.to_string(),
+                    None => format!("${}", var.index),
+                },
+            }
+            .into(),
+            UnnamedTerm::Abs(abs) => {
+                let unnamed::Abs {
+                    param_name,
+                    param_type,
+                    body,
+                    ..
+                } = *abs;
+                let (param, body_ctx) = unpositioned_binder(param_name, ctx);
+                Abs {
+                    position,
+                    param,
+                    param_type,
+                    body: Term::unpositioned_from_unnamed(body, &body_ctx),
+                }
+                .into()
+            }
+            UnnamedTerm::App(app) => App {
+                position,
+                callee: from(app.callee),
+                arg: from(app.arg),
+            }
+            .into(),
+            UnnamedTerm::Bool(b) => Bool {
+                position,
+                value: b.value,
+            }
+            .into(),
+            UnnamedTerm::If(if_) => If {
+                position,
+                guard: from(if_.guard),
+                then: from(if_.then),
+                else_: from(if_.else_),
+            }
+            .into(),
+            UnnamedTerm::Zero(_) => Zero { position }.into(),
+            UnnamedTerm::Numeral(numeral) => Numeral {
+                position,
+                value: numeral.value,
+            }
+            .into(),
+            UnnamedTerm::Succ(succ) => Succ {
+                position,
+                arg: from(succ.arg),
+            }
+            .into(),
+            UnnamedTerm::Pred(pred) => Pred {
+                position,
+                arg: from(pred.arg),
+            }
+            .into(),
+            UnnamedTerm::IsZero(is_zero) => IsZero {
+                position,
+                arg: from(is_zero.arg),
+            }
+            .into(),
+            UnnamedTerm::Unit(_) => Unit { position }.into(),
+            UnnamedTerm::Ascription(ascription) => Ascription {
+                position,
+                term: from(ascription.term),
+                ty: ascription.ty,
+            }
+            .into(),
+            UnnamedTerm::Seq(seq) => Seq {
+                position,
+                first: from(seq.first),
+                second: from(seq.second),
+            }
+            .into(),
+            UnnamedTerm::Let(let_) => {
+                let unnamed::Let {
+                    pattern,
+                    value,
+                    body,
+                    ..
+                } = *let_;
+                let mut body_ctx = ctx.clone();
+                let pattern = pattern.map_names(&mut |name| {
+                    let (var, ctx) = unpositioned_binder(name, &body_ctx);
+                    body_ctx = ctx;
+                    var.name
+                });
+                Let {
+                    position,
+                    pattern,
+                    value: from(value),
+                    body: Term::unpositioned_from_unnamed(body, &body_ctx),
+                }
+                .into()
+            }
+            UnnamedTerm::Record(record) => Record {
+                position,
+                fields: record
+                    .fields
+                    .into_iter()
+                    .map(|(label, term)| (label, from(term)))
+                    .collect(),
+            }
+            .into(),
+            UnnamedTerm::Proj(proj) => Proj {
+                position,
+                term: from(proj.term),
+                label: proj.label,
+            }
+            .into(),
+            UnnamedTerm::Tag(tag) => Tag {
+                position,
+                label: tag.label,
+                term: from(tag.term),
+                ty: tag.ty,
+            }
+            .into(),
+            UnnamedTerm::Case(case) => {
+                let unnamed::Case {
+                    scrutinee,
+                    branches,
+                    ..
+                } = *case;
+                Case {
+                    position,
+                    scrutinee: from(scrutinee),
+                    branches: branches
+                        .into_iter()
+                        .map(|branch| {
+                            let (pattern, body_ctx) = match branch.pattern {
+                                CasePattern::Tag { label, var } => {
+                                    let (var, body_ctx) = unpositioned_binder(var.name, ctx);
+                                    (CasePattern::Tag { label, var }, body_ctx)
+                                }
+                                CasePattern::Wildcard(_) => {
+                                    (CasePattern::Wildcard(position), ctx.clone())
+                                }
+                            };
+                            Branch {
+                                position,
+                                pattern,
+                                body: Term::unpositioned_from_unnamed(branch.body, &body_ctx),
+                            }
+                        })
+                        .collect(),
+                }
+                .into()
+            }
+            UnnamedTerm::Fix(fix) => Fix {
+                position,
+                term: from(fix.term),
+            }
+            .into(),
+            UnnamedTerm::Nil(nil) => Nil {
+                position,
+                ty: nil.ty,
+            }
+            .into(),
+            UnnamedTerm::Cons(cons) => Cons {
+                position,
+                ty: cons.ty,
+                head: from(cons.head),
+                tail: from(cons.tail),
+            }
+            .into(),
+            UnnamedTerm::IsNil(is_nil) => IsNil {
+                position,
+                ty: is_nil.ty,
+                arg: from(is_nil.arg),
+            }
+            .into(),
+            UnnamedTerm::Head(head) => Head {
+                position,
+                ty: head.ty,
+                arg: from(head.arg),
+            }
+            .into(),
+            UnnamedTerm::Tail(tail) => Tail {
+                position,
+                ty: tail.ty,
+                arg: from(tail.arg),
+            }
+            .into(),
+            UnnamedTerm::Ref(ref_) => Ref {
+                position,
+                term: from(ref_.term),
+            }
+            .into(),
+            UnnamedTerm::Deref(deref) => Deref {
+                position,
+                term: from(deref.term),
+            }
+            .into(),
+            UnnamedTerm::Assign(assign) => Assign {
+                position,
+                target: from(assign.target),
+                value: from(assign.value),
+            }
+            .into(),
+            UnnamedTerm::Loc(loc) => Loc {
+                position,
+                location: loc.location,
+            }
+            .into(),
+            UnnamedTerm::Error(_) => Error { position }.into(),
+            UnnamedTerm::Raise(raise) => Raise {
+                position,
+                term: from(raise.term),
+            }
+            .into(),
+            UnnamedTerm::Try(try_) => {
+                let unnamed::Try {
+                    term,
+                    var_name,
+                    handler,
+                    ..
+                } = *try_;
+                let (var, handler) = match var_name {
+                    Some(name) => {
+                        let (var, handler_ctx) = unpositioned_binder(name, ctx);
+                        let handler = Term::unpositioned_from_unnamed(handler, &handler_ctx);
+                        (Some(var), handler)
+                    }
+                    None => (None, from(handler)),
+                };
+                Try {
+                    position,
+                    term: from(term),
+                    var,
+                    handler,
+                }
+                .into()
+            }
+        }
+    }
+}
+
+/// Primes `name` until it no longer shadows a name in `ctx`,
+/// and returns it with the context of the scope it binds.
+fn unpositioned_binder(mut name: String, ctx: &Context) -> (Var, Context) {
+    if name != WILDCARD {
+        while ctx.index(&name).is_some() {
+            name.push('\'');
+        }
+    }
+    let body_ctx = ctx.clone() + name.clone();
+    let var = Var {
+        position: dummy_position(),
+        name,
+    };
+    (var, body_ctx)
+}
+
+fn dummy_position() -> FilePositionRange {
+    let position = FilePosition {
+        index: 0,
+        column: 0,
+        line: 0,
+    };
+    FilePositionRange {
+        start: position,
+        end: position,
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(n) = self.as_numeral() {
+            return write!(f, "{}", n);
+        }
+        match self {
+            Term::Var(var) => write!(f, "{}", var.name),
+            Term::Abs(abs) => write!(f, "(\\{}:{}. {})", abs.param.name, abs.param_type, abs.body),
+            Term::App(app) => write!(f, "({} {})", app.callee, app.arg),
+            Term::Bool(b) => write!(f, "{}", b.value),
+            Term::If(if_) => write!(f, "(if {} then {} else {})", if_.guard, if_.then, if_.else_),
+            Term::Zero(_) => write!(f, "0"),
+            Term::Numeral(numeral) => write!(f, "{}", numeral.value),
+            Term::Succ(succ) => write!(f, "(succ {})", succ.arg),
+            Term::Pred(pred) => write!(f, "(pred {})", pred.arg),
+            Term::IsZero(is_zero) => write!(f, "(iszero {})", is_zero.arg),
+            Term::Unit(_) => write!(f, "unit"),
+            Term::Ascription(ascription) => {
+                write!(f, "({} as {})", ascription.term, ascription.ty)
+            }
+            Term::Seq(seq) => write!(f, "({}; {})", seq.first, seq.second),
+            Term::Let(let_) => write!(
+                f,
+                "(let {} = {} in {})",
+                let_.pattern, let_.value, let_.body
+            ),
+            Term::Record(record) => {
+                let fields: Vec<_> = record
+                    .fields
+                    .iter()
+                    .map(|(label, term)| (label.name.as_str(), term))
+                    .collect();
+                ty::fmt_fields(f, &fields, "=")
+            }
+            Term::Proj(proj) => write!(f, "{}.{}", proj.term, proj.label.name),
+            Term::Tag(tag) => fmt_tag(f, &tag.label.name, &tag.term, &tag.ty),
+            Term::Case(case) => {
+                let branches: Vec<_> = case
+                    .branches
+                    .iter()
+                    .map(|branch| (&branch.pattern, &branch.body))
+                    .collect();
+                fmt_case(f, &case.scrutinee, &branches)
+            }
+            Term::Fix(fix) => write!(f, "(fix {})", fix.term),
+            Term::Nil(nil) => write!(f, "nil[{}]", nil.ty),
+            Term::Cons(cons) => write!(f, "(cons[{}] {} {})", cons.ty, cons.head, cons.tail),
+            Term::IsNil(is_nil) => write!(f, "(isnil[{}] {})", is_nil.ty, is_nil.arg),
+            Term::Head(head) => write!(f, "(head[{}] {})", head.ty, head.arg),
+            Term::Tail(tail) => write!(f, "(tail[{}] {})", tail.ty, tail.arg),
+            Term::Ref(ref_) => write!(f, "(ref {})", ref_.term),
+            Term::Deref(deref) => write!(f, "(!{})", deref.term),
+            Term::Assign(assign) => write!(f, "({} := {})", assign.target, assign.value),
+            Term::Loc(loc) => write!(f, "{}", loc),
+            Term::Error(_) => write!(f, "error"),
+            Term::Raise(raise) => write!(f, "(raise {})", raise.term),
+            Term::Try(try_) => match &try_.var {
+                Some(var) => write!(
+                    f,
+                    "(try {} with {} => {})",
+                    try_.term, var.name, try_.handler
+                ),
+                None => write!(f, "(try {} with {})", try_.term, try_.handler),
+            },
+        }
+    }
+}
@@ -0,0 +1,993 @@
+use super::super::ty::{self, Type};
+use super::named::{self, Term as NamedTerm};
+use crate::chapter_7::term::unnamed::Context;
+use crate::file_position::{FilePositionRange, Position};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Term {
+    Var(Var),
+    Abs(Box<Abs>),
+    App(Box<App>),
+    Bool(Bool),
+    If(Box<If>),
+    Zero(Zero),
+    Numeral(Numeral),
+    Succ(Box<Succ>),
+    Pred(Box<Pred>),
+    IsZero(Box<IsZero>),
+    Unit(Unit),
+    Ascription(Box<Ascription>),
+    Seq(Box<Seq>),
+    Let(Box<Let>),
+    Record(Record),
+    Proj(Box<Proj>),
+    Tag(Box<Tag>),
+    Case(Box<Case>),
+    Fix(Box<Fix>),
+    Nil(Nil),
+    Cons(Box<Cons>),
+    IsNil(Box<IsNil>),
+    Head(Box<Head>),
+    Tail(Box<Tail>),
+    Ref(Box<Ref>),
+    Deref(Box<Deref>),
+    Assign(Box<Assign>),
+    Loc(Loc),
+    Error(Error),
+    Raise(Box<Raise>),
+    Try(Box<Try>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Var {
+    pub position: FilePositionRange,
+    pub index: usize,
+    pub context_length: usize,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Abs {
+    pub position: FilePositionRange,
+    pub param_name: String,
+    pub param_type: Type,
+    pub body: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct App {
+    pub position: FilePositionRange,
+    pub callee: Term,
+    pub arg: Term,
+}
+
+pub use named::{
+    Bool, CasePattern, Error, Label, Loc, Nil, Numeral, Pattern, RecordPattern, Unit, Zero,
+};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct If {
+    pub position: FilePositionRange,
+    pub guard: Term,
+    pub then: Term,
+    pub else_: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Succ {
+    pub position: FilePositionRange,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Pred {
+    pub position: FilePositionRange,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IsZero {
+    pub position: FilePositionRange,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Ascription {
+    pub position: FilePositionRange,
+    pub term: Term,
+    pub ty: Type,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Seq {
+    pub position: FilePositionRange,
+    pub first: Term,
+    pub second: Term,
+}
+
+/// `let pattern = value in body`. The body is under one binder for each variable
+/// of `pattern`, the last variable being the innermost.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Let {
+    pub position: FilePositionRange,
+    pub pattern: Pattern,
+    pub value: Term,
+    pub body: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Record {
+    pub position: FilePositionRange,
+    pub fields: Vec<(Label, Term)>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Proj {
+    pub position: FilePositionRange,
+    pub term: Term,
+    pub label: Label,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Tag {
+    pub position: FilePositionRange,
+    pub label: Label,
+    pub term: Term,
+    pub ty: Type,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Case {
+    pub position: FilePositionRange,
+    pub scrutinee: Term,
+    pub branches: Vec<Branch>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Fix {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Cons {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub head: Term,
+    pub tail: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IsNil {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Head {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Tail {
+    pub position: FilePositionRange,
+    pub ty: Type,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Ref {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Deref {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Assign {
+    pub position: FilePositionRange,
+    pub target: Term,
+    pub value: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Raise {
+    pub position: FilePositionRange,
+    pub term: Term,
+}
+
+/// `try term with handler`, or `try term with var_name => handler`,
+/// in which case the handler is under one binder.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Try {
+    pub position: FilePositionRange,
+    pub term: Term,
+    pub var_name: Option<String>,
+    pub handler: Term,
+}
+
+/// A branch of `case`. The body is under the binders of `pattern`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Branch {
+    pub position: FilePositionRange,
+    pub pattern: CasePattern,
+    pub body: Term,
+}
+
+impl Term {
+    pub fn shift(self, amount: i32) -> Term {
+        self.map_vars(0, &|var, cutoff| {
+            Term::Var(Var {
+                index: if var.index >= cutoff {
+                    ((var.index as i32) + amount) as usize
+                } else {
+                    var.index
+                },
+                context_length: ((var.context_length as i32) + amount) as usize,
+                ..var
+            })
+        })
+    }
+
+    pub fn subst(self, replacee: usize, replacer: &Term) -> Term {
+        self.map_vars(0, &|var, depth| {
+            if var.index == replacee + depth {
+                replacer.clone().shift(depth as i32)
+            } else {
+                Term::Var(var)
+            }
+        })
+    }
+
+    /// Replaces the variable with index `0` with `value`, which is in the context
+    /// outside the binder, and removes the binder, as in E-AppAbs and E-LetV.
+    pub fn subst_top(self, value: &Term) -> Term {
+        self.subst(0, &value.clone().shift(1)).shift(-1)
+    }
+
+    /// Replaces every variable, passing the number of binders it is under.
+    fn map_vars(self, depth: usize, f: &dyn Fn(Var, usize) -> Term) -> Term {
+        match self {
+            Term::Var(var) => f(var, depth),
+            Term::Abs(abs) => Term::Abs(Box::new(Abs {
+                body: abs.body.map_vars(depth + 1, f),
+                ..*abs
+            })),
+            Term::App(app) => Term::App(Box::new(App {
+                callee: app.callee.map_vars(depth, f),
+                arg: app.arg.map_vars(depth, f),
+                ..*app
+            })),
+            Term::Bool(b) => Term::Bool(b),
+            Term::If(if_) => Term::If(Box::new(If {
+                guard: if_.guard.map_vars(depth, f),
+                then: if_.then.map_vars(depth, f),
+                else_: if_.else_.map_vars(depth, f),
+                ..*if_
+            })),
+            Term::Zero(zero) => Term::Zero(zero),
+            Term::Numeral(numeral) => Term::Numeral(numeral),
+            Term::Succ(succ) => Term::Succ(Box::new(Succ {
+                arg: succ.arg.map_vars(depth, f),
+                ..*succ
+            })),
+            Term::Pred(pred) => Term::Pred(Box::new(Pred {
+                arg: pred.arg.map_vars(depth, f),
+                ..*pred
+            })),
+            Term::IsZero(is_zero) => Term::IsZero(Box::new(IsZero {
+                arg: is_zero.arg.map_vars(depth, f),
+                ..*is_zero
+            })),
+            Term::Unit(unit) => Term::Unit(unit),
+            Term::Ascription(ascription) => Term::Ascription(Box::new(Ascription {
+                term: ascription.term.map_vars(depth, f),
+                ..*ascription
+            })),
+            Term::Seq(seq) => Term::Seq(Box::new(Seq {
+                first: seq.first.map_vars(depth, f),
+                second: seq.second.map_vars(depth, f),
+                ..*seq
+            })),
+            Term::Let(let_) => {
+                let binders = let_.pattern.vars().len();
+                Term::Let(Box::new(Let {
+                    value: let_.value.map_vars(depth, f),
+                    body: let_.body.map_vars(depth + binders, f),
+                    ..*let_
+                }))
+            }
+            Term::Record(record) => Term::Record(Record {
+                fields: record
+                    .fields
+                    .into_iter()
+                    .map(|(label, term)| (label, term.map_vars(depth, f)))
+                    .collect(),
+                ..record
+            }),
+            Term::Proj(proj) => Term::Proj(Box::new(Proj {
+                term: proj.term.map_vars(depth, f),
+                ..*proj
+            })),
+            Term::Tag(tag) => Term::Tag(Box::new(Tag {
+                term: tag.term.map_vars(depth, f),
+                ..*tag
+            })),
+            Term::Case(case) => Term::Case(Box::new(Case {
+                scrutinee: case.scrutinee.map_vars(depth, f),
+                branches: case
+                    .branches
+                    .into_iter()
+                    .map(|branch| Branch {
+                        body: branch.body.map_vars(depth + branch.pattern.binders(), f),
+                        ..branch
+                    })
+                    .collect(),
+                ..*case
+            })),
+            Term::Fix(fix) => Term::Fix(Box::new(Fix {
+                term: fix.term.map_vars(depth, f),
+                ..*fix
+            })),
+            Term::Nil(nil) => Term::Nil(nil),
+            Term::Cons(cons) => Term::Cons(Box::new(Cons {
+                head: cons.head.map_vars(depth, f),
+                tail: cons.tail.map_vars(depth, f),
+                ..*cons
+            })),
+            Term::IsNil(is_nil) => Term::IsNil(Box::new(IsNil {
+                arg: is_nil.arg.map_vars(depth, f),
+                ..*is_nil
+            })),
+            Term::Head(head) => Term::Head(Box::new(Head {
+                arg: head.arg.map_vars(depth, f),
+                ..*head
+            })),
+            Term::Tail(tail) => Term::Tail(Box::new(Tail {
+                arg: tail.arg.map_vars(depth, f),
+                ..*tail
+            })),
+            Term::Ref(ref_) => Term::Ref(Box::new(Ref {
+                term: ref_.term.map_vars(depth, f),
+                ..*ref_
+            })),
+            Term::Deref(deref) => Term::Deref(Box::new(Deref {
+                term: deref.term.map_vars(depth, f),
+                ..*deref
+            })),
+            Term::Assign(assign) => Term::Assign(Box::new(Assign {
+                target: assign.target.map_vars(depth, f),
+                value: assign.value.map_vars(depth, f),
+                ..*assign
+            })),
+            Term::Loc(loc) => Term::Loc(loc),
+            Term::Error(error) => Term::Error(error),
+            Term::Raise(raise) => Term::Raise(Box::new(Raise {
+                term: raise.term.map_vars(depth, f),
+                ..*raise
+            })),
+            Term::Try(try_) => {
+                let binders = if try_.var_name.is_some() { 1 } else { 0 };
+                Term::Try(Box::new(Try {
+                    term: try_.term.map_vars(depth, f),
+                    handler: try_.handler.map_vars(depth + binders, f),
+                    ..*try_
+                }))
+            }
+        }
+    }
+
+    /// The immediate subterms, from left to right.
+    pub fn subterms(&self) -> Vec<&Term> {
+        match self {
+            Term::Var(_)
+            | Term::Bool(_)
+            | Term::Zero(_)
+            | Term::Numeral(_)
+            | Term::Unit(_)
+            | Term::Nil(_)
+            | Term::Loc(_)
+            | Term::Error(_) => vec![],
+            Term::Abs(abs) => vec![&abs.body],
+            Term::App(app) => vec![&app.callee, &app.arg],
+            Term::If(if_) => vec![&if_.guard, &if_.then, &if_.else_],
+            Term::Succ(succ) => vec![&succ.arg],
+            Term::Pred(pred) => vec![&pred.arg],
+            Term::IsZero(is_zero) => vec![&is_zero.arg],
+            Term::Ascription(ascription) => vec![&ascription.term],
+            Term::Seq(seq) => vec![&seq.first, &seq.second],
+            Term::Let(let_) => vec![&let_.value, &let_.body],
+            Term::Record(record) => record.fields.iter().map(|(_, term)| term).collect(),
+            Term::Proj(proj) => vec![&proj.term],
+            Term::Tag(tag) => vec![&tag.term],
+            Term::Case(case) => std::iter::once(&case.scrutinee)
+                .chain(case.branches.iter().map(|branch| &branch.body))
+                .collect(),
+            Term::Fix(fix) => vec![&fix.term],
+            Term::Cons(cons) => vec![&cons.head, &cons.tail],
+            Term::IsNil(is_nil) => vec![&is_nil.arg],
+            Term::Head(head) => vec![&head.arg],
+            Term::Tail(tail) => vec![&tail.arg],
+            Term::Ref(ref_) => vec![&ref_.term],
+            Term::Deref(deref) => vec![&deref.term],
+            Term::Assign(assign) => vec![&assign.target, &assign.value],
+            Term::Raise(raise) => vec![&raise.term],
+            Term::Try(try_) => vec![&try_.term, &try_.handler],
+        }
+    }
+
+    /// The locations that occur in the term, which are the roots it gives
+    /// `Store::collect`.
+    pub fn locations(&self) -> Vec<usize> {
+        let mut locations = vec![];
+        let mut pending = vec![self];
+        while let Some(term) = pending.pop() {
+            match term {
+                Term::Loc(loc) => locations.push(loc.location),
+                _ => pending.extend(term.subterms()),
+            }
+        }
+        locations
+    }
+
+    /// Whether `self` is `error` or `raise v`, which abort the evaluation of every term
+    /// around them up to the nearest `try` that catches them.
+    pub fn is_exception(&self) -> bool {
+        match self {
+            Term::Error(_) => true,
+            Term::Raise(raise) => raise.term.is_val(),
+            _ => false,
+        }
+    }
+
+    pub fn is_val(&self) -> bool {
+        match self {
+            Term::Abs(_) | Term::Bool(_) | Term::Unit(_) | Term::Nil(_) | Term::Loc(_) => true,
+            Term::Cons(cons) => cons.head.is_val() && cons.tail.is_val(),
+            Term::Record(record) => record.fields.iter().all(|(_, term)| term.is_val()),
+            Term::Tag(tag) => tag.term.is_val(),
+            _ => self.is_numeric_val(),
+        }
+    }
+
+    pub fn is_numeric_val(&self) -> bool {
+        match self {
+            Term::Zero(_) | Term::Numeral(_) => true,
+            Term::Succ(succ) => succ.arg.is_numeric_val(),
+            _ => false,
+        }
+    }
+
+    pub fn into_unpositioned_named(self) -> NamedTerm {
+        NamedTerm::unpositioned_from_unnamed(self, &Context::empty())
+    }
+}
+
+impl Abs {
+    pub fn apply(self, argument: &Term) -> Term {
+        self.body.subst_top(argument)
+    }
+}
+
+impl From<Var> for Term {
+    fn from(var: Var) -> Term {
+        Term::Var(var)
+    }
+}
+
+impl From<Abs> for Term {
+    fn from(abs: Abs) -> Term {
+        Term::Abs(Box::new(abs))
+    }
+}
+
+impl From<App> for Term {
+    fn from(app: App) -> Term {
+        Term::App(Box::new(app))
+    }
+}
+
+impl From<Bool> for Term {
+    fn from(b: Bool) -> Term {
+        Term::Bool(b)
+    }
+}
+
+impl From<If> for Term {
+    fn from(if_: If) -> Term {
+        Term::If(Box::new(if_))
+    }
+}
+
+impl From<Zero> for Term {
+    fn from(zero: Zero) -> Term {
+        Term::Zero(zero)
+    }
+}
+
+impl From<Numeral> for Term {
+    fn from(numeral: Numeral) -> Term {
+        Term::Numeral(numeral)
+    }
+}
+
+impl From<Succ> for Term {
+    fn from(succ: Succ) -> Term {
+        Term::Succ(Box::new(succ))
+    }
+}
+
+impl From<Pred> for Term {
+    fn from(pred: Pred) -> Term {
+        Term::Pred(Box::new(pred))
+    }
+}
+
+impl From<IsZero> for Term {
+    fn from(is_zero: IsZero) -> Term {
+        Term::IsZero(Box::new(is_zero))
+    }
+}
+
+impl From<Unit> for Term {
+    fn from(unit: Unit) -> Term {
+        Term::Unit(unit)
+    }
+}
+
+impl From<Ascription> for Term {
+    fn from(ascription: Ascription) -> Term {
+        Term::Ascription(Box::new(ascription))
+    }
+}
+
+impl From<Seq> for Term {
+    fn from(seq: Seq) -> Term {
+        Term::Seq(Box::new(seq))
+    }
+}
+
+impl From<Let> for Term {
+    fn from(let_: Let) -> Term {
+        Term::Let(Box::new(let_))
+    }
+}
+
+impl From<Record> for Term {
+    fn from(record: Record) -> Term {
+        Term::Record(record)
+    }
+}
+
+impl From<Proj> for Term {
+    fn from(proj: Proj) -> Term {
+        Term::Proj(Box::new(proj))
+    }
+}
+
+impl From<Tag> for Term {
+    fn from(tag: Tag) -> Term {
+        Term::Tag(Box::new(tag))
+    }
+}
+
+impl From<Case> for Term {
+    fn from(case: Case) -> Term {
+        Term::Case(Box::new(case))
+    }
+}
+
+impl From<Fix> for Term {
+    fn from(fix: Fix) -> Term {
+        Term::Fix(Box::new(fix))
+    }
+}
+
+impl From<Nil> for Term {
+    fn from(nil: Nil) -> Term {
+        Term::Nil(nil)
+    }
+}
+
+impl From<Cons> for Term {
+    fn from(cons: Cons) -> Term {
+        Term::Cons(Box::new(cons))
+    }
+}
+
+impl From<IsNil> for Term {
+    fn from(is_nil: IsNil) -> Term {
+        Term::IsNil(Box::new(is_nil))
+    }
+}
+
+impl From<Head> for Term {
+    fn from(head: Head) -> Term {
+        Term::Head(Box::new(head))
+    }
+}
+
+impl From<Tail> for Term {
+    fn from(tail: Tail) -> Term {
+        Term::Tail(Box::new(tail))
+    }
+}
+
+impl From<Ref> for Term {
+    fn from(ref_: Ref) -> Term {
+        Term::Ref(Box::new(ref_))
+    }
+}
+
+impl From<Deref> for Term {
+    fn from(deref: Deref) -> Term {
+        Term::Deref(Box::new(deref))
+    }
+}
+
+impl From<Assign> for Term {
+    fn from(assign: Assign) -> Term {
+        Term::Assign(Box::new(assign))
+    }
+}
+
+impl From<Loc> for Term {
+    fn from(loc: Loc) -> Term {
+        Term::Loc(loc)
+    }
+}
+
+impl From<Error> for Term {
+    fn from(error: Error) -> Term {
+        Term::Error(error)
+    }
+}
+
+impl From<Raise> for Term {
+    fn from(raise: Raise) -> Term {
+        Term::Raise(Box::new(raise))
+    }
+}
+
+impl From<Try> for Term {
+    fn from(try_: Try) -> Term {
+        Term::Try(Box::new(try_))
+    }
+}
+
+impl Position for &Term {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Term::Var(var) => var.position,
+            Term::Abs(abs) => abs.position,
+            Term::App(app) => app.position,
+            Term::Bool(b) => b.position,
+            Term::If(if_) => if_.position,
+            Term::Zero(zero) => zero.position,
+            Term::Numeral(numeral) => numeral.position,
+            Term::Succ(succ) => succ.position,
+            Term::Pred(pred) => pred.position,
+            Term::IsZero(is_zero) => is_zero.position,
+            Term::Unit(unit) => unit.position,
+            Term::Ascription(ascription) => ascription.position,
+            Term::Seq(seq) => seq.position,
+            Term::Let(let_) => let_.position,
+            Term::Record(record) => record.position,
+            Term::Proj(proj) => proj.position,
+            Term::Tag(tag) => tag.position,
+            Term::Case(case) => case.position,
+            Term::Fix(fix) => fix.position,
+            Term::Nil(nil) => nil.position,
+            Term::Cons(cons) => cons.position,
+            Term::IsNil(is_nil) => is_nil.position,
+            Term::Head(head) => head.position,
+            Term::Tail(tail) => tail.position,
+            Term::Ref(ref_) => ref_.position,
+            Term::Deref(deref) => deref.position,
+            Term::Assign(assign) => assign.position,
+            Term::Loc(loc) => loc.position,
+            Term::Error(error) => error.position,
+            Term::Raise(raise) => raise.position,
+            Term::Try(try_) => try_.position,
+        }
+    }
+}
+
+impl Term {
+    pub fn from_named(named: NamedTerm, ctx: &Context) -> Result<Term, CannotFindVarInCtxErr> {
+        let from = |term| Term::from_named(term, ctx);
+        Ok(match named {
+            NamedTerm::Var(var) => match ctx.index(&var.name) {
+                Some(index) if var.name != named::WILDCARD => Var {
+                    position: var.position,
+                    index,
+                    context_length: ctx.len(),
+                }
+                .into(),
+                _ => return Err(CannotFindVarInCtxErr(var)),
+            },
+            NamedTerm::Abs(abs) => {
+                let named::Abs {
+                    position,
+                    param,
+                    param_type,
+                    body,
+                } = *abs;
+                let body_ctx = ctx.clone() + param.name.clone();
+                Abs {
+                    position,
+                    param_name: param.name,
+                    param_type,
+                    body: Term::from_named(body, &body_ctx)?,
+                }
+                .into()
+            }
+            NamedTerm::App(app) => App {
+                position: app.position,
+                callee: from(app.callee)?,
+                arg: from(app.arg)?,
+            }
+            .into(),
+            NamedTerm::Bool(b) => Term::Bool(b),
+            NamedTerm::If(if_) => If {
+                position: if_.position,
+                guard: from(if_.guard)?,
+                then: from(if_.then)?,
+                else_: from(if_.else_)?,
+            }
+            .into(),
+            NamedTerm::Zero(zero) => Term::Zero(zero),
+            NamedTerm::Numeral(numeral) => Term::Numeral(numeral),
+            NamedTerm::Succ(succ) => Succ {
+                position: succ.position,
+                arg: from(succ.arg)?,
+            }
+            .into(),
+            NamedTerm::Pred(pred) => Pred {
+                position: pred.position,
+                arg: from(pred.arg)?,
+            }
+            .into(),
+            NamedTerm::IsZero(is_zero) => IsZero {
+                position: is_zero.position,
+                arg: from(is_zero.arg)?,
+            }
+            .into(),
+            NamedTerm::Unit(unit) => Term::Unit(unit),
+            NamedTerm::Ascription(ascription) => Ascription {
+                position: ascription.position,
+                term: from(ascription.term)?,
+                ty: ascription.ty,
+            }
+            .into(),
+            NamedTerm::Seq(seq) => Seq {
+                position: seq.position,
+                first: from(seq.first)?,
+                second: from(seq.second)?,
+            }
+            .into(),
+            NamedTerm::Let(let_) => {
+                let named::Let {
+                    position,
+                    pattern,
+                    value,
+                    body,
+                } = *let_;
+                let body_ctx = pattern
+                    .vars()
+                    .into_iter()
+                    .fold(ctx.clone(), |ctx, var| ctx + var.name.clone());
+                Let {
+                    position,
+                    pattern,
+                    value: from(value)?,
+                    body: Term::from_named(body, &body_ctx)?,
+                }
+                .into()
+            }
+            NamedTerm::Record(record) => Record {
+                position: record.position,
+                fields: record
+                    .fields
+                    .into_iter()
+                    .map(|(label, term)| Ok((label, from(term)?)))
+                    .collect::<Result<_, _>>()?,
+            }
+            .into(),
+            NamedTerm::Proj(proj) => Proj {
+                position: proj.position,
+                term: from(proj.term)?,
+                label: proj.label,
+            }
+            .into(),
+            NamedTerm::Tag(tag) => Tag {
+                position: tag.position,
+                label: tag.label,
+                term: from(tag.term)?,
+                ty: tag.ty,
+            }
+            .into(),
+            NamedTerm::Case(case) => {
+                let named::Case {
+                    position,
+                    scrutinee,
+                    branches,
+                } = *case;
+                Case {
+                    position,
+                    scrutinee: from(scrutinee)?,
+                    branches: branches
+                        .into_iter()
+                        .map(|branch| {
+                            let body_ctx = match &branch.pattern {
+                                CasePattern::Tag { var, .. } => ctx.clone() + var.name.clone(),
+                                CasePattern::Wildcard(_) => ctx.clone(),
+                            };
+                            Ok(Branch {
+                                position: branch.position,
+                                pattern: branch.pattern,
+                                body: Term::from_named(branch.body, &body_ctx)?,
+                            })
+                        })
+                        .collect::<Result<_, _>>()?,
+                }
+                .into()
+            }
+            NamedTerm::Fix(fix) => Fix {
+                position: fix.position,
+                term: from(fix.term)?,
+            }
+            .into(),
+            NamedTerm::Nil(nil) => Term::Nil(nil),
+            NamedTerm::Cons(cons) => Cons {
+                position: cons.position,
+                ty: cons.ty,
+                head: from(cons.head)?,
+                tail: from(cons.tail)?,
+            }
+            .into(),
+            NamedTerm::IsNil(is_nil) => IsNil {
+                position: is_nil.position,
+                ty: is_nil.ty,
+                arg: from(is_nil.arg)?,
+            }
+            .into(),
+            NamedTerm::Head(head) => Head {
+                position: head.position,
+                ty: head.ty,
+                arg: from(head.arg)?,
+            }
+            .into(),
+            NamedTerm::Tail(tail) => Tail {
+                position: tail.position,
+                ty: tail.ty,
+                arg: from(tail.arg)?,
+            }
+            .into(),
+            NamedTerm::Ref(ref_) => Ref {
+                position: ref_.position,
+                term: from(ref_.term)?,
+            }
+            .into(),
+            NamedTerm::Deref(deref) => Deref {
+                position: deref.position,
+                term: from(deref.term)?,
+            }
+            .into(),
+            NamedTerm::Assign(assign) => Assign {
+                position: assign.position,
+                target: from(assign.target)?,
+                value: from(assign.value)?,
+            }
+            .into(),
+            NamedTerm::Loc(loc) => Term::Loc(loc),
+            NamedTerm::Error(error) => Term::Error(error),
+            NamedTerm::Raise(raise) => Raise {
+                position: raise.position,
+                term: from(raise.term)?,
+            }
+            .into(),
+            NamedTerm::Try(try_) => {
+                let named::Try {
+                    position,
+                    term,
+                    var,
+                    handler,
+                } = *try_;
+                let handler = match &var {
+                    Some(var) => Term::from_named(handler, &(ctx.clone() + var.name.clone()))?,
+                    None => from(handler)?,
+                };
+                Try {
+                    position,
+                    term: from(term)?,
+                    var_name: var.map(|var| var.name),
+                    handler,
+                }
+                .into()
+            }
+        })
+    }
+}
+
+/// A variable that is not in the context, or a use of the wildcard `_`.
+#[derive(Debug, Clone)]
+pub struct CannotFindVarInCtxErr(pub named::Var);
+
+impl fmt::Display for CannotFindVarInCtxErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: Cannot find `{}` in context.",
+            self.0.position.start, self.0.name
+        )
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(var) => write!(f, "{}", var.index),
+            Term::Abs(abs) => write!(f, "(\\:{}. {})", abs.param_type, abs.body),
+            Term::App(app) => write!(f, "({} {})", app.callee, app.arg),
+            Term::Bool(b) => write!(f, "{}", b.value),
+            Term::If(if_) => write!(f, "(if {} then {} else {})", if_.guard, if_.then, if_.else_),
+            Term::Zero(_) => write!(f, "0"),
+            Term::Numeral(numeral) => write!(f, "{}", numeral.value),
+            Term::Succ(succ) => write!(f, "(succ {})", succ.arg),
+            Term::Pred(pred) => write!(f, "(pred {})", pred.arg),
+            Term::IsZero(is_zero) => write!(f, "(iszero {})", is_zero.arg),
+            Term::Unit(_) => write!(f, "unit"),
+            Term::Ascription(ascription) => {
+                write!(f, "({} as {})", ascription.term, ascription.ty)
+            }
+            Term::Seq(seq) => write!(f, "({}; {})", seq.first, seq.second),
+            Term::Let(let_) => write!(
+                f,
+                "(let {} = {} in {})",
+                let_.pattern, let_.value, let_.body
+            ),
+            Term::Record(record) => {
+                let fields: Vec<_> = record
+                    .fields
+                    .iter()
+                    .map(|(label, term)| (label.name.as_str(), term))
+                    .collect();
+                ty::fmt_fields(f, &fields, "=")
+            }
+            Term::Proj(proj) => write!(f, "{}.{}", proj.term, proj.label.name),
+            Term::Tag(tag) => named::fmt_tag(f, &tag.label.name, &tag.term, &tag.ty),
+            Term::Case(case) => {
+                let branches: Vec<_> = case
+                    .branches
+                    .iter()
+                    .map(|branch| (&branch.pattern, &branch.body))
+                    .collect();
+                named::fmt_case(f, &case.scrutinee, &branches)
+            }
+            Term::Fix(fix) => write!(f, "(fix {})", fix.term),
+            Term::Nil(nil) => write!(f, "nil[{}]", nil.ty),
+            Term::Cons(cons) => write!(f, "(cons[{}] {} {})", cons.ty, cons.head, cons.tail),
+            Term::IsNil(is_nil) => write!(f, "(isnil[{}] {})", is_nil.ty, is_nil.arg),
+            Term::Head(head) => write!(f, "(head[{}] {})", head.ty, head.arg),
+            Term::Tail(tail) => write!(f, "(tail[{}] {})", tail.ty, tail.arg),
+            Term::Ref(ref_) => write!(f, "(ref {})", ref_.term),
+            Term::Deref(deref) => write!(f, "(!{})", deref.term),
+            Term::Assign(assign) => write!(f, "({} := {})", assign.target, assign.value),
+            Term::Loc(loc) => write!(f, "{}", loc),
+            Term::Error(_) => write!(f, "error"),
+            Term::Raise(raise) => write!(f, "(raise {})", raise.term),
+            Term::Try(try_) => match &try_.var_name {
+                Some(name) => write!(f, "(try {} with {} => {})", try_.term, name, try_.handler),
+                None => write!(f, "(try {} with {})", try_.term, try_.handler),
+            },
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Bool,
+    Nat,
+    Unit,
+    Arrow(Box<Type>, Box<Type>),
+    /// `{l1:T1, ..., ln:Tn}`. A tuple `{T1, ..., Tn}` is a record with the labels `1` to `n`,
+    /// and a pair is a tuple with two fields.
+    Record(Vec<(String, Type)>),
+    /// `<l1:T1, ..., ln:Tn>`. A sum `T1 + T2` is a variant with the labels `inl` and `inr`.
+    Variant(Vec<(String, Type)>),
+    /// `List T`, whose terms are built from `nil[T]` and `cons[T]` (Section 11.12).
+    List(Box<Type>),
+    /// `Ref T`, the type of locations holding values of type `T` (Chapter 13).
+    Ref(Box<Type>),
+    /// The supertype of every type (Section 15.2), which only `chapter_15` relates
+    /// to other types.
+    Top,
+    /// The type of `error` and `raise t`, which have no value and so can stand in for
+    /// a term of any type (Sections 14.1 and 15.4).
+    Bot,
+}
+
+pub fn arrow(param: Type, result: Type) -> Type {
+    Type::Arrow(Box::new(param), Box::new(result))
+}
+
+pub fn sum(left: Type, right: Type) -> Type {
+    Type::Variant(vec![("inl".to_string(), left), ("inr".to_string(), right)])
+}
+
+pub fn list(element: Type) -> Type {
+    Type::List(Box::new(element))
+}
+
+pub fn reference(ty: Type) -> Type {
+    Type::Ref(Box::new(ty))
+}
+
+/// The type of the tuple whose fields have types `types`.
+pub fn tuple(types: Vec<Type>) -> Type {
+    Type::Record(
+        types
+            .into_iter()
+            .enumerate()
+            .map(|(i, ty)| ((i + 1).to_string(), ty))
+            .collect(),
+    )
+}
+
+impl Type {
+    /// The type of the field `label` if `self` is a record or variant with such a field.
+    pub fn field(&self, label: &str) -> Option<&Type> {
+        match self {
+            Type::Record(fields) | Type::Variant(fields) => {
+                fields.iter().find(|(l, _)| l == label).map(|(_, ty)| ty)
+            }
+            _ => None,
+        }
+    }
+
+    /// The summands if `self` is the sum `left + right`.
+    pub fn as_sum(&self) -> Option<(&Type, &Type)> {
+        match self {
+            Type::Variant(fields) => match fields.as_slice() {
+                [(inl, left), (inr, right)] if inl == "inl" && inr == "inr" => Some((left, right)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Whether a term of type `actual` can be used where one of type `expected` is:
+/// either the types are equal, or they differ only where `actual` has `Bot`.
+/// `Bot` may stand in for the result of an arrow, a field or an element,
+/// but not for the parameter of an arrow or the contents of a reference.
+pub fn fits(actual: &Type, expected: &Type) -> bool {
+    match (actual, expected) {
+        (Type::Bot, _) => true,
+        (Type::Arrow(param1, result1), Type::Arrow(param2, result2)) => {
+            param1 == param2 && fits(result1, result2)
+        }
+        (Type::Record(fields1), Type::Record(fields2))
+        | (Type::Variant(fields1), Type::Variant(fields2)) => {
+            fields1.len() == fields2.len()
+                && fields1
+                    .iter()
+                    .zip(fields2)
+                    .all(|((l1, ty1), (l2, ty2))| l1 == l2 && fits(ty1, ty2))
+        }
+        (Type::List(element1), Type::List(element2)) => fits(element1, element2),
+        _ => actual == expected,
+    }
+}
+
+/// The type of `if` or `case` whose branches have types `a` and `b`,
+/// which is the one the other fits, if any.
+pub fn common(a: &Type, b: &Type) -> Option<Type> {
+    if fits(a, b) {
+        Some(b.clone())
+    } else if fits(b, a) {
+        Some(a.clone())
+    } else {
+        None
+    }
+}
+
+/// Whether `labels` are `1` to `n` in order, so that the fields they label can be written
+/// without them.
+pub fn are_tuple_labels<'a>(labels: impl Iterator<Item = &'a str>) -> bool {
+    labels
+        .enumerate()
+        .all(|(i, label)| label == (i + 1).to_string())
+}
+
+/// Writes `{l1<separator>x1, ..., ln<separator>xn}`, leaving out the labels of tuples.
+pub fn fmt_fields<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    fields: &[(&str, T)],
+    separator: &str,
+) -> fmt::Result {
+    let is_tuple = are_tuple_labels(fields.iter().map(|(label, _)| *label));
+    write!(f, "{{")?;
+    for (i, (label, x)) in fields.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        if is_tuple {
+            write!(f, "{}", x)?;
+        } else {
+            write!(f, "{}{}{}", label, separator, x)?;
+        }
+    }
+    write!(f, "}}")
+}
+
+impl fmt::Display for Type {
+    /// Arrows associate to the right, so only parameters that are arrows are parenthesized.
+    /// Sums bind tighter than arrows and associate to the left, and `List` and `Ref` tighter still.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((left, right)) = self.as_sum() {
+            match left {
+                Type::Arrow(_, _) => write!(f, "({})", left)?,
+                _ => write!(f, "{}", left)?,
+            }
+            return match right {
+                Type::Arrow(_, _) => write!(f, " + ({})", right),
+                _ if right.as_sum().is_some() => write!(f, " + ({})", right),
+                _ => write!(f, " + {}", right),
+            };
+        }
+        match self {
+            Type::Bool => write!(f, "Bool"),
+            Type::Nat => write!(f, "Nat"),
+            Type::Unit => write!(f, "Unit"),
+            Type::Top => write!(f, "Top"),
+            Type::Bot => write!(f, "Bot"),
+            Type::Arrow(param, result) => match **param {
+                Type::Arrow(_, _) => write!(f, "({}) -> {}", param, result),
+                _ => write!(f, "{} -> {}", param, result),
+            },
+            Type::Record(fields) => {
+                let fields: Vec<_> = fields.iter().map(|(l, ty)| (l.as_str(), ty)).collect();
+                fmt_fields(f, &fields, ":")
+            }
+            Type::Variant(fields) => {
+                write!(f, "<")?;
+                for (i, (label, ty)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}:{}", label, ty)?;
+                }
+                write!(f, ">")
+            }
+            Type::List(element) => fmt_constructor(f, "List", element),
+            Type::Ref(ty) => fmt_constructor(f, "Ref", ty),
+        }
+    }
+}
+
+/// Writes `name arg`, parenthesizing `arg` if it is an arrow or a sum.
+fn fmt_constructor(f: &mut fmt::Formatter<'_>, name: &str, arg: &Type) -> fmt::Result {
+    match arg {
+        Type::Arrow(_, _) => write!(f, "{} ({})", name, arg),
+        _ if arg.as_sum().is_some() => write!(f, "{} ({})", name, arg),
+        _ => write!(f, "{} {}", name, arg),
+    }
+}
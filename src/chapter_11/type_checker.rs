@@ -0,0 +1,781 @@
+#[cfg(test)]
+mod test;
+
+use super::term::unnamed::{Case, CasePattern, Label, Pattern, Term};
+use super::ty::{self, Type};
+use crate::file_position::{FilePositionRange, Position};
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Add;
+
+/// The types of the variables in scope, like `chapter_10::type_checker::TypeContext`,
+/// and the type of the values that `raise` takes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeContext {
+    types: Vec<Type>,
+    exn_type: Type,
+}
+
+/// The exception type of `TypeContext::empty`. Section 14.3 suggests `Nat`
+/// for error codes like those of Unix system calls.
+pub const DEFAULT_EXN_TYPE: Type = Type::Nat;
+
+impl Default for TypeContext {
+    fn default() -> TypeContext {
+        TypeContext::empty()
+    }
+}
+
+impl TypeContext {
+    pub fn empty() -> TypeContext {
+        TypeContext {
+            types: vec![],
+            exn_type: DEFAULT_EXN_TYPE,
+        }
+    }
+
+    /// `self`, but with `T_exn` set to `exn_type`, such as the variant
+    /// `<divideByZero:Unit, overflow:Unit, fileNotFound:Unit>`.
+    pub fn with_exn_type(self, exn_type: Type) -> TypeContext {
+        TypeContext { exn_type, ..self }
+    }
+
+    /// `T_exn`, the type of `v` in `raise v` and of the variable bound by `try t with x => t'`.
+    pub fn exn_type(&self) -> &Type {
+        &self.exn_type
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Type> {
+        self.types.iter().rev().nth(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
+
+impl Add<Type> for TypeContext {
+    type Output = TypeContext;
+
+    fn add(mut self, ty: Type) -> TypeContext {
+        self.types.push(ty);
+        self
+    }
+}
+
+impl Add<Type> for &TypeContext {
+    type Output = TypeContext;
+
+    fn add(self, ty: Type) -> TypeContext {
+        self.clone() + ty
+    }
+}
+
+/// The types of the values in the store, indexed by location (Section 13.4).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StoreTyping {
+    types: Vec<Type>,
+}
+
+impl StoreTyping {
+    pub fn empty() -> StoreTyping {
+        StoreTyping { types: vec![] }
+    }
+
+    pub fn get(&self, location: usize) -> Option<&Type> {
+        self.types.get(location)
+    }
+
+    /// Gives the next location the type `ty` and returns the location.
+    pub fn push(&mut self, ty: Type) -> usize {
+        self.types.push(ty);
+        self.types.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
+
+/// A term without a type. The position is that of the offending subterm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeErr {
+    pub position: FilePositionRange,
+    pub kind: TypeErrKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeErrKind {
+    GuardNotBool(Type),
+    BranchesDiffer {
+        then: Type,
+        else_: Type,
+    },
+    CalleeNotArrow(Type),
+    /// An argument whose type does not fit the parameter type.
+    ArgMismatch {
+        param: Type,
+        arg: Type,
+    },
+    /// A subterm whose type does not fit the one fixed by its position,
+    /// such as the argument of `succ`, the first term of a sequence or an ascribed term.
+    Mismatch {
+        expected: Type,
+        actual: Type,
+    },
+    /// An `ArgMismatch` or `Mismatch`, with the reason `Relation::explain` gave for it.
+    Explained {
+        mismatch: Box<TypeErrKind>,
+        reason: String,
+    },
+    /// A projection from, or a record pattern matched against, a term that is not a record.
+    NotRecord(Type),
+    /// A projection, record pattern, tag or `case` branch whose label is not a field
+    /// of the record or variant type. The position is that of the label.
+    MissingLabel {
+        label: String,
+        ty: Type,
+    },
+    /// A tag ascribed, or a `case` applied to a term of, a type that is not a variant.
+    NotVariant(Type),
+    /// A branch of `case` whose type differs from that of the first branch.
+    CaseBranchesDiffer {
+        first: Type,
+        other: Type,
+    },
+    /// `!t` or `t := t'` where `t` is not a reference.
+    NotRef(Type),
+    /// `try t with t'` where the types of `t` and `t'` differ.
+    /// The position is that of the handler `t'`.
+    HandlerMismatch {
+        term: Type,
+        handler: Type,
+    },
+    /// A variable whose index is not less than the length of the context.
+    UnboundVar(usize),
+    /// A location that the store typing has no type for.
+    UnboundLoc(usize),
+}
+
+impl Position for &TypeErr {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl fmt::Display for TypeErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.position.start)?;
+        fmt_kind(f, &self.kind, None)
+    }
+}
+
+/// Writes the message for `kind`, ending a mismatch with `reason` if there is one.
+fn fmt_kind(f: &mut fmt::Formatter<'_>, kind: &TypeErrKind, reason: Option<&str>) -> fmt::Result {
+    match kind {
+        TypeErrKind::GuardNotBool(actual) => write!(
+            f,
+            "Expected the guard of `if` to have type `Bool`, but it has type `{}`.",
+            actual
+        ),
+        TypeErrKind::BranchesDiffer { then, else_ } => write!(
+            f,
+            "Expected the branches of `if` to have the same type, but they have types `{}` and `{}`.",
+            then, else_
+        ),
+        TypeErrKind::CalleeNotArrow(actual) => write!(
+            f,
+            "Expected a function, but found a term of type `{}`.",
+            actual
+        ),
+        TypeErrKind::ArgMismatch { param, arg } => {
+            write!(
+                f,
+                "Expected an argument of type `{}`, but found a term of type `{}`",
+                param, arg
+            )?;
+            fmt_reason(f, reason)
+        }
+        TypeErrKind::Mismatch { expected, actual } => {
+            write!(
+                f,
+                "Expected a term of type `{}`, but found a term of type `{}`",
+                expected, actual
+            )?;
+            fmt_reason(f, reason)
+        }
+        TypeErrKind::NotRecord(actual) => write!(
+            f,
+            "Expected a record, but found a term of type `{}`.",
+            actual
+        ),
+        TypeErrKind::MissingLabel { label, ty } => write!(
+            f,
+            "Expected a {} with label `{}`, but found a term of type `{}`.",
+            if let Type::Variant(_) = ty {
+                "variant"
+            } else {
+                "record"
+            },
+            label,
+            ty
+        ),
+        TypeErrKind::NotVariant(actual) => {
+            write!(f, "Expected a variant type, but found `{}`.", actual)
+        }
+        TypeErrKind::CaseBranchesDiffer { first, other } => write!(
+            f,
+            "Expected the branches of `case` to have the same type, but they have types `{}` and `{}`.",
+            first, other
+        ),
+        TypeErrKind::NotRef(actual) => write!(
+            f,
+            "Expected a reference, but found a term of type `{}`.",
+            actual
+        ),
+        TypeErrKind::HandlerMismatch { term, handler } => write!(
+            f,
+            "Expected the handler of `try` to have the type `{}` of the term it guards, but it has type `{}`.",
+            term, handler
+        ),
+        TypeErrKind::UnboundVar(index) => write!(f, "Cannot find index {} in context.", index),
+        TypeErrKind::UnboundLoc(location) => {
+            write!(f, "Cannot find location #{} in the store typing.", location)
+        }
+        TypeErrKind::Explained { mismatch, reason } => fmt_kind(f, mismatch, Some(reason)),
+    }
+}
+
+/// Ends a message about a type that does not fit with the reason, if there is one.
+fn fmt_reason(f: &mut fmt::Formatter<'_>, reason: Option<&str>) -> fmt::Result {
+    match reason {
+        Some(reason) => write!(f, ", {}.", reason),
+        None => write!(f, "."),
+    }
+}
+
+fn err(term: &Term, kind: TypeErrKind) -> TypeErr {
+    TypeErr {
+        position: term.position(),
+        kind,
+    }
+}
+
+/// The error for the `ArgMismatch` or `Mismatch` `kind` at `term`,
+/// explained by `R` if it can.
+fn mismatch<R: Relation>(term: &Term, kind: TypeErrKind) -> TypeErr {
+    let reason = match &kind {
+        TypeErrKind::ArgMismatch { param, arg } => R::explain(arg, param),
+        TypeErrKind::Mismatch { expected, actual } => R::explain(actual, expected),
+        _ => None,
+    };
+    match reason {
+        Some(reason) => err(
+            term,
+            TypeErrKind::Explained {
+                mismatch: Box::new(kind),
+                reason,
+            },
+        ),
+        None => err(term, kind),
+    }
+}
+
+fn missing_label(label: &Label, ty: Type) -> TypeErr {
+    TypeErr {
+        position: label.position,
+        kind: TypeErrKind::MissingLabel {
+            label: label.name.clone(),
+            ty,
+        },
+    }
+}
+
+/// A well-typed `case` whose branches do not match the labels of its variant type
+/// one to one. The position is that of the `case` or of the branch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    pub position: FilePositionRange,
+    pub kind: WarningKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A `case` without a branch for the labels `missing`,
+    /// whose values would get stuck.
+    NonExhaustive { missing: Vec<String> },
+    /// A branch that follows a wildcard, or one with the same label,
+    /// or a wildcard that follows branches for every label.
+    Redundant,
+}
+
+impl Position for &Warning {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: Warning: ", self.position.start)?;
+        match &self.kind {
+            WarningKind::NonExhaustive { missing } => {
+                let missing: Vec<_> = missing.iter().map(|l| format!("`{}`", l)).collect();
+                write!(f, "`case` has no branch for {}.", missing.join(", "))
+            }
+            WarningKind::Redundant => write!(f, "This branch is never taken."),
+        }
+    }
+}
+
+/// How the type checker relates the type of a subterm to the type its position calls for,
+/// and the types of the branches of `if`, `case` and `try` to the type of the whole.
+/// `chapter_15` relates them by subtyping.
+pub trait Relation {
+    /// Whether a term of type `actual` can be used where one of type `expected` is.
+    fn fits(actual: &Type, expected: &Type) -> bool;
+
+    /// The type of `if`, `case` or `try` whose branches have types `a` and `b`, if any.
+    fn common(a: &Type, b: &Type) -> Option<Type>;
+
+    /// Why `actual` does not fit `expected`, if there is more to say than that they differ.
+    fn explain(_actual: &Type, _expected: &Type) -> Option<String> {
+        None
+    }
+}
+
+/// The relation of Chapters 11 to 14, in which the types must be equal
+/// except where `Bot` stands in for another type, as `ty::fits` describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exact {}
+
+impl Relation for Exact {
+    fn fits(actual: &Type, expected: &Type) -> bool {
+        ty::fits(actual, expected)
+    }
+
+    fn common(a: &Type, b: &Type) -> Option<Type> {
+        ty::common(a, b)
+    }
+}
+
+/// The type of `term`, whose free variables have the types in `ctx`,
+/// using the rules of Figure 9-1, Figure 8-2, Sections 11.2 to 11.12 and 13.4 and Chapter 14.
+pub fn type_of(term: &Term, ctx: &TypeContext) -> Result<Type, TypeErr> {
+    check::<Exact>(term, ctx, &StoreTyping::empty(), &mut vec![])
+}
+
+/// Like `type_of`, but for terms that may contain locations,
+/// whose types are in `store_typing` (T-Loc).
+pub fn type_of_in_store(
+    term: &Term,
+    ctx: &TypeContext,
+    store_typing: &StoreTyping,
+) -> Result<Type, TypeErr> {
+    check::<Exact>(term, ctx, store_typing, &mut vec![])
+}
+
+/// Like `type_of`, but also returns the warnings about the branches of each `case`,
+/// in the order of the branches and cases they are about.
+pub fn type_of_with_warnings(
+    term: &Term,
+    ctx: &TypeContext,
+) -> Result<(Type, Vec<Warning>), TypeErr> {
+    type_of_by::<Exact>(term, ctx, &StoreTyping::empty())
+}
+
+/// Like `type_of_with_warnings` and `type_of_in_store`, but relating types by `R`.
+pub fn type_of_by<R: Relation>(
+    term: &Term,
+    ctx: &TypeContext,
+    store_typing: &StoreTyping,
+) -> Result<(Type, Vec<Warning>), TypeErr> {
+    let mut warnings = vec![];
+    let ty = check::<R>(term, ctx, store_typing, &mut warnings)?;
+    Ok((ty, warnings))
+}
+
+fn check<R: Relation>(
+    term: &Term,
+    ctx: &TypeContext,
+    store_typing: &StoreTyping,
+    warnings: &mut Vec<Warning>,
+) -> Result<Type, TypeErr> {
+    match term {
+        Term::Var(var) => ctx
+            .get(var.index)
+            .cloned()
+            .ok_or_else(|| err(term, TypeErrKind::UnboundVar(var.index))),
+        Term::Abs(abs) => {
+            let body_ctx = ctx + abs.param_type.clone();
+            let body_type = check::<R>(&abs.body, &body_ctx, store_typing, warnings)?;
+            Ok(ty::arrow(abs.param_type.clone(), body_type))
+        }
+        Term::App(app) => {
+            let callee_type = check::<R>(&app.callee, ctx, store_typing, warnings)?;
+            let arg_type = check::<R>(&app.arg, ctx, store_typing, warnings)?;
+            match callee_type {
+                Type::Bot => Ok(Type::Bot),
+                Type::Arrow(param, result) => {
+                    if R::fits(&arg_type, &param) {
+                        Ok(*result)
+                    } else {
+                        Err(mismatch::<R>(
+                            &app.arg,
+                            TypeErrKind::ArgMismatch {
+                                param: *param,
+                                arg: arg_type,
+                            },
+                        ))
+                    }
+                }
+                other => Err(err(&app.callee, TypeErrKind::CalleeNotArrow(other))),
+            }
+        }
+        Term::Bool(_) => Ok(Type::Bool),
+        Term::Zero(_) | Term::Numeral(_) => Ok(Type::Nat),
+        Term::Succ(succ) => {
+            expect::<R>(&succ.arg, ctx, Type::Nat, store_typing, warnings)?;
+            Ok(Type::Nat)
+        }
+        Term::Pred(pred) => {
+            expect::<R>(&pred.arg, ctx, Type::Nat, store_typing, warnings)?;
+            Ok(Type::Nat)
+        }
+        Term::IsZero(is_zero) => {
+            expect::<R>(&is_zero.arg, ctx, Type::Nat, store_typing, warnings)?;
+            Ok(Type::Bool)
+        }
+        Term::Unit(_) => Ok(Type::Unit),
+        Term::Ascription(ascription) => {
+            expect::<R>(
+                &ascription.term,
+                ctx,
+                ascription.ty.clone(),
+                store_typing,
+                warnings,
+            )?;
+            Ok(ascription.ty.clone())
+        }
+        Term::Seq(seq) => {
+            expect::<R>(&seq.first, ctx, Type::Unit, store_typing, warnings)?;
+            check::<R>(&seq.second, ctx, store_typing, warnings)
+        }
+        Term::Let(let_) => {
+            let value_type = check::<R>(&let_.value, ctx, store_typing, warnings)?;
+            let body_ctx = bind_pattern(&let_.pattern, value_type, ctx.clone())?;
+            check::<R>(&let_.body, &body_ctx, store_typing, warnings)
+        }
+        Term::Record(record) => Ok(Type::Record(
+            record
+                .fields
+                .iter()
+                .map(|(label, term)| {
+                    Ok((
+                        label.name.clone(),
+                        check::<R>(term, ctx, store_typing, warnings)?,
+                    ))
+                })
+                .collect::<Result<_, _>>()?,
+        )),
+        Term::Proj(proj) => {
+            let ty = check::<R>(&proj.term, ctx, store_typing, warnings)?;
+            if ty == Type::Bot {
+                Ok(Type::Bot)
+            } else if let Type::Record(_) = ty {
+                match ty.field(&proj.label.name) {
+                    Some(field) => Ok(field.clone()),
+                    None => Err(missing_label(&proj.label, ty)),
+                }
+            } else {
+                Err(err(&proj.term, TypeErrKind::NotRecord(ty)))
+            }
+        }
+        Term::Tag(tag) => {
+            if !matches!(tag.ty, Type::Variant(_)) {
+                return Err(err(term, TypeErrKind::NotVariant(tag.ty.clone())));
+            }
+            let field = match tag.ty.field(&tag.label.name) {
+                Some(field) => field.clone(),
+                None => return Err(missing_label(&tag.label, tag.ty.clone())),
+            };
+            expect::<R>(&tag.term, ctx, field, store_typing, warnings)?;
+            Ok(tag.ty.clone())
+        }
+        Term::Case(case) => check_case::<R>(case, ctx, store_typing, warnings),
+        Term::Nil(nil) => Ok(ty::list(nil.ty.clone())),
+        Term::Cons(cons) => {
+            expect::<R>(&cons.head, ctx, cons.ty.clone(), store_typing, warnings)?;
+            expect::<R>(
+                &cons.tail,
+                ctx,
+                ty::list(cons.ty.clone()),
+                store_typing,
+                warnings,
+            )?;
+            Ok(ty::list(cons.ty.clone()))
+        }
+        Term::IsNil(is_nil) => {
+            expect::<R>(
+                &is_nil.arg,
+                ctx,
+                ty::list(is_nil.ty.clone()),
+                store_typing,
+                warnings,
+            )?;
+            Ok(Type::Bool)
+        }
+        Term::Head(head) => {
+            expect::<R>(
+                &head.arg,
+                ctx,
+                ty::list(head.ty.clone()),
+                store_typing,
+                warnings,
+            )?;
+            Ok(head.ty.clone())
+        }
+        Term::Tail(tail) => {
+            expect::<R>(
+                &tail.arg,
+                ctx,
+                ty::list(tail.ty.clone()),
+                store_typing,
+                warnings,
+            )?;
+            Ok(ty::list(tail.ty.clone()))
+        }
+        Term::Ref(ref_) => Ok(ty::reference(check::<R>(
+            &ref_.term,
+            ctx,
+            store_typing,
+            warnings,
+        )?)),
+        Term::Deref(deref) => match check::<R>(&deref.term, ctx, store_typing, warnings)? {
+            Type::Ref(ty) => Ok(*ty),
+            Type::Bot => Ok(Type::Bot),
+            other => Err(err(&deref.term, TypeErrKind::NotRef(other))),
+        },
+        Term::Assign(assign) => match check::<R>(&assign.target, ctx, store_typing, warnings)? {
+            Type::Ref(ty) => {
+                expect::<R>(&assign.value, ctx, *ty, store_typing, warnings)?;
+                Ok(Type::Unit)
+            }
+            Type::Bot => {
+                check::<R>(&assign.value, ctx, store_typing, warnings)?;
+                Ok(Type::Unit)
+            }
+            other => Err(err(&assign.target, TypeErrKind::NotRef(other))),
+        },
+        Term::Loc(loc) => store_typing
+            .get(loc.location)
+            .map(|ty| ty::reference(ty.clone()))
+            .ok_or_else(|| err(term, TypeErrKind::UnboundLoc(loc.location))),
+        Term::Fix(fix) => match check::<R>(&fix.term, ctx, store_typing, warnings)? {
+            Type::Arrow(param, result) if R::fits(&result, &param) => Ok(*param),
+            Type::Bot => Ok(Type::Bot),
+            // In `letrec x:T = t1 in t2`, this points at `t1` rather than the whole definition.
+            Type::Arrow(param, result) => match &fix.term {
+                Term::Abs(abs) => Err(mismatch::<R>(
+                    &abs.body,
+                    TypeErrKind::Mismatch {
+                        expected: *param,
+                        actual: *result,
+                    },
+                )),
+                _ => Err(mismatch::<R>(
+                    &fix.term,
+                    TypeErrKind::Mismatch {
+                        expected: ty::arrow((*param).clone(), (*param).clone()),
+                        actual: ty::arrow(*param, *result),
+                    },
+                )),
+            },
+            other => Err(err(&fix.term, TypeErrKind::CalleeNotArrow(other))),
+        },
+        Term::If(if_) => {
+            let guard_type = check::<R>(&if_.guard, ctx, store_typing, warnings)?;
+            if !R::fits(&guard_type, &Type::Bool) {
+                return Err(err(&if_.guard, TypeErrKind::GuardNotBool(guard_type)));
+            }
+            let then_type = check::<R>(&if_.then, ctx, store_typing, warnings)?;
+            let else_type = check::<R>(&if_.else_, ctx, store_typing, warnings)?;
+            R::common(&then_type, &else_type).ok_or_else(|| {
+                err(
+                    &if_.else_,
+                    TypeErrKind::BranchesDiffer {
+                        then: then_type,
+                        else_: else_type,
+                    },
+                )
+            })
+        }
+        Term::Error(_) => Ok(Type::Bot),
+        Term::Raise(raise) => {
+            expect::<R>(
+                &raise.term,
+                ctx,
+                ctx.exn_type().clone(),
+                store_typing,
+                warnings,
+            )?;
+            Ok(Type::Bot)
+        }
+        Term::Try(try_) => {
+            let term_type = check::<R>(&try_.term, ctx, store_typing, warnings)?;
+            let handler_type = match try_.var_name {
+                Some(_) => {
+                    let handler_ctx = ctx + ctx.exn_type().clone();
+                    check::<R>(&try_.handler, &handler_ctx, store_typing, warnings)?
+                }
+                None => check::<R>(&try_.handler, ctx, store_typing, warnings)?,
+            };
+            R::common(&term_type, &handler_type).ok_or_else(|| {
+                err(
+                    &try_.handler,
+                    TypeErrKind::HandlerMismatch {
+                        term: term_type,
+                        handler: handler_type,
+                    },
+                )
+            })
+        }
+    }
+}
+
+/// Checks that the type of `term` fits `expected`.
+fn expect<R: Relation>(
+    term: &Term,
+    ctx: &TypeContext,
+    expected: Type,
+    store_typing: &StoreTyping,
+    warnings: &mut Vec<Warning>,
+) -> Result<(), TypeErr> {
+    let actual = check::<R>(term, ctx, store_typing, warnings)?;
+    if R::fits(&actual, &expected) {
+        Ok(())
+    } else {
+        Err(mismatch::<R>(
+            term,
+            TypeErrKind::Mismatch { expected, actual },
+        ))
+    }
+}
+
+/// Adds the types of the variables of `pattern`, matched against a value of type `ty`, to `ctx`.
+fn bind_pattern(pattern: &Pattern, ty: Type, ctx: TypeContext) -> Result<TypeContext, TypeErr> {
+    match pattern {
+        Pattern::Var(_) => Ok(ctx + ty),
+        Pattern::Record(record_pattern) => {
+            if !matches!(ty, Type::Record(_) | Type::Bot) {
+                return Err(TypeErr {
+                    position: record_pattern.position,
+                    kind: TypeErrKind::NotRecord(ty),
+                });
+            }
+            let mut ctx = ctx;
+            for (label, pattern) in &record_pattern.fields {
+                // A `Bot` has no value to take apart, so every field is a `Bot` too.
+                let field = match ty.field(&label.name) {
+                    Some(field) => field.clone(),
+                    None if ty == Type::Bot => Type::Bot,
+                    None => return Err(missing_label(label, ty)),
+                };
+                ctx = bind_pattern(pattern, field, ctx)?;
+            }
+            Ok(ctx)
+        }
+    }
+}
+
+/// T-Case of Figure 11-11, allowing a wildcard branch
+/// and warning about missing and redundant branches instead of rejecting them.
+fn check_case<R: Relation>(
+    case: &Case,
+    ctx: &TypeContext,
+    store_typing: &StoreTyping,
+    warnings: &mut Vec<Warning>,
+) -> Result<Type, TypeErr> {
+    let scrutinee_type = check::<R>(&case.scrutinee, ctx, store_typing, warnings)?;
+    // The labels of the variant, unknown if the scrutinee is a `Bot`,
+    // which has no value and so can stand in for any variant.
+    let labels: Option<Vec<String>> = match &scrutinee_type {
+        Type::Variant(fields) => Some(fields.iter().map(|(label, _)| label.clone()).collect()),
+        Type::Bot => None,
+        _ => {
+            return Err(err(
+                &case.scrutinee,
+                TypeErrKind::NotVariant(scrutinee_type),
+            ))
+        }
+    };
+
+    let mut covered = HashSet::new();
+    let mut has_wildcard = false;
+    let mut result: Option<Type> = None;
+    for branch in &case.branches {
+        let (is_redundant, body_ctx) = match &branch.pattern {
+            CasePattern::Tag { label, .. } => {
+                let field = match scrutinee_type.field(&label.name) {
+                    Some(field) => field.clone(),
+                    None if labels.is_none() => Type::Bot,
+                    None => return Err(missing_label(label, scrutinee_type)),
+                };
+                let is_new = covered.insert(label.name.as_str());
+                (has_wildcard || !is_new, ctx + field)
+            }
+            CasePattern::Wildcard(_) => {
+                let is_redundant = has_wildcard
+                    || labels
+                        .as_ref()
+                        .is_some_and(|labels| covered.len() == labels.len());
+                has_wildcard = true;
+                (is_redundant, ctx.clone())
+            }
+        };
+        if is_redundant {
+            warnings.push(Warning {
+                position: branch.position,
+                kind: WarningKind::Redundant,
+            });
+        }
+        let body_type = check::<R>(&branch.body, &body_ctx, store_typing, warnings)?;
+        result = match result {
+            None => Some(body_type),
+            Some(first) => match R::common(&first, &body_type) {
+                Some(common) => Some(common),
+                None => {
+                    return Err(err(
+                        &branch.body,
+                        TypeErrKind::CaseBranchesDiffer {
+                            first,
+                            other: body_type,
+                        },
+                    ))
+                }
+            },
+        };
+    }
+
+    if let (false, Some(labels)) = (has_wildcard, labels) {
+        let missing: Vec<String> = labels
+            .into_iter()
+            .filter(|label| !covered.contains(label.as_str()))
+            .collect();
+        if !missing.is_empty() {
+            warnings.push(Warning {
+                position: case.position,
+                kind: WarningKind::NonExhaustive { missing },
+            });
+        }
+    }
+    Ok(result.expect("Parsed a `case` without branches."))
+}
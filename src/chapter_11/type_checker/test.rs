@@ -0,0 +1,465 @@
+use super::{
+    type_of, type_of_in_store, type_of_with_warnings, StoreTyping, TypeContext, TypeErr,
+    TypeErrKind, WarningKind,
+};
+use crate::chapter_11::{
+    eval1, parse,
+    parser::parse_type,
+    store::Store,
+    term::unnamed::Term,
+    ty::{arrow, Type},
+};
+use crate::chapter_7::term::unnamed::Context;
+
+fn checked(src: &str) -> Result<Type, TypeErr> {
+    let term = Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.");
+    type_of(&term, &TypeContext::empty())
+}
+
+fn ty(src: &str) -> Type {
+    parse_type(src).expect("Cannot parse.")
+}
+
+#[test]
+fn well_typed_terms() {
+    let examples = [
+        ("unit", "Unit"),
+        ("iszero (pred 2)", "Bool"),
+        (r"\_:Unit. 0", "Unit -> Nat"),
+        (r"\x:Unit. x; x", "Unit -> Unit"),
+        (r"(\x:Nat. x) as Nat -> Nat", "Nat -> Nat"),
+        (r"let double = \x:Nat. x in double 3", "Nat"),
+        (r"let _ = unit in let x = true in x", "Bool"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+}
+
+#[test]
+fn let_binds_the_type_of_its_value() {
+    let ctx = Context::from_strs(&["f"]);
+    let term = Term::from_named(
+        parse("let x = f 0 in iszero x").expect("Cannot parse."),
+        &ctx,
+    )
+    .expect("Cannot remove names.");
+    let types = TypeContext::empty() + arrow(Type::Nat, Type::Nat);
+    assert_eq!(type_of(&term, &types), Ok(Type::Bool));
+    let types = TypeContext::empty() + arrow(Type::Nat, Type::Bool);
+    let err = type_of(&term, &types).expect_err("`x` is a `Bool`.");
+    assert_eq!(err.position.start.column, 22);
+}
+
+#[test]
+fn mismatches_point_at_the_offending_subterm() {
+    let examples = [
+        (
+            "succ true",
+            Type::Nat,
+            Type::Bool,
+            "1:6: Expected a term of type `Nat`, but found a term of type `Bool`.",
+        ),
+        (
+            "0; unit",
+            Type::Unit,
+            Type::Nat,
+            "1:1: Expected a term of type `Unit`, but found a term of type `Nat`.",
+        ),
+        (
+            r"(\x:Nat. x) as Nat -> Bool",
+            ty("Nat -> Bool"),
+            ty("Nat -> Nat"),
+            "1:2: Expected a term of type `Nat -> Bool`, but found a term of type `Nat -> Nat`.",
+        ),
+    ];
+    for (src, expected, actual, message) in examples.iter() {
+        let err = checked(src).expect_err("Expected a type error.");
+        assert_eq!(
+            err.kind,
+            TypeErrKind::Mismatch {
+                expected: expected.clone(),
+                actual: actual.clone()
+            },
+            "`{}`",
+            src
+        );
+        assert_eq!(err.to_string(), *message);
+    }
+}
+
+#[test]
+fn records_and_projections() {
+    let examples = [
+        ("{1, true}", "{Nat, Bool}"),
+        ("{x=1, y={unit}}.y", "{Unit}"),
+        (r"(\p:{Nat, Nat}. succ p.2) {1, 2}", "Nat"),
+        (
+            "let {x=a, y={b, _}} = {y={true, 0}, x=unit} in {b, a}",
+            "{Bool, Unit}",
+        ),
+        ("let {2=b} = {0, false} in b", "Bool"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+}
+
+#[test]
+fn missing_labels_are_reported_at_the_label() {
+    let err = checked("{x=1, y=2}.z").expect_err("`z` is missing.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::MissingLabel {
+            label: "z".to_string(),
+            ty: ty("{x:Nat, y:Nat}"),
+        }
+    );
+    assert_eq!(err.position.start.column, 11);
+    assert_eq!(err.position.end.column, 12);
+    assert_eq!(
+        err.to_string(),
+        "1:12: Expected a record with label `z`, but found a term of type `{x:Nat, y:Nat}`."
+    );
+
+    let err = checked("let {a, b, c} = {1, 2} in a").expect_err("`3` is missing.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::MissingLabel {
+            label: "3".to_string(),
+            ty: ty("{Nat, Nat}"),
+        }
+    );
+    assert_eq!(err.position.start.column, 11);
+
+    let err = checked("let {a} = 1 in a").expect_err("`1` is not a record.");
+    assert_eq!(err.kind, TypeErrKind::NotRecord(Type::Nat));
+    assert_eq!(err.position.start.column, 4);
+    let err = checked("true.1").expect_err("`true` is not a record.");
+    assert_eq!(
+        err.to_string(),
+        "1:1: Expected a record, but found a term of type `Bool`."
+    );
+}
+
+fn warnings(src: &str) -> Vec<String> {
+    let term = Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.");
+    let (_, warnings) =
+        type_of_with_warnings(&term, &TypeContext::empty()).expect("Expected a well-typed term.");
+    warnings.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn sums_variants_and_case() {
+    let examples = [
+        ("inl 0 as Nat + Bool", "Nat + Bool"),
+        (
+            "case inr true as Nat + Bool of inl n => iszero n | inr b => b",
+            "Bool",
+        ),
+        (
+            r"\o:<none:Unit, some:Nat>. case o of <none=_> => 0 | <some=n> => succ n",
+            "<none:Unit, some:Nat> -> Nat",
+        ),
+        (
+            "case <b=unit> as <a:Nat, b:Unit> of <a=n> => n | _ => 0",
+            "Nat",
+        ),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+}
+
+#[test]
+fn ill_typed_tags_and_cases() {
+    let err = checked("<c=0> as <a:Nat, b:Bool>").expect_err("`c` is missing.");
+    assert_eq!(
+        err.to_string(),
+        "1:2: Expected a variant with label `c`, but found a term of type `<a:Nat, b:Bool>`."
+    );
+    let err = checked("inl 0 as Nat").expect_err("`Nat` is not a variant.");
+    assert_eq!(err.kind, TypeErrKind::NotVariant(Type::Nat));
+    let err = checked("inl true as Nat + Bool").expect_err("`true` is not a `Nat`.");
+    assert_eq!(err.position.start.column, 4);
+    let err = checked("case 0 of _ => 0").expect_err("`0` is not a variant.");
+    assert_eq!(
+        err.to_string(),
+        "1:6: Expected a variant type, but found `Nat`."
+    );
+    let err = checked("case inl 0 as Nat + Nat of inl n => n | inr n => iszero n")
+        .expect_err("The branches differ.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::CaseBranchesDiffer {
+            first: Type::Nat,
+            other: Type::Bool,
+        }
+    );
+    assert_eq!(err.position.start.column, 49);
+}
+
+#[test]
+fn non_exhaustive_and_redundant_cases_are_warned_about() {
+    let ty = "<a:Nat, b:Nat, c:Nat>";
+    assert_eq!(
+        warnings(&format!("case <a=0> as {} of <b=x> => x", ty)),
+        ["1:1: Warning: `case` has no branch for `a`, `c`."]
+    );
+    assert_eq!(
+        warnings(&format!(
+            "case <a=0> as {} of <a=x> => x | _ => 0 | <b=x> => x",
+            ty
+        )),
+        ["1:62: Warning: This branch is never taken."]
+    );
+    assert_eq!(
+        warnings(&format!(
+            "case <a=0> as {} of <a=x> => x | <a=y> => y | <b=x> => x | <c=x> => x | _ => 0",
+            ty
+        )),
+        [
+            "1:53: Warning: This branch is never taken.",
+            "1:92: Warning: This branch is never taken.",
+        ]
+    );
+    assert!(warnings(&format!("case <a=0> as {} of <c=x> => x | _ => 0", ty)).is_empty());
+
+    let src = "case inl (case inl 0 as Nat + Nat of inl n => n) as Nat + Nat of _ => 0";
+    let term = Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.");
+    let (_, warnings) =
+        type_of_with_warnings(&term, &TypeContext::empty()).expect("Expected a well-typed term.");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].kind,
+        WarningKind::NonExhaustive {
+            missing: vec!["inr".to_string()]
+        }
+    );
+    assert_eq!(warnings[0].position.start.column, 10);
+}
+
+#[test]
+fn fix_takes_a_function_from_a_type_to_itself() {
+    assert_eq!(checked(r"fix (\x:Nat. succ x)"), Ok(Type::Nat));
+    assert_eq!(
+        checked(
+            r"letrec iseven:Nat -> Bool =
+                \x:Nat. if iszero x then true else if iszero (pred x) then false else iseven (pred (pred x))
+              in iseven"
+        ),
+        Ok(ty("Nat -> Bool"))
+    );
+
+    let err = checked(r"letrec f:Nat -> Nat = \n:Nat. iszero n in f").expect_err("Not `Nat`.");
+    assert_eq!(
+        err.to_string(),
+        "1:23: Expected a term of type `Nat -> Nat`, but found a term of type `Nat -> Bool`."
+    );
+    let err = checked(r"\f:Nat -> Bool. fix f").expect_err("Not `Nat -> Nat`.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::Mismatch {
+            expected: ty("Nat -> Nat"),
+            actual: ty("Nat -> Bool"),
+        }
+    );
+    let err = checked("fix 0").expect_err("`0` is not a function.");
+    assert_eq!(err.kind, TypeErrKind::CalleeNotArrow(Type::Nat));
+}
+
+#[test]
+fn lists() {
+    let examples = [
+        ("nil[Nat]", "List Nat"),
+        ("[true, false] as List Bool", "List Bool"),
+        (
+            r"\l:List Nat. if isnil[Nat] l then 0 else head[Nat] l",
+            "List Nat -> Nat",
+        ),
+        ("tail[Unit] ([unit] as List Unit)", "List Unit"),
+        ("head[Nat] nil[Nat]", "Nat"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+
+    let err = checked("[1, true] as List Nat").expect_err("`true` is not a `Nat`.");
+    assert_eq!(
+        err.to_string(),
+        "1:5: Expected a term of type `Nat`, but found a term of type `Bool`."
+    );
+    let err = checked("cons[Nat] 0 nil[Bool]").expect_err("Not a `List Nat`.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::Mismatch {
+            expected: ty("List Nat"),
+            actual: ty("List Bool"),
+        }
+    );
+    assert_eq!(err.position.start.column, 12);
+    let err = checked("head[Nat] 0").expect_err("`0` is not a list.");
+    assert_eq!(
+        err.to_string(),
+        "1:11: Expected a term of type `List Nat`, but found a term of type `Nat`."
+    );
+}
+
+#[test]
+fn references() {
+    let examples = [
+        ("ref 0", "Ref Nat"),
+        (r"\r:Ref Nat. !r", "Ref Nat -> Nat"),
+        (r"\r:Ref Nat. r := succ (!r); !r", "Ref Nat -> Nat"),
+        (r"(\f:Ref (Nat -> Nat). !f 0) (ref (\n:Nat. n))", "Nat"),
+        ("let r = ref unit in r := !r", "Unit"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+
+    let err = checked("!0").expect_err("`0` is not a reference.");
+    assert_eq!(
+        err.to_string(),
+        "1:2: Expected a reference, but found a term of type `Nat`."
+    );
+    assert_eq!(err.kind, TypeErrKind::NotRef(Type::Nat));
+    let err = checked("unit := 0").expect_err("`unit` is not a reference.");
+    assert_eq!(err.kind, TypeErrKind::NotRef(Type::Unit));
+    let err = checked("ref 0 := true").expect_err("`true` is not a `Nat`.");
+    assert_eq!(
+        err.to_string(),
+        "1:10: Expected a term of type `Nat`, but found a term of type `Bool`."
+    );
+}
+
+/// Locations only appear during evaluation, so their types come from a store typing (T-Loc).
+#[test]
+fn locations_are_typed_by_the_store_typing() {
+    let term = Term::from_named(parse("ref true").expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.");
+    let mut store = Store::new();
+    let loc = eval1(term, &mut store).expect("Expected a step.");
+    let err = type_of_in_store(&loc, &TypeContext::empty(), &StoreTyping::empty())
+        .expect_err("The store typing is empty.");
+    assert_eq!(err.kind, TypeErrKind::UnboundLoc(0));
+    assert_eq!(
+        err.to_string(),
+        "1:1: Cannot find location #0 in the store typing."
+    );
+
+    let mut store_typing = StoreTyping::empty();
+    store_typing.push(Type::Bool);
+    assert_eq!(
+        type_of_in_store(&loc, &TypeContext::empty(), &store_typing),
+        Ok(ty("Ref Bool"))
+    );
+}
+
+#[test]
+fn error_stands_in_for_any_type() {
+    let examples = [
+        ("error", "Bot"),
+        ("succ error", "Nat"),
+        (r"\n:Nat. if iszero n then error else pred n", "Nat -> Nat"),
+        (r"\n:Nat. error", "Nat -> Bot"),
+        (r"(\f:Nat -> Nat. f 0) (\n:Nat. error)", "Nat"),
+        ("error 0 true", "Bot"),
+        ("{error, 0}.1", "Bot"),
+        (
+            "case inl 0 as Nat + Nat of inl x => error | inr y => y",
+            "Nat",
+        ),
+        ("raise 3", "Bot"),
+        ("let x = raise 3 in iszero x", "Bool"),
+        ("case error of <a=x> => x", "Bot"),
+        ("case error of <a=x> => succ x | <b=y> => 0", "Nat"),
+        ("let {x=a} = error in a", "Bot"),
+        ("let {x=a, y={b, c}} = error in if a then b else c", "Bot"),
+        ("let {x=a} = error in iszero a", "Bool"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+
+    // The labels of a `Bot` scrutinee are unknown, so no branch can be missing.
+    assert_eq!(
+        warnings("case error of <a=x> => x | <a=y> => y | _ => 0"),
+        ["1:28: Warning: This branch is never taken."]
+    );
+
+    // `Bot` cannot stand in for the parameter of a function.
+    let err = checked(r"(\f:Nat -> Nat. f 0) (\n:Bot. 0)").expect_err("Parameters differ.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::ArgMismatch {
+            param: ty("Nat -> Nat"),
+            arg: ty("Bot -> Nat"),
+        }
+    );
+}
+
+#[test]
+fn try_and_raise() {
+    let examples = [
+        ("try pred error with 0", "Nat"),
+        ("try raise 3 with e => succ e", "Nat"),
+        ("try raise 3 with _ => unit", "Unit"),
+        ("try error with error", "Bot"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+
+    let err = checked("try 0 with true").expect_err("`true` is not a `Nat`.");
+    assert_eq!(
+        err.to_string(),
+        "1:12: Expected the handler of `try` to have the type `Nat` of the term it guards, but it has type `Bool`."
+    );
+    let err = checked("raise true").expect_err("The exception type is `Nat`.");
+    assert_eq!(
+        err.to_string(),
+        "1:7: Expected a term of type `Nat`, but found a term of type `Bool`."
+    );
+}
+
+#[test]
+fn the_exception_type_is_configurable() {
+    let exn = ty("<divideByZero:Unit, overflow:Unit>");
+    let ctx = TypeContext::empty().with_exn_type(exn.clone());
+    assert_eq!(TypeContext::empty().exn_type(), &Type::Nat);
+    assert_eq!(ctx.exn_type(), &exn);
+
+    let src = format!(
+        r"\n:Nat. try (if iszero n then raise <divideByZero=unit> as {exn} else n)
+          with e => case e of <divideByZero=u> => 0 | <overflow=u> => n",
+        exn = exn
+    );
+    let term = Term::from_named(parse(&src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.");
+    assert_eq!(type_of(&term, &ctx), Ok(ty("Nat -> Nat")));
+    assert!(type_of(&term, &TypeContext::empty()).is_err());
+}
+
+/// Without the subtyping of `chapter_15`, records must match exactly
+/// and the branches of `if` must have the same type.
+#[test]
+fn types_must_be_equal_where_bot_does_not_stand_in() {
+    let err = checked(r"(\r:{x:Nat}. r.x) {x=0, y=1}").expect_err("`y` is extra.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::ArgMismatch {
+            param: ty("{x:Nat}"),
+            arg: ty("{x:Nat, y:Nat}"),
+        }
+    );
+    let err = checked("if true then 0 else false").expect_err("The branches differ.");
+    assert_eq!(
+        err.to_string(),
+        "1:21: Expected the branches of `if` to have the same type, \
+         but they have types `Nat` and `Bool`."
+    );
+}
@@ -0,0 +1,8 @@
+//! Subtyping (Chapter 15) for the language of `chapter_11`, with `Top`,
+//! joins and meets, and type checking by the algorithmic rules of Chapter 16.
+
+pub mod subtyping;
+pub mod type_checker;
+
+pub use subtyping::{join, meet, subtype};
+pub use type_checker::type_of;
@@ -0,0 +1,586 @@
+#[cfg(test)]
+mod test;
+
+use crate::chapter_11::ty::{self, Type};
+use std::fmt;
+
+/// Why `sub <: sup` does not hold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotSubtypeErr {
+    pub sub: Type,
+    pub sup: Type,
+    /// The components of `sub` and `sup` in which the failure lies, from the outside in.
+    pub path: Vec<Component>,
+    pub failure: Box<Failure>,
+}
+
+/// A component of a pair of types whose subtyping is checked to check theirs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Component {
+    /// The parameters of two arrows, for which subtyping goes the other way (S-Arrow).
+    Param,
+    Result,
+    Field(String),
+    Tag(String),
+    Element,
+    /// The contents of two references, each of which must be a subtype of the other (S-Ref).
+    Contents,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// No rule relates the types, as for `Nat` and `Bool`, or a record and an arrow.
+    Unrelated { sub: Type, sup: Type },
+    /// The record `sub` lacks the field `label` of the record `sup`.
+    MissingLabel { sub: Type, sup: Type, label: String },
+    /// The variant `sub` has the tag `label`, which the variant `sup` lacks.
+    ExtraTag { sub: Type, sup: Type, label: String },
+}
+
+impl NotSubtypeErr {
+    /// Which component broke subtyping and how, such as
+    /// ``in the parameter, `{x:Nat}` lacks the label `y` ``,
+    /// or `None` if `sub` and `sup` are simply unrelated.
+    pub fn reason(&self) -> Option<String> {
+        let failure = match &*self.failure {
+            Failure::Unrelated { .. } if self.path.is_empty() => return None,
+            Failure::Unrelated { sub, sup } => format!("`{}` is not a subtype of `{}`", sub, sup),
+            Failure::MissingLabel { sub, label, .. } => {
+                format!("`{}` lacks the label `{}`", sub, label)
+            }
+            Failure::ExtraTag { sub, sup, label } => {
+                format!("`{}` has the tag `{}`, which `{}` lacks", sub, label, sup)
+            }
+        };
+        if self.path.is_empty() {
+            return Some(failure);
+        }
+        let components: Vec<_> = self
+            .path
+            .iter()
+            .rev()
+            .map(|component| match component {
+                Component::Param => "the parameter".to_string(),
+                Component::Result => "the result".to_string(),
+                Component::Field(label) => format!("the field `{}`", label),
+                Component::Tag(label) => format!("the tag `{}`", label),
+                Component::Element => "the elements".to_string(),
+                Component::Contents => "the contents of the reference".to_string(),
+            })
+            .collect();
+        Some(format!("in {}, {}", components.join(" of "), failure))
+    }
+}
+
+/// Writes ``"`{x:Nat}` is not a subtype of `{y:Nat}`: `{x:Nat}` lacks the label `y`."``
+impl fmt::Display for NotSubtypeErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a subtype of `{}`", self.sub, self.sup)?;
+        match self.reason() {
+            Some(reason) => write!(f, ": {}.", reason),
+            None => write!(f, "."),
+        }
+    }
+}
+
+/// Whether `sub <: sup`, using the algorithmic rules of Figure 16-2,
+/// extended with `Top` and `Bot` (Sections 15.4 and 16.4),
+/// variants and lists (Section 15.5) and references (Section 15.5, S-Ref).
+///
+/// The algorithm is syntax directed: S-Refl is only needed for base types
+/// and S-Trans not at all (Lemma 16.1.2), and checking each field of `sup` against
+/// the field of `sub` with the same label combines S-RcdWidth, S-RcdDepth and S-RcdPerm.
+pub fn subtype(sub: &Type, sup: &Type) -> Result<(), NotSubtypeErr> {
+    check(sub, sup).map_err(|(mut path, failure)| {
+        path.reverse();
+        NotSubtypeErr {
+            sub: sub.clone(),
+            sup: sup.clone(),
+            path,
+            failure: Box::new(failure),
+        }
+    })
+}
+
+/// Like `subtype`, but with the path of a failure from the inside out.
+fn check(sub: &Type, sup: &Type) -> Result<(), (Vec<Component>, Failure)> {
+    match (sub, sup) {
+        _ if sub == sup => Ok(()),
+        (_, Type::Top) | (Type::Bot, _) => Ok(()),
+        (Type::Arrow(sub_param, sub_result), Type::Arrow(sup_param, sup_result)) => {
+            within(Component::Param, check(sup_param, sub_param))?;
+            within(Component::Result, check(sub_result, sup_result))
+        }
+        (Type::Record(sub_fields), Type::Record(sup_fields)) => {
+            for (label, sup_field) in sup_fields {
+                match sub.field(label) {
+                    Some(sub_field) => {
+                        within(Component::Field(label.clone()), check(sub_field, sup_field))?
+                    }
+                    None => {
+                        return Err((
+                            vec![],
+                            Failure::MissingLabel {
+                                sub: Type::Record(sub_fields.clone()),
+                                sup: Type::Record(sup_fields.clone()),
+                                label: label.clone(),
+                            },
+                        ))
+                    }
+                }
+            }
+            Ok(())
+        }
+        (Type::Variant(sub_fields), Type::Variant(sup_fields)) => {
+            for (label, sub_field) in sub_fields {
+                match sup.field(label) {
+                    Some(sup_field) => {
+                        within(Component::Tag(label.clone()), check(sub_field, sup_field))?
+                    }
+                    None => {
+                        return Err((
+                            vec![],
+                            Failure::ExtraTag {
+                                sub: Type::Variant(sub_fields.clone()),
+                                sup: Type::Variant(sup_fields.clone()),
+                                label: label.clone(),
+                            },
+                        ))
+                    }
+                }
+            }
+            Ok(())
+        }
+        (Type::List(sub_element), Type::List(sup_element)) => {
+            within(Component::Element, check(sub_element, sup_element))
+        }
+        (Type::Ref(sub_contents), Type::Ref(sup_contents)) => within(
+            Component::Contents,
+            check(sub_contents, sup_contents).and_then(|()| check(sup_contents, sub_contents)),
+        ),
+        _ => Err((
+            vec![],
+            Failure::Unrelated {
+                sub: sub.clone(),
+                sup: sup.clone(),
+            },
+        )),
+    }
+}
+
+fn within(
+    component: Component,
+    result: Result<(), (Vec<Component>, Failure)>,
+) -> Result<(), (Vec<Component>, Failure)> {
+    result.map_err(|(mut path, failure)| {
+        path.push(component);
+        (path, failure)
+    })
+}
+
+/// The least common supertype of `a` and `b` (Section 16.3),
+/// which is the type of `if` whose branches have these types (TA-If).
+/// With `Top`, every pair of types has a join, if only `Top` itself.
+pub fn join(a: &Type, b: &Type) -> Type {
+    if subtype(a, b).is_ok() {
+        return b.clone();
+    }
+    if subtype(b, a).is_ok() {
+        return a.clone();
+    }
+    match (a, b) {
+        (Type::Arrow(a_param, a_result), Type::Arrow(b_param, b_result)) => {
+            ty::arrow(meet(a_param, b_param), join(a_result, b_result))
+        }
+        // The labels the records have in common.
+        (Type::Record(a_fields), Type::Record(_)) => Type::Record(
+            a_fields
+                .iter()
+                .filter_map(|(label, a_field)| {
+                    b.field(label)
+                        .map(|b_field| (label.clone(), join(a_field, b_field)))
+                })
+                .collect(),
+        ),
+        // The tags of either variant.
+        (Type::Variant(a_fields), Type::Variant(b_fields)) => Type::Variant(
+            a_fields
+                .iter()
+                .map(|(label, a_field)| match b.field(label) {
+                    Some(b_field) => (label.clone(), join(a_field, b_field)),
+                    None => (label.clone(), a_field.clone()),
+                })
+                .chain(
+                    b_fields
+                        .iter()
+                        .filter(|(label, _)| a.field(label).is_none())
+                        .cloned(),
+                )
+                .collect(),
+        ),
+        (Type::List(a_element), Type::List(b_element)) => ty::list(join(a_element, b_element)),
+        _ => Type::Top,
+    }
+}
+
+/// The greatest common subtype of `a` and `b` (Section 16.3),
+/// which `join` needs for the parameters of arrows.
+/// With `Bot`, every pair of types has a meet, if only `Bot` itself (Section 16.4).
+pub fn meet(a: &Type, b: &Type) -> Type {
+    if subtype(a, b).is_ok() {
+        return a.clone();
+    }
+    if subtype(b, a).is_ok() {
+        return b.clone();
+    }
+    match (a, b) {
+        (Type::Arrow(a_param, a_result), Type::Arrow(b_param, b_result)) => {
+            ty::arrow(join(a_param, b_param), meet(a_result, b_result))
+        }
+        // The labels of either record.
+        (Type::Record(a_fields), Type::Record(b_fields)) => Type::Record(
+            a_fields
+                .iter()
+                .map(|(label, a_field)| match b.field(label) {
+                    Some(b_field) => (label.clone(), meet(a_field, b_field)),
+                    None => (label.clone(), a_field.clone()),
+                })
+                .chain(
+                    b_fields
+                        .iter()
+                        .filter(|(label, _)| a.field(label).is_none())
+                        .cloned(),
+                )
+                .collect(),
+        ),
+        // The tags the variants have in common.
+        (Type::Variant(a_fields), Type::Variant(_)) => Type::Variant(
+            a_fields
+                .iter()
+                .filter_map(|(label, a_field)| {
+                    b.field(label)
+                        .map(|b_field| (label.clone(), meet(a_field, b_field)))
+                })
+                .collect(),
+        ),
+        (Type::List(a_element), Type::List(b_element)) => ty::list(meet(a_element, b_element)),
+        _ => Type::Bot,
+    }
+}
+
+/// A derivation of `S <: T` with the declarative rules of Figure 15-1 and 15-3,
+/// which `conclusion` checks. `derive` builds one for any `S <: T` that `subtype` accepts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Derivation {
+    /// S-Refl: `S <: S`.
+    Refl(Type),
+    /// S-Trans: from `S <: U` and `U <: T`, `S <: T`.
+    Trans(Box<Derivation>, Box<Derivation>),
+    /// S-Top: `S <: Top`.
+    Top(Type),
+    /// S-Bot: `Bot <: T`.
+    Bot(Type),
+    /// S-Arrow: from `T1 <: S1` and `S2 <: T2`, `S1 -> S2 <: T1 -> T2`.
+    Arrow {
+        param: Box<Derivation>,
+        result: Box<Derivation>,
+    },
+    /// S-RcdWidth: a record is a subtype of the record of its first `kept` fields.
+    RcdWidth {
+        fields: Vec<(String, Type)>,
+        kept: usize,
+    },
+    /// S-RcdDepth: from `Si <: Ti` for each label `li`, `{li:Si} <: {li:Ti}`.
+    RcdDepth(Vec<(String, Derivation)>),
+    /// S-RcdPerm: a record is a subtype of any record with its fields in another order.
+    RcdPerm {
+        sub: Vec<(String, Type)>,
+        sup: Vec<(String, Type)>,
+    },
+    /// S-VariantWidth: the variant of the first `kept` tags of `fields`
+    /// is a subtype of the variant of all of them.
+    VariantWidth {
+        fields: Vec<(String, Type)>,
+        kept: usize,
+    },
+    /// S-VariantDepth: from `Si <: Ti` for each tag `li`, `<li:Si> <: <li:Ti>`.
+    VariantDepth(Vec<(String, Derivation)>),
+    /// S-VariantPerm: a variant is a subtype of any variant with its tags in another order.
+    VariantPerm {
+        sub: Vec<(String, Type)>,
+        sup: Vec<(String, Type)>,
+    },
+    /// S-List: from `S <: T`, `List S <: List T`.
+    List(Box<Derivation>),
+    /// S-Ref: from `S <: T` and `T <: S`, `Ref S <: Ref T`.
+    Ref(Box<Derivation>, Box<Derivation>),
+}
+
+impl Derivation {
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Derivation::Refl(_) => "S-Refl",
+            Derivation::Trans(_, _) => "S-Trans",
+            Derivation::Top(_) => "S-Top",
+            Derivation::Bot(_) => "S-Bot",
+            Derivation::Arrow { .. } => "S-Arrow",
+            Derivation::RcdWidth { .. } => "S-RcdWidth",
+            Derivation::RcdDepth(_) => "S-RcdDepth",
+            Derivation::RcdPerm { .. } => "S-RcdPerm",
+            Derivation::VariantWidth { .. } => "S-VariantWidth",
+            Derivation::VariantDepth(_) => "S-VariantDepth",
+            Derivation::VariantPerm { .. } => "S-VariantPerm",
+            Derivation::List(_) => "S-List",
+            Derivation::Ref(_, _) => "S-Ref",
+        }
+    }
+
+    /// The premises of the rule, in the order the rule lists them.
+    pub fn premises(&self) -> Vec<&Derivation> {
+        match self {
+            Derivation::Trans(first, second) | Derivation::Ref(first, second) => {
+                vec![first, second]
+            }
+            Derivation::Arrow { param, result } => vec![param, result],
+            Derivation::RcdDepth(fields) | Derivation::VariantDepth(fields) => {
+                fields.iter().map(|(_, premise)| premise).collect()
+            }
+            Derivation::List(element) => vec![element],
+            _ => vec![],
+        }
+    }
+
+    /// The `(S, T)` of the `S <: T` this derives,
+    /// or `None` if the premises do not fit the rule, such as S-Trans whose premises
+    /// `S <: U` and `U' <: T` have different `U` and `U'`.
+    pub fn conclusion(&self) -> Option<(Type, Type)> {
+        match self {
+            Derivation::Refl(ty) => Some((ty.clone(), ty.clone())),
+            Derivation::Trans(first, second) => {
+                let (sub, middle) = first.conclusion()?;
+                let (other_middle, sup) = second.conclusion()?;
+                if middle == other_middle {
+                    Some((sub, sup))
+                } else {
+                    None
+                }
+            }
+            Derivation::Top(ty) => Some((ty.clone(), Type::Top)),
+            Derivation::Bot(ty) => Some((Type::Bot, ty.clone())),
+            Derivation::Arrow { param, result } => {
+                let (sup_param, sub_param) = param.conclusion()?;
+                let (sub_result, sup_result) = result.conclusion()?;
+                Some((
+                    ty::arrow(sub_param, sub_result),
+                    ty::arrow(sup_param, sup_result),
+                ))
+            }
+            Derivation::RcdWidth { fields, kept } if *kept <= fields.len() => Some((
+                Type::Record(fields.clone()),
+                Type::Record(fields[..*kept].to_vec()),
+            )),
+            Derivation::RcdDepth(fields) => depth_conclusion(fields, Type::Record),
+            Derivation::RcdPerm { sub, sup } if is_permutation(sub, sup) => {
+                Some((Type::Record(sub.clone()), Type::Record(sup.clone())))
+            }
+            Derivation::VariantWidth { fields, kept } if *kept <= fields.len() => Some((
+                Type::Variant(fields[..*kept].to_vec()),
+                Type::Variant(fields.clone()),
+            )),
+            Derivation::VariantDepth(fields) => depth_conclusion(fields, Type::Variant),
+            Derivation::VariantPerm { sub, sup } if is_permutation(sub, sup) => {
+                Some((Type::Variant(sub.clone()), Type::Variant(sup.clone())))
+            }
+            Derivation::List(element) => {
+                let (sub, sup) = element.conclusion()?;
+                Some((ty::list(sub), ty::list(sup)))
+            }
+            Derivation::Ref(forward, backward) => {
+                let (sub, sup) = forward.conclusion()?;
+                if backward.conclusion()? == (sup.clone(), sub.clone()) {
+                    Some((ty::reference(sub), ty::reference(sup)))
+                } else {
+                    None
+                }
+            }
+            Derivation::RcdWidth { .. }
+            | Derivation::RcdPerm { .. }
+            | Derivation::VariantWidth { .. }
+            | Derivation::VariantPerm { .. } => None,
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}{}: ", "", self.rule(), indent = 2 * depth)?;
+        match self.conclusion() {
+            Some((sub, sup)) => write!(f, "{} <: {}", sub, sup)?,
+            None => write!(f, "(invalid)")?,
+        }
+        for premise in self.premises() {
+            writeln!(f)?;
+            premise.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// The conclusion of S-RcdDepth or S-VariantDepth, whose types `kind` builds from fields.
+fn depth_conclusion(
+    fields: &[(String, Derivation)],
+    kind: fn(Vec<(String, Type)>) -> Type,
+) -> Option<(Type, Type)> {
+    let mut sub_fields = vec![];
+    let mut sup_fields = vec![];
+    for (label, premise) in fields {
+        let (sub, sup) = premise.conclusion()?;
+        sub_fields.push((label.clone(), sub));
+        sup_fields.push((label.clone(), sup));
+    }
+    Some((kind(sub_fields), kind(sup_fields)))
+}
+
+/// Whether `b` has the fields of `a`, which has no label twice, in some order.
+fn is_permutation(a: &[(String, Type)], b: &[(String, Type)]) -> bool {
+    a.len() == b.len()
+        && a.iter().all(|field| b.contains(field))
+        && a.iter()
+            .enumerate()
+            .all(|(i, (label, _))| a[..i].iter().all(|(other, _)| other != label))
+}
+
+/// Writes the conclusion of each rule on its own line, as `S-Trans: S <: T`,
+/// followed by its premises, indented.
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// A derivation of `sub <: sup` with the declarative rules, if `subtype` accepts them.
+/// Records are taken apart as in the proof of Lemma 16.1.2:
+/// S-RcdPerm brings the fields of `sup` to the front, S-RcdWidth drops the others
+/// and S-RcdDepth relates the fields that remain, joined by S-Trans.
+pub fn derive(sub: &Type, sup: &Type) -> Result<Derivation, NotSubtypeErr> {
+    subtype(sub, sup)?;
+    Ok(build(sub, sup))
+}
+
+/// The derivation of `sub <: sup`, which holds.
+fn build(sub: &Type, sup: &Type) -> Derivation {
+    match (sub, sup) {
+        _ if sub == sup => Derivation::Refl(sub.clone()),
+        (_, Type::Top) => Derivation::Top(sub.clone()),
+        (Type::Bot, _) => Derivation::Bot(sup.clone()),
+        (Type::Arrow(sub_param, sub_result), Type::Arrow(sup_param, sup_result)) => {
+            Derivation::Arrow {
+                param: Box::new(build(sup_param, sub_param)),
+                result: Box::new(build(sub_result, sup_result)),
+            }
+        }
+        (Type::Record(sub_fields), Type::Record(sup_fields)) => {
+            build_record(sub_fields, sup_fields)
+        }
+        (Type::Variant(sub_fields), Type::Variant(sup_fields)) => {
+            build_variant(sub_fields, sup_fields)
+        }
+        (Type::List(sub_element), Type::List(sup_element)) => {
+            Derivation::List(Box::new(build(sub_element, sup_element)))
+        }
+        (Type::Ref(sub_contents), Type::Ref(sup_contents)) => Derivation::Ref(
+            Box::new(build(sub_contents, sup_contents)),
+            Box::new(build(sup_contents, sub_contents)),
+        ),
+        _ => unreachable!("`{}` is not a subtype of `{}`.", sub, sup),
+    }
+}
+
+fn build_record(sub: &[(String, Type)], sup: &[(String, Type)]) -> Derivation {
+    let kept = sup.len();
+    let permuted: Vec<_> = sup
+        .iter()
+        .map(|(label, _)| sub.iter().find(|(l, _)| l == label).cloned())
+        .map(|field| field.expect("`subtype` checked every label."))
+        .chain(
+            sub.iter()
+                .filter(|(label, _)| sup.iter().all(|(l, _)| l != label))
+                .cloned(),
+        )
+        .collect();
+
+    let mut steps = vec![];
+    if permuted != sub {
+        steps.push(Derivation::RcdPerm {
+            sub: sub.to_vec(),
+            sup: permuted.clone(),
+        });
+    }
+    if permuted.len() > kept {
+        steps.push(Derivation::RcdWidth {
+            fields: permuted.clone(),
+            kept,
+        });
+    }
+    if permuted[..kept] != *sup {
+        steps.push(Derivation::RcdDepth(
+            permuted[..kept]
+                .iter()
+                .zip(sup)
+                .map(|((label, sub_field), (_, sup_field))| {
+                    (label.clone(), build(sub_field, sup_field))
+                })
+                .collect(),
+        ));
+    }
+    chain(steps)
+}
+
+/// Like `build_record`, but since a variant with fewer tags is the subtype,
+/// S-VariantDepth comes first, then S-VariantWidth adds the other tags of `sup`
+/// and S-VariantPerm puts them in its order.
+fn build_variant(sub: &[(String, Type)], sup: &[(String, Type)]) -> Derivation {
+    let kept = sub.len();
+    let widened: Vec<_> = sub
+        .iter()
+        .map(|(label, _)| sup.iter().find(|(l, _)| l == label).cloned())
+        .map(|field| field.expect("`subtype` checked every tag."))
+        .chain(
+            sup.iter()
+                .filter(|(label, _)| sub.iter().all(|(l, _)| l != label))
+                .cloned(),
+        )
+        .collect();
+
+    let mut steps = vec![];
+    if widened[..kept] != *sub {
+        steps.push(Derivation::VariantDepth(
+            sub.iter()
+                .zip(&widened)
+                .map(|((label, sub_field), (_, sup_field))| {
+                    (label.clone(), build(sub_field, sup_field))
+                })
+                .collect(),
+        ));
+    }
+    if widened.len() > kept {
+        steps.push(Derivation::VariantWidth {
+            fields: widened.clone(),
+            kept,
+        });
+    }
+    if widened != sup {
+        steps.push(Derivation::VariantPerm {
+            sub: widened,
+            sup: sup.to_vec(),
+        });
+    }
+    chain(steps)
+}
+
+/// Joins the derivations of `S <: U1`, `U1 <: U2`, ..., `Un <: T` into one of `S <: T`
+/// with S-Trans.
+fn chain(mut steps: Vec<Derivation>) -> Derivation {
+    let last = steps.pop().expect("Built no step between different types.");
+    steps.into_iter().rev().fold(last, |rest, step| {
+        Derivation::Trans(Box::new(step), Box::new(rest))
+    })
+}
@@ -0,0 +1,248 @@
+use super::{derive, join, meet, subtype, Component, Derivation, Failure, NotSubtypeErr};
+use crate::chapter_11::{parser::parse_type, Type};
+
+fn ty(src: &str) -> Type {
+    parse_type(src).expect("Cannot parse.")
+}
+
+fn fields(src: &str) -> Vec<(String, Type)> {
+    match ty(src) {
+        Type::Record(fields) | Type::Variant(fields) => fields,
+        other => panic!("Expected a record or variant, but found `{}`.", other),
+    }
+}
+
+#[test]
+fn subtypes() {
+    let examples = [
+        ("Nat", "Nat"),
+        ("Nat -> Bool", "Top"),
+        ("Bot", "{x:Nat}"),
+        // S-RcdWidth, S-RcdPerm and S-RcdDepth.
+        ("{x:Nat, y:Nat}", "{x:Nat}"),
+        ("{x:Nat, y:Bool}", "{y:Bool, x:Nat}"),
+        ("{a:{x:Nat, y:Nat}, b:{m:Nat}}", "{a:{x:Nat}, b:{}}"),
+        ("{x:{a:Nat, b:Nat}, y:{m:Nat}}", "{x:{a:Nat}}"),
+        // S-Arrow: contravariant in the parameter, covariant in the result.
+        ("{x:Nat} -> {x:Nat, y:Nat}", "{x:Nat, y:Nat} -> {x:Nat}"),
+        ("Top -> Bot", "Nat -> Nat"),
+        ("<a:Nat>", "<a:Top, b:Bool>"),
+        ("List {x:Nat, y:Nat}", "List {x:Nat}"),
+        ("Ref {x:Nat, y:Nat}", "Ref {y:Nat, x:Nat}"),
+    ];
+    for (sub, sup) in examples.iter() {
+        assert_eq!(
+            subtype(&ty(sub), &ty(sup)),
+            Ok(()),
+            "`{}` <: `{}`",
+            sub,
+            sup
+        );
+    }
+}
+
+#[test]
+fn failures_point_at_the_broken_component() {
+    let err = subtype(&ty("{x:Nat}"), &ty("{x:Nat, y:Nat}")).expect_err("`y` is missing.");
+    assert_eq!(
+        err,
+        NotSubtypeErr {
+            sub: ty("{x:Nat}"),
+            sup: ty("{x:Nat, y:Nat}"),
+            path: vec![],
+            failure: Box::new(Failure::MissingLabel {
+                sub: ty("{x:Nat}"),
+                sup: ty("{x:Nat, y:Nat}"),
+                label: "y".to_string(),
+            }),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "`{x:Nat}` is not a subtype of `{x:Nat, y:Nat}`: `{x:Nat}` lacks the label `y`."
+    );
+
+    let err = subtype(&ty("{f:{x:Nat, y:Nat} -> Nat}"), &ty("{f:{x:Nat} -> Nat}"))
+        .expect_err("Parameters are contravariant.");
+    assert_eq!(
+        err.path,
+        [Component::Field("f".to_string()), Component::Param]
+    );
+    assert_eq!(
+        err.reason().as_deref(),
+        Some("in the parameter of the field `f`, `{x:Nat}` lacks the label `y`")
+    );
+
+    let examples = [
+        ("Nat", "Bool", "`Nat` is not a subtype of `Bool`."),
+        (
+            "Nat -> Nat",
+            "Nat -> Bool",
+            "`Nat -> Nat` is not a subtype of `Nat -> Bool`: \
+             in the result, `Nat` is not a subtype of `Bool`.",
+        ),
+        (
+            "<a:Nat, b:Nat>",
+            "<a:Nat>",
+            "`<a:Nat, b:Nat>` is not a subtype of `<a:Nat>`: \
+             `<a:Nat, b:Nat>` has the tag `b`, which `<a:Nat>` lacks.",
+        ),
+        (
+            "Ref {x:Nat, y:Nat}",
+            "Ref {x:Nat}",
+            "`Ref {x:Nat, y:Nat}` is not a subtype of `Ref {x:Nat}`: \
+             in the contents of the reference, `{x:Nat}` lacks the label `y`.",
+        ),
+        (
+            "List Top",
+            "List Nat",
+            "`List Top` is not a subtype of `List Nat`: \
+             in the elements, `Top` is not a subtype of `Nat`.",
+        ),
+    ];
+    for (sub, sup, message) in examples.iter() {
+        let err = subtype(&ty(sub), &ty(sup)).expect_err("Expected a failure.");
+        assert_eq!(err.to_string(), *message);
+    }
+}
+
+#[test]
+fn joins_and_meets() {
+    let examples = [
+        ("Nat", "Nat", "Nat", "Nat"),
+        ("Nat", "Bool", "Top", "Bot"),
+        ("Bot", "Nat", "Nat", "Bot"),
+        (
+            "{x:Nat, y:Bool}",
+            "{y:Bool, z:Unit}",
+            "{y:Bool}",
+            "{x:Nat, y:Bool, z:Unit}",
+        ),
+        ("{x:Nat}", "{x:Bool}", "{x:Top}", "{x:Bot}"),
+        (
+            "{x:Nat} -> {a:Nat, b:Nat}",
+            "{y:Nat} -> {b:Nat, c:Nat}",
+            "{x:Nat, y:Nat} -> {b:Nat}",
+            "{} -> {a:Nat, b:Nat, c:Nat}",
+        ),
+        ("<a:Nat>", "<b:Bool>", "<a:Nat, b:Bool>", "<>"),
+        (
+            "List {x:Nat}",
+            "List {y:Nat}",
+            "List {}",
+            "List {x:Nat, y:Nat}",
+        ),
+        ("Ref Nat", "Ref Bool", "Top", "Bot"),
+    ];
+    for (a, b, expected_join, expected_meet) in examples.iter() {
+        let (a, b) = (ty(a), ty(b));
+        let (joined, met) = (join(&a, &b), meet(&a, &b));
+        assert_eq!(joined, ty(expected_join), "`{}` ∨ `{}`", a, b);
+        assert_eq!(met, ty(expected_meet), "`{}` ∧ `{}`", a, b);
+        for bound in [&a, &b].iter() {
+            assert_eq!(
+                subtype(bound, &joined),
+                Ok(()),
+                "`{}` <: `{}`",
+                bound,
+                joined
+            );
+            assert_eq!(subtype(&met, bound), Ok(()), "`{}` <: `{}`", met, bound);
+        }
+    }
+}
+
+#[test]
+fn derivations_use_the_declarative_rules() {
+    let derivation = derive(&ty("{x:Nat, y:Nat}"), &ty("{y:Nat}")).expect("Expected a subtype.");
+    assert_eq!(
+        derivation,
+        Derivation::Trans(
+            Box::new(Derivation::RcdPerm {
+                sub: fields("{x:Nat, y:Nat}"),
+                sup: fields("{y:Nat, x:Nat}"),
+            }),
+            Box::new(Derivation::RcdWidth {
+                fields: fields("{y:Nat, x:Nat}"),
+                kept: 1,
+            }),
+        )
+    );
+    assert_eq!(
+        derivation.to_string(),
+        "S-Trans: {x:Nat, y:Nat} <: {y:Nat}\n  \
+         S-RcdPerm: {x:Nat, y:Nat} <: {y:Nat, x:Nat}\n  \
+         S-RcdWidth: {y:Nat, x:Nat} <: {y:Nat}"
+    );
+
+    // The example of Section 15.2.
+    let derivation = derive(&ty("{x:{a:Nat, b:Nat}, y:{m:Nat}}"), &ty("{x:{a:Nat}}"))
+        .expect("Expected a subtype.");
+    assert_eq!(
+        derivation.to_string(),
+        "S-Trans: {x:{a:Nat, b:Nat}, y:{m:Nat}} <: {x:{a:Nat}}\n  \
+         S-RcdWidth: {x:{a:Nat, b:Nat}, y:{m:Nat}} <: {x:{a:Nat, b:Nat}}\n  \
+         S-RcdDepth: {x:{a:Nat, b:Nat}} <: {x:{a:Nat}}\n    \
+         S-RcdWidth: {a:Nat, b:Nat} <: {a:Nat}"
+    );
+
+    assert!(derive(&ty("{x:Nat}"), &ty("{y:Nat}")).is_err());
+}
+
+#[test]
+fn derivations_conclude_what_subtype_accepts() {
+    let types = [
+        "Nat",
+        "Top",
+        "Bot",
+        "{x:Nat, y:Nat}",
+        "{y:Nat, x:Nat}",
+        "{x:Top}",
+        "{x:Bot, z:Unit}",
+        "{x:Nat} -> {y:Nat}",
+        "{} -> {x:Nat, y:Nat}",
+        "Top -> Bot",
+        "<a:Nat>",
+        "<b:Unit, a:Top>",
+        "List {x:Nat, y:Nat}",
+        "List {x:Nat}",
+        "Ref {x:Nat, y:Nat}",
+        "Ref {y:Nat, x:Nat}",
+    ];
+    for sub in types.iter() {
+        for sup in types.iter() {
+            let (sub, sup) = (ty(sub), ty(sup));
+            match derive(&sub, &sup) {
+                Ok(derivation) => {
+                    assert_eq!(
+                        derivation.conclusion(),
+                        Some((sub.clone(), sup.clone())),
+                        "{}",
+                        derivation
+                    );
+                }
+                Err(err) => assert_eq!(subtype(&sub, &sup), Err(err)),
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid_derivations_have_no_conclusion() {
+    let trans = Derivation::Trans(
+        Box::new(Derivation::Refl(Type::Nat)),
+        Box::new(Derivation::Top(Type::Bool)),
+    );
+    assert_eq!(trans.conclusion(), None);
+    let perm = Derivation::RcdPerm {
+        sub: fields("{x:Nat, y:Nat}"),
+        sup: fields("{y:Nat, x:Bool}"),
+    };
+    assert_eq!(perm.conclusion(), None);
+    let width = Derivation::RcdWidth {
+        fields: fields("{x:Nat}"),
+        kept: 2,
+    };
+    assert_eq!(width.conclusion(), None);
+    assert_eq!(width.to_string(), "S-RcdWidth: (invalid)");
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod test;
+
+use super::subtyping::{join, subtype};
+use crate::chapter_11::term::unnamed::Term;
+use crate::chapter_11::type_checker::{
+    type_of_by, Relation, StoreTyping, TypeContext, TypeErr, Warning,
+};
+use crate::chapter_11::Type;
+
+/// Subtyping as the relation of the type checker of `chapter_11`, so that subsumption
+/// (T-Sub) is applied as in the algorithmic rules of Figure 16-3: only where a subterm
+/// must have a given type, such as an argument, and with the join of the types of the
+/// branches of `if`, `case` and `try` (Section 16.3).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subtyping {}
+
+impl Relation for Subtyping {
+    fn fits(actual: &Type, expected: &Type) -> bool {
+        subtype(actual, expected).is_ok()
+    }
+
+    fn common(a: &Type, b: &Type) -> Option<Type> {
+        Some(join(a, b))
+    }
+
+    /// The component that broke subtyping, unless the types are simply unrelated.
+    fn explain(actual: &Type, expected: &Type) -> Option<String> {
+        let reason = subtype(actual, expected).err()?.reason()?;
+        Some(format!("which is not a subtype because {}", reason))
+    }
+}
+
+/// Like `chapter_11::type_of`, but with subtyping.
+/// The result is the minimal type of `term` (Theorem 16.2.4).
+pub fn type_of(term: &Term, ctx: &TypeContext) -> Result<Type, TypeErr> {
+    type_of_in_store(term, ctx, &StoreTyping::empty())
+}
+
+/// Like `chapter_11::type_checker::type_of_in_store`, but with subtyping.
+pub fn type_of_in_store(
+    term: &Term,
+    ctx: &TypeContext,
+    store_typing: &StoreTyping,
+) -> Result<Type, TypeErr> {
+    type_of_by::<Subtyping>(term, ctx, store_typing).map(|(ty, _)| ty)
+}
+
+/// Like `chapter_11::type_checker::type_of_with_warnings`, but with subtyping.
+pub fn type_of_with_warnings(
+    term: &Term,
+    ctx: &TypeContext,
+) -> Result<(Type, Vec<Warning>), TypeErr> {
+    type_of_by::<Subtyping>(term, ctx, &StoreTyping::empty())
+}
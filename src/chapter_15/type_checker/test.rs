@@ -0,0 +1,146 @@
+use super::type_of;
+use crate::chapter_11::{
+    eval1, parse,
+    parser::parse_type,
+    store::Store,
+    term::unnamed::Term,
+    type_checker::{TypeContext, TypeErr, TypeErrKind},
+    Type,
+};
+use crate::chapter_15::subtype;
+use crate::chapter_7::term::unnamed::Context;
+
+fn unnamed(src: &str) -> Term {
+    Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.")
+}
+
+fn checked(src: &str) -> Result<Type, TypeErr> {
+    type_of(&unnamed(src), &TypeContext::empty())
+}
+
+fn ty(src: &str) -> Type {
+    parse_type(src).expect("Cannot parse.")
+}
+
+#[test]
+fn arguments_may_be_subtypes_of_parameters() {
+    let examples = [
+        // Section 15.1: a function that only needs `x` accepts a record with more fields.
+        (r"(\r:{x:Nat}. r.x) {x=0, y=1}", "Nat"),
+        // S-RcdPerm and S-RcdDepth.
+        (r"(\r:{y:Nat, x:Nat}. r.x) {x=0, y=1}", "Nat"),
+        (r"(\r:{a:{x:Nat}}. r.a.x) {a={x=0, y=1}, b=true}", "Nat"),
+        // S-Arrow: a function taking any record with `x` fits where one taking more is expected.
+        (
+            r"(\f:{x:Nat, y:Nat} -> Top. f {x=0, y=1}) (\r:{x:Nat}. r.x)",
+            "Top",
+        ),
+        (r"{x=0, y=true} as {x:Nat}", "{x:Nat}"),
+        (r"(\x:Top. x) (\n:Nat. n)", "Top"),
+        (
+            r"(\l:List {x:Nat}. l) [{x=0, y=0}] as List {x:Nat, y:Nat}",
+            "List {x:Nat}",
+        ),
+        (r"<a=0> as <a:Nat> as <a:Nat, b:Bool>", "<a:Nat, b:Bool>"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+}
+
+#[test]
+fn branches_have_the_join_of_their_types() {
+    let examples = [
+        ("if true then 0 else false", "Top"),
+        (
+            "if true then {x=0, y=true} else {y=false, z=unit}",
+            "{y:Bool}",
+        ),
+        (
+            r"if true then \r:{x:Nat}. {x=r.x, y=0} else \r:{y:Nat}. {x=r.y}",
+            "{x:Nat, y:Nat} -> {x:Nat}",
+        ),
+        (
+            "case inl 0 as Nat + Nat of inl n => {a=n, b=n} | inr n => {a=iszero n}",
+            "{a:Top}",
+        ),
+        ("try {x=0} with {x=1, y=2}", "{x:Nat}"),
+        ("try 0 with true", "Top"),
+    ];
+    for (src, expected) in examples.iter() {
+        assert_eq!(checked(src), Ok(ty(expected)), "`{}`", src);
+    }
+}
+
+#[test]
+fn mismatches_explain_which_component_is_not_a_subtype() {
+    let err = checked(r"(\r:{x:Nat, y:Nat}. r.x) {x=0}").expect_err("`y` is missing.");
+    assert_eq!(
+        err.kind,
+        TypeErrKind::Explained {
+            mismatch: Box::new(TypeErrKind::ArgMismatch {
+                param: ty("{x:Nat, y:Nat}"),
+                arg: ty("{x:Nat}"),
+            }),
+            reason: "which is not a subtype because `{x:Nat}` lacks the label `y`".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "1:26: Expected an argument of type `{x:Nat, y:Nat}`, but found a term of type `{x:Nat}`, \
+         which is not a subtype because `{x:Nat}` lacks the label `y`."
+    );
+    let err = checked(r"{a={x=0}} as {a:{x:Bool}}").expect_err("`x` differs.");
+    assert_eq!(
+        err.to_string(),
+        "1:1: Expected a term of type `{a:{x:Bool}}`, but found a term of type `{a:{x:Nat}}`, \
+         which is not a subtype because in the field `x` of the field `a`, \
+         `Nat` is not a subtype of `Bool`."
+    );
+    let err = checked(r"(\f:{x:Nat} -> Nat. f {x=0}) (\r:{x:Nat, y:Nat}. r.y)")
+        .expect_err("Parameters are contravariant.");
+    assert_eq!(
+        err.to_string(),
+        "1:31: Expected an argument of type `{x:Nat} -> Nat`, \
+         but found a term of type `{x:Nat, y:Nat} -> Nat`, \
+         which is not a subtype because in the parameter, `{x:Nat}` lacks the label `y`."
+    );
+    let err = checked(r"letrec f:Nat -> Nat = \n:Nat. iszero n in f").expect_err("Not `Nat`.");
+    assert_eq!(
+        err.to_string(),
+        "1:23: Expected a term of type `Nat -> Nat`, but found a term of type `Nat -> Bool`, \
+         which is not a subtype because in the result, `Bool` is not a subtype of `Nat`."
+    );
+    let err = checked("0 as Bool").expect_err("`Nat` is not `Bool`.");
+    assert_eq!(
+        err.to_string(),
+        "1:1: Expected a term of type `Bool`, but found a term of type `Nat`."
+    );
+}
+
+/// Each step gives a term whose type is a subtype of the original (Theorem 15.3.5),
+/// such as `Bot` for `error` and `raise v`, or a record with more fields.
+#[test]
+fn steps_preserve_types_up_to_subtyping() {
+    let examples = [
+        r"(\x:Nat. iszero x) (pred error)",
+        r"try (\x:Nat. {x, raise x}) 4 with e => {e, 0}",
+        r"(\r:{x:Nat}. r) {x=0, y=true}",
+        r"if true then {x=0, y=true} else {y=false, z=unit}",
+    ];
+    for src in examples.iter() {
+        let mut term = unnamed(src);
+        let ty = type_of(&term, &TypeContext::empty()).expect("Expected a type.");
+        while let Some(next) = eval1(term.clone(), &mut Store::new()) {
+            let next_type = type_of(&next, &TypeContext::empty()).expect("Expected a type.");
+            assert!(
+                subtype(&next_type, &ty).is_ok(),
+                "`{}`: `{}`",
+                src,
+                next_type
+            );
+            term = next;
+        }
+    }
+}
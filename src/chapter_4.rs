@@ -1,11 +1,14 @@
 pub mod big_step_evaluator;
 pub mod evaluator;
+pub mod latex;
 mod lexer;
 pub mod parser;
 
-pub use evaluator::eval;
+pub use evaluator::{eval, eval_count};
 pub use parser::parse;
 
+use std::fmt;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Term {
     True,
@@ -35,3 +38,40 @@ pub mod term_builder {
         Term::IsZero(Box::new(t1))
     }
 }
+
+impl Term {
+    fn is_atomic(&self) -> bool {
+        matches!(self, Term::True | Term::False | Term::Zero)
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_operand(f: &mut fmt::Formatter<'_>, t: &Term) -> fmt::Result {
+            if t.is_atomic() {
+                write!(f, "{}", t)
+            } else {
+                write!(f, "({})", t)
+            }
+        }
+
+        match self {
+            Term::True => write!(f, "true"),
+            Term::False => write!(f, "false"),
+            Term::Zero => write!(f, "0"),
+            Term::If(guard, then, else_) => write!(f, "if {} then {} else {}", guard, then, else_),
+            Term::Succ(t1) => {
+                write!(f, "succ ")?;
+                write_operand(f, t1)
+            }
+            Term::Pred(t1) => {
+                write!(f, "pred ")?;
+                write_operand(f, t1)
+            }
+            Term::IsZero(t1) => {
+                write!(f, "iszero ")?;
+                write_operand(f, t1)
+            }
+        }
+    }
+}
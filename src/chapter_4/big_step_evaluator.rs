@@ -13,9 +13,7 @@ pub fn eval(t: Term) -> Option<Term> {
                 _ => None,
             })
         }
-        Term::Succ(n) => eval(*n)
-            .filter(is_numeric_val)
-            .map(|evaluated_n| term_builder::succ(evaluated_n)),
+        Term::Succ(n) => eval(*n).filter(is_numeric_val).map(term_builder::succ),
         Term::Pred(n) => eval(*n).and_then(|evaluated_n| {
             if let Term::Zero = evaluated_n {
                 Some(Term::Zero)
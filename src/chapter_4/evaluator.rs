@@ -30,10 +30,10 @@ pub fn eval1(t: Term) -> Option<Term> {
             )
         }),
 
-        Term::Succ(n) => eval1(*n).map(|evaluated_n| term_builder::succ(evaluated_n)),
+        Term::Succ(n) => eval1(*n).map(term_builder::succ),
         Term::Pred(n) => {
             if let Term::Succ(n2) = *n {
-                if is_numeric_val(&*n2) {
+                if is_numeric_val(&n2) {
                     Some(*n2.clone())
                 } else {
                     eval1(*n2.clone())
@@ -41,18 +41,18 @@ pub fn eval1(t: Term) -> Option<Term> {
             } else if Term::Zero == *n {
                 Some(Term::Zero)
             } else {
-                eval1(*n).map(|evaluated_n| term_builder::pred(evaluated_n))
+                eval1(*n).map(term_builder::pred)
             }
         }
         Term::IsZero(n) => {
-            if is_numeric_val(&*n) {
+            if is_numeric_val(&n) {
                 if *n == Term::Zero {
                     Some(Term::True)
                 } else {
                     Some(Term::False)
                 }
             } else {
-                eval1(*n).map(|evaluated_n| term_builder::is_zero(evaluated_n))
+                eval1(*n).map(term_builder::is_zero)
             }
         }
 
@@ -62,11 +62,18 @@ pub fn eval1(t: Term) -> Option<Term> {
 
 pub fn eval(mut t: Term) -> Option<Term> {
     while !is_val(&t) {
-        t = if let Some(evaluated_t) = eval1(t) {
-            evaluated_t
-        } else {
-            return None;
-        };
+        t = eval1(t)?;
     }
     Some(t)
 }
+
+/// Evaluates `t` until no more rules apply, returning the resulting
+/// term (which is stuck if it is not a value) and the number of steps taken.
+pub fn eval_count(mut t: Term) -> (Term, usize) {
+    let mut i = 0;
+    while let Some(evaluated_t) = eval1(t.clone()) {
+        i += 1;
+        t = evaluated_t;
+    }
+    (t, i)
+}
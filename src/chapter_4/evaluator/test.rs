@@ -1,5 +1,5 @@
 use super::super::{
-    eval, parse, term_builder as tb,
+    eval, eval_count, parse, term_builder as tb,
     Term::{self, False, True, Zero},
 };
 
@@ -185,6 +185,27 @@ fn is_zero_false() {
     assert_eq!(actual, expected);
 }
 
+#[test]
+fn eval_count_value() {
+    let actual = eval_count(tb::succ(Zero));
+    let expected = (tb::succ(Zero), 0);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn eval_count_if() {
+    let actual = parse("if (if true then false else true) then succ 0 else pred 0").map(eval_count);
+    let expected = Some((Zero, 3));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn eval_count_stuck() {
+    let actual = parse("succ iszero 0").map(eval_count);
+    let expected = Some((tb::succ(True), 1));
+    assert_eq!(actual, expected);
+}
+
 fn evaluate_parsed(src: &str) -> Option<Term> {
     parse(src).and_then(eval)
 }
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod test;
+
+use super::{evaluator, term_builder, Term};
+use crate::latex::{align_steps, Chunk, Derivation};
+
+/// Typesets a term in math mode, e.g., `\mathsf{succ}\;(\mathsf{pred}\;0)`.
+pub fn term(t: &Term) -> String {
+    chunks(t)
+        .into_iter()
+        .map(|chunk| chunk.latex)
+        .collect::<Vec<_>>()
+        .join(r"\;")
+}
+
+/// Splits a term into the pieces that may be placed on separate lines.
+fn chunks(t: &Term) -> Vec<Chunk> {
+    match t {
+        Term::If(guard, then, else_) => vec![
+            keyword_then("if", guard),
+            keyword_then("then", then),
+            keyword_then("else", else_),
+        ],
+        _ => vec![Chunk {
+            latex: latex(t),
+            plain: t.to_string(),
+        }],
+    }
+}
+
+fn keyword_then(keyword: &str, t: &Term) -> Chunk {
+    Chunk {
+        latex: format!(r"\mathsf{{{}}}\;{}", keyword, latex(t)),
+        plain: format!("{} {}", keyword, t),
+    }
+}
+
+fn latex(t: &Term) -> String {
+    fn operand(t: &Term) -> String {
+        match t {
+            Term::True | Term::False | Term::Zero => latex(t),
+            _ => format!("({})", latex(t)),
+        }
+    }
+
+    match t {
+        Term::True => r"\mathsf{true}".to_string(),
+        Term::False => r"\mathsf{false}".to_string(),
+        Term::Zero => "0".to_string(),
+        Term::If(guard, then, else_) => format!(
+            r"\mathsf{{if}}\;{}\;\mathsf{{then}}\;{}\;\mathsf{{else}}\;{}",
+            latex(guard),
+            latex(then),
+            latex(else_)
+        ),
+        Term::Succ(t1) => format!(r"\mathsf{{succ}}\;{}", operand(t1)),
+        Term::Pred(t1) => format!(r"\mathsf{{pred}}\;{}", operand(t1)),
+        Term::IsZero(t1) => format!(r"\mathsf{{iszero}}\;{}", operand(t1)),
+    }
+}
+
+/// Typesets every step `evaluator::eval1` takes from `t`, one per line,
+/// breaking terms longer than `width` characters at `then` and `else`.
+pub fn trace(t: &Term, width: usize) -> String {
+    let mut terms = vec![chunks(t)];
+    let mut t = t.clone();
+    while let Some(evaluated) = evaluator::eval1(t) {
+        terms.push(chunks(&evaluated));
+        t = evaluated;
+    }
+    align_steps(&terms, width)
+}
+
+/// Builds the derivation of `t -> t'` using the rules of Figures 3-1 and 3-2,
+/// returning `None` if `t` is a normal form.
+pub fn step_derivation(t: &Term) -> Option<Derivation> {
+    step(t).map(|(derivation, _)| derivation)
+}
+
+fn step(t: &Term) -> Option<(Derivation, Term)> {
+    let (rule, premise, evaluated) = match t {
+        Term::If(guard, then, else_) => match &**guard {
+            Term::True => ("E-IfTrue", None, (**then).clone()),
+            Term::False => ("E-IfFalse", None, (**else_).clone()),
+            _ => {
+                let (premise, guard) = step(guard)?;
+                let evaluated = term_builder::if_(guard, (**then).clone(), (**else_).clone());
+                ("E-If", Some(premise), evaluated)
+            }
+        },
+        Term::Succ(t1) => {
+            let (premise, t1) = step(t1)?;
+            ("E-Succ", Some(premise), term_builder::succ(t1))
+        }
+        Term::Pred(t1) => match &**t1 {
+            Term::Zero => ("E-PredZero", None, Term::Zero),
+            Term::Succ(nv) if evaluator::is_numeric_val(nv) => ("E-PredSucc", None, (**nv).clone()),
+            _ => {
+                let (premise, t1) = step(t1)?;
+                ("E-Pred", Some(premise), term_builder::pred(t1))
+            }
+        },
+        Term::IsZero(t1) => match &**t1 {
+            Term::Zero => ("E-IszeroZero", None, Term::True),
+            Term::Succ(nv) if evaluator::is_numeric_val(nv) => ("E-IszeroSucc", None, Term::False),
+            _ => {
+                let (premise, t1) = step(t1)?;
+                ("E-IsZero", Some(premise), term_builder::is_zero(t1))
+            }
+        },
+        Term::True | Term::False | Term::Zero => return None,
+    };
+    let derivation = Derivation {
+        rule: rule.to_string(),
+        premises: premise.into_iter().collect(),
+        conclusion: format!(r"{} \longrightarrow {}", term(t), term(&evaluated)),
+    };
+    Some((derivation, evaluated))
+}
+
+/// Builds the derivation of `t ⇓ v` using the big-step rules of Exercise 3.5.17,
+/// returning `None` if `t` does not evaluate to a value.
+pub fn eval_derivation(t: &Term) -> Option<Derivation> {
+    eval(t).map(|(derivation, _)| derivation)
+}
+
+fn eval(t: &Term) -> Option<(Derivation, Term)> {
+    let (rule, premises, value) = match t {
+        Term::True | Term::False | Term::Zero => ("B-Value", vec![], t.clone()),
+        Term::If(guard, then, else_) => {
+            let (guard, guard_value) = eval(guard)?;
+            match guard_value {
+                Term::True => {
+                    let (then, value) = eval(then)?;
+                    ("B-IfTrue", vec![guard, then], value)
+                }
+                Term::False => {
+                    let (else_, value) = eval(else_)?;
+                    ("B-IfFalse", vec![guard, else_], value)
+                }
+                _ => return None,
+            }
+        }
+        Term::Succ(t1) => {
+            let (t1, value) = eval(t1)?;
+            if !evaluator::is_numeric_val(&value) {
+                return None;
+            }
+            ("B-Succ", vec![t1], term_builder::succ(value))
+        }
+        Term::Pred(t1) => {
+            let (t1, value) = eval(t1)?;
+            match value {
+                Term::Zero => ("B-PredZero", vec![t1], Term::Zero),
+                Term::Succ(nv) => ("B-PredSucc", vec![t1], *nv),
+                _ => return None,
+            }
+        }
+        Term::IsZero(t1) => {
+            let (t1, value) = eval(t1)?;
+            match value {
+                Term::Zero => ("B-IszeroZero", vec![t1], Term::True),
+                Term::Succ(_) => ("B-IszeroSucc", vec![t1], Term::False),
+                _ => return None,
+            }
+        }
+    };
+    let derivation = Derivation {
+        rule: rule.to_string(),
+        premises,
+        conclusion: format!(r"{} \Downarrow {}", term(t), term(&value)),
+    };
+    Some((derivation, value))
+}
@@ -0,0 +1,72 @@
+use super::{eval_derivation, step_derivation, term, trace};
+use crate::chapter_4::{evaluator, parse, Term};
+use crate::latex::{Derivation, Style};
+
+fn parsed(src: &str) -> Term {
+    parse(src).expect("Cannot parse.")
+}
+
+fn rules(derivation: &Derivation) -> Vec<&str> {
+    let mut rules = vec![derivation.rule.as_str()];
+    for premise in &derivation.premises {
+        rules.extend(self::rules(premise));
+    }
+    rules
+}
+
+#[test]
+fn terms() {
+    assert_eq!(
+        term(&parsed("if iszero 0 then succ (pred 0) else false")),
+        r"\mathsf{if}\;\mathsf{iszero}\;0\;\mathsf{then}\;\mathsf{succ}\;(\mathsf{pred}\;0)\;\mathsf{else}\;\mathsf{false}"
+    );
+}
+
+#[test]
+fn traces_break_at_then_and_else() {
+    let actual = trace(&parsed("if iszero 0 then succ 0 else 0"), 20);
+    let expected = r"\begin{align*}
+&\mathsf{if}\;\mathsf{iszero}\;0 \\
+&\quad \mathsf{then}\;\mathsf{succ}\;0\;\mathsf{else}\;0 \\
+\longrightarrow{} &\mathsf{if}\;\mathsf{true}\;\mathsf{then}\;\mathsf{succ}\;0 \\
+&\quad \mathsf{else}\;0 \\
+\longrightarrow{} &\mathsf{succ}\;0
+\end{align*}";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn step_derivations() {
+    let t = parsed("succ (if iszero succ 0 then 0 else 0)");
+    let derivation = step_derivation(&t).expect("Expected a step.");
+    assert_eq!(rules(&derivation), vec!["E-Succ", "E-If", "E-IszeroSucc"]);
+    let expected_conclusion = format!(
+        r"{} \longrightarrow {}",
+        term(&t),
+        term(&evaluator::eval1(t.clone()).expect("Expected a step."))
+    );
+    assert_eq!(derivation.conclusion, expected_conclusion);
+    assert_eq!(step_derivation(&parsed("succ 0")), None);
+    assert_eq!(step_derivation(&parsed("succ true")), None);
+}
+
+#[test]
+fn eval_derivations() {
+    let derivation =
+        eval_derivation(&parsed("if true then pred succ 0 else 0")).expect("Expected a value.");
+    assert_eq!(
+        rules(&derivation),
+        vec!["B-IfTrue", "B-Value", "B-PredSucc", "B-Succ", "B-Value"]
+    );
+    assert_eq!(
+        derivation.premises[1]
+            .to_latex(Style::Mathpartir)
+            .expect("Cannot typeset."),
+        r"\inferrule*[right=\textsc{B-PredSucc}]{
+  \inferrule*[right=\textsc{B-Succ}]{
+    \inferrule*[right=\textsc{B-Value}]{}{0 \Downarrow 0}
+  }{\mathsf{succ}\;0 \Downarrow \mathsf{succ}\;0}
+}{\mathsf{pred}\;(\mathsf{succ}\;0) \Downarrow 0}"
+    );
+    assert_eq!(eval_derivation(&parsed("pred true")), None);
+}
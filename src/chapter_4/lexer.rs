@@ -29,7 +29,7 @@ pub fn tokenize(mut src: &str) -> Option<Vec<Token>> {
     while !src.is_empty() {
         if src.starts_with(char::is_whitespace) || src.starts_with('(') || src.starts_with(')') {
             src = &src[1..];
-        } else if let Some((substr, token)) = get_leading_token(&src) {
+        } else if let Some((substr, token)) = get_leading_token(src) {
             out.push(token);
             src = &src[substr.len()..];
         } else {
@@ -43,10 +43,8 @@ impl<'a> Parser<'a> {
             Some(t)
         } else if let Some(t) = self.accept_pred() {
             Some(t)
-        } else if let Some(t) = self.accept_is_zero() {
-            Some(t)
         } else {
-            None
+            self.accept_is_zero()
         }
     }
 
@@ -75,3 +75,17 @@ fn if_complex_guard_then_else_parens() {
     ));
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn display_round_trips() {
+    let term = tb::if_(
+        tb::is_zero(tb::succ(Zero)),
+        tb::succ(tb::pred(tb::if_(True, Zero, Zero))),
+        tb::if_(False, tb::succ(Zero), False),
+    );
+    assert_eq!(
+        term.to_string(),
+        "if iszero (succ 0) then succ (pred (if true then 0 else 0)) else if false then succ 0 else false"
+    );
+    assert_eq!(parse(&term.to_string()), Some(term));
+}
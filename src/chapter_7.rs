@@ -1,6 +1,22 @@
+pub mod big_step_evaluator;
+pub mod church;
+pub mod combinator;
+pub mod conversion;
+pub mod debugger;
+pub mod dot;
 pub mod evaluator;
+pub mod json;
+pub mod latex;
+pub mod levels_evaluator;
+pub mod locally_nameless_evaluator;
+pub mod mixed_evaluator;
+pub mod named_evaluator;
 pub mod parser;
+pub mod statement;
 pub mod term;
+#[cfg(test)]
+mod test_util;
+pub mod trace;
 
 pub use evaluator::{eval, eval1, eval_count};
-pub use parser::parse;
+pub use parser::{parse, parse_statements};
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod test;
+
+use super::term::unnamed::{App, Term};
+
+/// Evaluates `term` using the big-step call-by-value rules of Exercise 5.3.8,
+/// returning `None` if evaluation gets stuck.
+///
+/// Like `evaluator::eval`, this does not terminate if `term` diverges.
+pub fn eval(term: Term) -> Option<Term> {
+    eval_with_fuel(term, usize::MAX).ok()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EvalErr {
+    /// Evaluation reached a term that is not a value,
+    /// but to which no rule applies.
+    Stuck(Term),
+    OutOfFuel,
+}
+
+/// Like `eval`, but gives up after `fuel` beta-reductions.
+pub fn eval_with_fuel(term: Term, fuel: usize) -> Result<Term, EvalErr> {
+    let mut fuel = fuel;
+    eval_with_fuel_ref(term, &mut fuel)
+}
+
+fn eval_with_fuel_ref(mut term: Term, fuel: &mut usize) -> Result<Term, EvalErr> {
+    // B-App's final premise is evaluated in this loop rather than
+    // recursively, so divergent terms do not overflow the stack.
+    loop {
+        match term {
+            Term::Abs(_) => return Ok(term),
+            Term::Var(_) => return Err(EvalErr::Stuck(term)),
+            Term::App(app) => {
+                let App {
+                    callee,
+                    arg,
+                    position,
+                } = *app;
+                let callee = eval_with_fuel_ref(callee, fuel)?;
+                let arg = eval_with_fuel_ref(arg, fuel)?;
+                if let Term::Abs(abs) = callee {
+                    if *fuel == 0 {
+                        return Err(EvalErr::OutOfFuel);
+                    }
+                    *fuel -= 1;
+                    term = abs.apply(&arg);
+                } else {
+                    return Err(EvalErr::Stuck(
+                        App {
+                            callee,
+                            arg,
+                            position,
+                        }
+                        .into(),
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::{eval, eval_with_fuel, EvalErr};
+use crate::chapter_7::{
+    evaluator, parse,
+    term::unnamed::{Context, Term},
+};
+use std::convert::TryFrom;
+
+#[test]
+fn abs() {
+    let actual = evaluate_parsed(r"\x. x");
+    let expected = Some(r"(\x. x)".to_string());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn identity_app() {
+    let actual = evaluate_parsed(r"(\x. x) \y. y");
+    let expected = Some(r"(\y. y)".to_string());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn args_are_evaluated_before_substitution() {
+    let actual = evaluate_parsed(r"(\a. a \b. a) ((\x. x) \b. b)");
+    let expected = Some(r"(\b. (\b'. b'))".to_string());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn agrees_with_small_step() {
+    const SRCS: [&str; 4] = [
+        r"(\a. a \b. a) \b.b",
+        r"(\t. \f. t) (\x. x) \y. y",
+        r"(\n. \s. \z. s (n s z)) \s. \z. z",
+        r"(\f. \x. f (f x)) (\f. \x. f (f x)) (\y. y)",
+    ];
+    for src in &SRCS {
+        let term = unnamed(src);
+        assert_eq!(eval(term.clone()), Some(evaluator::eval(term)));
+    }
+}
+
+#[test]
+fn free_var_is_stuck() {
+    let named = parse(r"(\x. x) y").expect("Cannot parse.");
+    let term = Term::from_named(named, &Context::from_strs(&["y"])).expect("Cannot remove names.");
+    assert!(matches!(
+        eval_with_fuel(term, 10),
+        Err(EvalErr::Stuck(Term::Var(_)))
+    ));
+}
+
+#[test]
+fn omega_runs_out_of_fuel() {
+    let actual = eval_with_fuel(unnamed(r"(\x. x x) \x. x x"), 10_000);
+    let expected = Err(EvalErr::OutOfFuel);
+    assert_eq!(actual, expected);
+}
+
+fn unnamed(src: &str) -> Term {
+    Term::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.")
+}
+
+fn evaluate_parsed(src: &str) -> Option<String> {
+    eval(unnamed(src)).map(|value| value.into_unpositioned_named().to_string())
+}
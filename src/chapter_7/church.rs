@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{eval_count_with_fuel, OutOfFuelErr, Strategy};
+use super::parse_statements;
+use super::statement::{Definitions, Statement};
+use super::term::{
+    named::{self, Term as NamedTerm},
+    unnamed::Term,
+};
+use crate::chapter_4::{self, term_builder};
+use crate::file_position::{FilePosition, FilePositionRange};
+
+/// The Church encodings of Section 5.2 that compiled terms refer to.
+/// The subterms of a compiled term that come from a definition keep their
+/// positions in this source, but the applications that `compile` builds
+/// around them are all given an empty range at its start.
+pub const PRELUDE: &str = r"
+tru = \t. \f. t;
+fls = \t. \f. f;
+test = \l. \m. \n. l m n;
+c0 = \s. \z. z;
+scc = \n. \s. \z. s (n s z);
+iszro = \m. m (\x. fls) tru;
+pair = \f. \s. \b. b f s;
+fst = \p. p tru;
+snd = \p. p fls;
+zz = pair c0 c0;
+ss = \p. pair (snd p) (scc (snd p));
+prd = \m. fst (m ss zz);
+";
+
+thread_local! {
+    /// `PRELUDE`, parsed once per thread rather than on every `compile`.
+    static DEFINITIONS: Definitions = prelude();
+}
+
+fn prelude() -> Definitions {
+    let mut defs = Definitions::empty();
+    for statement in parse_statements(PRELUDE).expect("The prelude parses.") {
+        match statement {
+            Statement::Def(def) => defs.define(def).expect("The prelude is closed."),
+            Statement::Eval(_) => unreachable!("The prelude only has definitions."),
+        }
+    }
+    defs
+}
+
+/// An empty range at the start of the source, for the terms that `compile` builds.
+pub(crate) fn position() -> FilePositionRange {
+    let start = FilePosition {
+        index: 0,
+        line: 1,
+        column: 0,
+    };
+    FilePositionRange { start, end: start }
+}
+
+fn var(name: &str) -> NamedTerm {
+    named::Var {
+        position: position(),
+        name: name.to_string(),
+    }
+    .into()
+}
+
+fn app(callee: NamedTerm, arg: NamedTerm) -> NamedTerm {
+    named::App {
+        position: position(),
+        callee,
+        arg,
+    }
+    .into()
+}
+
+/// Translates an arithmetic term into a closed lambda term,
+/// e.g., `if t1 then t2 else t3` into `test t1 t2 t3` and `pred t` into `prd t`.
+pub fn compile(t: &chapter_4::Term) -> Term {
+    fn to_named(t: &chapter_4::Term) -> NamedTerm {
+        use chapter_4::Term::*;
+        match t {
+            True => var("tru"),
+            False => var("fls"),
+            Zero => var("c0"),
+            If(guard, then, else_) => app(
+                app(app(var("test"), to_named(guard)), to_named(then)),
+                to_named(else_),
+            ),
+            Succ(t1) => app(var("scc"), to_named(t1)),
+            Pred(t1) => app(var("prd"), to_named(t1)),
+            IsZero(t1) => app(var("iszro"), to_named(t1)),
+        }
+    }
+
+    DEFINITIONS.with(|defs| {
+        defs.resolve(to_named(t))
+            .expect("Every name is defined in the prelude.")
+    })
+}
+
+/// What a compiled term is read back as.
+/// Both are needed because `fls` and `c0` are the same term.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Bool,
+    Nat,
+}
+
+/// Reduces `term` to normal form under normal order evaluation,
+/// taking at most `fuel` steps, and reads it back as a boolean or numeral.
+/// Returns `None` if the normal form is not `tru`, `fls` or a Church numeral.
+pub fn read_back(
+    term: Term,
+    kind: Kind,
+    fuel: usize,
+) -> Result<Option<chapter_4::Term>, OutOfFuelErr> {
+    let (normal_form, _) = eval_count_with_fuel(term, Strategy::NormalOrder, fuel)?;
+    Ok(match kind {
+        Kind::Bool => read_bool(&normal_form).map(|b| {
+            if b {
+                chapter_4::Term::True
+            } else {
+                chapter_4::Term::False
+            }
+        }),
+        Kind::Nat => read_nat(&normal_form)
+            .map(|n| (0..n).fold(chapter_4::Term::Zero, |t, _| term_builder::succ(t))),
+    })
+}
+
+/// Reads `\t. \f. t` as `true` and `\t. \f. f` as `false`.
+pub fn read_bool(normal_form: &Term) -> Option<bool> {
+    match two_params_body(normal_form)? {
+        Term::Var(var) if var.index == 1 => Some(true),
+        Term::Var(var) if var.index == 0 => Some(false),
+        _ => None,
+    }
+}
+
+/// Reads `\s. \z. s (s ... z)` as the number of applications of `s`.
+pub fn read_nat(normal_form: &Term) -> Option<u64> {
+    let mut body = two_params_body(normal_form)?;
+    let mut n = 0;
+    loop {
+        match body {
+            Term::Var(var) if var.index == 0 => return Some(n),
+            Term::App(app) => match &app.callee {
+                Term::Var(var) if var.index == 1 => {
+                    n += 1;
+                    body = &app.arg;
+                }
+                _ => return None,
+            },
+            _ => return None,
+        }
+    }
+}
+
+fn two_params_body(term: &Term) -> Option<&Term> {
+    match term {
+        Term::Abs(outer) => match &outer.body {
+            Term::Abs(inner) => Some(&inner.body),
+            _ => None,
+        },
+        _ => None,
+    }
+}
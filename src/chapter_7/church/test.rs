@@ -0,0 +1,143 @@
+use super::{compile, read_back, read_bool, read_nat, Kind, PRELUDE};
+use crate::chapter_4::{self, evaluator::is_numeric_val, term_builder::*, Term};
+use crate::chapter_7::{eval, parse, term::unnamed, test_util::Random};
+use crate::file_position::Position;
+use std::convert::TryFrom;
+
+fn unnamed(src: &str) -> unnamed::Term {
+    unnamed::Term::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.")
+}
+
+/// Evaluates the compiled term with `chapter_7::eval` and reads it back.
+fn run(t: &Term, kind: Kind) -> Option<Term> {
+    read_back(eval(compile(t)), kind, 100_000).expect("Ran out of fuel.")
+}
+
+fn assert_agrees(t: &Term) {
+    let value = chapter_4::eval(t.clone()).expect("The term is not stuck.");
+    let kind = if is_numeric_val(&value) {
+        Kind::Nat
+    } else {
+        Kind::Bool
+    };
+    assert_eq!(run(t, kind), Some(value), "`{}`", t);
+}
+
+#[test]
+fn read_back_recognizes_values() {
+    assert_eq!(read_bool(&unnamed(r"\t. \f. t")), Some(true));
+    assert_eq!(read_bool(&unnamed(r"\t. \f. f")), Some(false));
+    assert_eq!(read_bool(&unnamed(r"\t. \f. f t")), None);
+    assert_eq!(read_nat(&unnamed(r"\s. \z. z")), Some(0));
+    assert_eq!(read_nat(&unnamed(r"\s. \z. s (s (s z))")), Some(3));
+    assert_eq!(read_nat(&unnamed(r"\s. \z. s (z s)")), None);
+}
+
+#[test]
+fn compiled_values_read_back() {
+    assert_eq!(run(&Term::True, Kind::Bool), Some(Term::True));
+    assert_eq!(run(&Term::False, Kind::Bool), Some(Term::False));
+    // `fls` and `c0` are the same term.
+    assert_eq!(run(&Term::False, Kind::Nat), Some(Term::Zero));
+    let two = succ(succ(Term::Zero));
+    assert_eq!(run(&two, Kind::Nat), Some(two));
+}
+
+/// Every term with at most one constructor above the constants.
+fn small_terms() -> Vec<Term> {
+    let constants = vec![Term::True, Term::False, Term::Zero];
+    let mut terms = constants.clone();
+    for t in constants.iter() {
+        terms.push(succ(t.clone()));
+        terms.push(pred(t.clone()));
+        terms.push(is_zero(t.clone()));
+        for t2 in constants.iter() {
+            for t3 in constants.iter() {
+                terms.push(if_(t.clone(), t2.clone(), t3.clone()));
+            }
+        }
+    }
+    terms
+}
+
+#[test]
+fn agrees_with_chapter_4_on_small_terms() {
+    let terms = small_terms();
+    assert_eq!(terms.len(), 39);
+    for t in terms.iter() {
+        if chapter_4::eval(t.clone()).is_some() {
+            assert_agrees(t);
+        }
+    }
+}
+
+/// Generates a well-typed number or boolean, which is never stuck.
+fn random_term(random: &mut Random, depth: usize, numeric: bool) -> Term {
+    let choice = if depth == 0 { 0 } else { random.below(4) };
+    match (numeric, choice) {
+        (true, 0) => Term::Zero,
+        (false, 0) => {
+            if random.below(2) == 0 {
+                Term::True
+            } else {
+                Term::False
+            }
+        }
+        (_, 1) => if_(
+            random_term(random, depth - 1, false),
+            random_term(random, depth - 1, numeric),
+            random_term(random, depth - 1, numeric),
+        ),
+        (true, 2) => succ(random_term(random, depth - 1, true)),
+        (true, _) => pred(random_term(random, depth - 1, true)),
+        (false, _) => is_zero(random_term(random, depth - 1, true)),
+    }
+}
+
+#[test]
+fn agrees_with_chapter_4_on_random_terms() {
+    let mut random = Random(11);
+    let mut checked = 0;
+    for _ in 0..300 {
+        let numeric = random.below(2) == 0;
+        let t = random_term(&mut random, 4, numeric);
+        if chapter_4::eval(t.clone()).is_some() {
+            assert_agrees(&t);
+            checked += 1;
+        }
+    }
+    assert_eq!(checked, 300);
+}
+
+#[test]
+fn stuck_terms_are_not_stuck_after_compilation() {
+    // The untyped encoding gives every stuck term some meaning.
+    let stuck = [
+        // `if` only looks at whether its guard selects the first argument.
+        (
+            if_(Term::Zero, Term::True, Term::False),
+            Kind::Bool,
+            Some(Term::False),
+        ),
+        (pred(Term::False), Kind::Nat, Some(Term::Zero)),
+        // Others evaluate to functions that are neither booleans nor numerals.
+        (succ(Term::True), Kind::Nat, None),
+        (is_zero(Term::True), Kind::Bool, None),
+    ];
+    for (t, kind, expected) in stuck.iter() {
+        assert_eq!(chapter_4::eval(t.clone()), None, "`{}` is not stuck.", t);
+        assert_eq!(run(t, *kind), *expected, "`{}`", t);
+    }
+}
+
+#[test]
+fn definitions_keep_their_positions_in_the_prelude() {
+    let tru = compile(&Term::True);
+    let start = (&tru).position().start.index;
+    assert!(PRELUDE[start..].starts_with(r"\t. \f. t;"));
+
+    let scc_c0 = compile(&succ(Term::Zero));
+    let position = (&scc_c0).position();
+    assert_eq!(position.start.index, 0);
+    assert_eq!(position.start, position.end);
+}
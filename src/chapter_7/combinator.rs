@@ -0,0 +1,368 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator;
+use super::term::unnamed;
+use crate::file_position::{FilePositionRange, Position};
+use std::fmt;
+
+/// A term of combinatory logic. Variables refer to the context
+/// the original lambda term was in; there are no binders.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Term {
+    Var(Var),
+    Const(Const),
+    App(Box<App>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Var {
+    pub position: FilePositionRange,
+    pub index: usize,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Const {
+    pub position: FilePositionRange,
+    pub combinator: Combinator,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct App {
+    pub position: FilePositionRange,
+    pub callee: Term,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Combinator {
+    /// `S x y z = x z (y z)`
+    S,
+    /// `K x y = x`
+    K,
+    /// `I x = x`
+    I,
+    /// `B x y z = x (y z)`
+    B,
+    /// `C x y z = x z y`
+    C,
+}
+
+impl Combinator {
+    /// The number of arguments a redex needs.
+    pub fn arity(self) -> usize {
+        match self {
+            Combinator::I => 1,
+            Combinator::K => 2,
+            Combinator::S | Combinator::B | Combinator::C => 3,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Combinator::S => "S",
+            Combinator::K => "K",
+            Combinator::I => "I",
+            Combinator::B => "B",
+            Combinator::C => "C",
+        }
+    }
+}
+
+/// The combinators bracket abstraction may use.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Basis {
+    /// Only `S`, `K` and `I`, with the rule `[x] t = K t` when `x` is not free in `t`.
+    Ski,
+    /// Also `B` and `C`, for applications where `x` is free on one side only,
+    /// and the eta rule `[x] (t x) = t`. The result is usually much smaller.
+    Skibc,
+}
+
+impl From<Var> for Term {
+    fn from(var: Var) -> Term {
+        Term::Var(var)
+    }
+}
+
+impl From<Const> for Term {
+    fn from(constant: Const) -> Term {
+        Term::Const(constant)
+    }
+}
+
+impl From<App> for Term {
+    fn from(app: App) -> Term {
+        Term::App(Box::new(app))
+    }
+}
+
+impl Position for &Term {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Term::Var(var) => var.position,
+            Term::Const(constant) => constant.position,
+            Term::App(app) => app.position,
+        }
+    }
+}
+
+fn constant(position: FilePositionRange, combinator: Combinator) -> Term {
+    Const {
+        position,
+        combinator,
+    }
+    .into()
+}
+
+fn app(position: FilePositionRange, callee: Term, arg: Term) -> Term {
+    App {
+        position,
+        callee,
+        arg,
+    }
+    .into()
+}
+
+impl Term {
+    /// The number of variables, constants and applications in `self`.
+    pub fn size(&self) -> usize {
+        match self {
+            Term::Var(_) | Term::Const(_) => 1,
+            Term::App(app) => 1 + app.callee.size() + app.arg.size(),
+        }
+    }
+
+    fn has_var_zero(&self) -> bool {
+        match self {
+            Term::Var(var) => var.index == 0,
+            Term::Const(_) => false,
+            Term::App(app) => app.callee.has_var_zero() || app.arg.has_var_zero(),
+        }
+    }
+
+    /// Removes the innermost variable from the context. It must not occur in `self`.
+    fn unshift(self) -> Term {
+        match self {
+            Term::Var(var) => Term::Var(Var {
+                index: var.index - 1,
+                ..var
+            }),
+            Term::Const(constant) => Term::Const(constant),
+            Term::App(app) => App {
+                callee: app.callee.unshift(),
+                arg: app.arg.unshift(),
+                ..*app
+            }
+            .into(),
+        }
+    }
+
+    /// Bracket abstraction of the innermost variable:
+    /// returns a term without it that, applied to it, reduces to `self`.
+    /// Generated combinators take the position of the abstraction, `position`.
+    fn abstract_var_zero(self, position: FilePositionRange, basis: Basis) -> Term {
+        if !self.has_var_zero() {
+            let position = (&self).position();
+            return app(position, constant(position, Combinator::K), self.unshift());
+        }
+        match self {
+            Term::Var(var) => constant(var.position, Combinator::I),
+            Term::Const(_) => unreachable!("Constants contain no variables."),
+            Term::App(a) => {
+                let App { callee, arg, .. } = *a;
+                let callee_has_var = callee.has_var_zero();
+                let arg_is_var = matches!(arg, Term::Var(Var { index: 0, .. }));
+                let (combinator, callee, arg) = match basis {
+                    Basis::Skibc if !callee_has_var && arg_is_var => return callee.unshift(),
+                    Basis::Skibc if !callee_has_var => (
+                        Combinator::B,
+                        callee.unshift(),
+                        arg.abstract_var_zero(position, basis),
+                    ),
+                    Basis::Skibc if !arg.has_var_zero() => (
+                        Combinator::C,
+                        callee.abstract_var_zero(position, basis),
+                        arg.unshift(),
+                    ),
+                    _ => (
+                        Combinator::S,
+                        callee.abstract_var_zero(position, basis),
+                        arg.abstract_var_zero(position, basis),
+                    ),
+                };
+                app(
+                    position,
+                    app(position, constant(position, combinator), callee),
+                    arg,
+                )
+            }
+        }
+    }
+
+    /// Translates a lambda term into combinators by bracket abstraction.
+    /// Free variables stay variables with the same indices.
+    pub fn compile(term: unnamed::Term, basis: Basis) -> Term {
+        match term {
+            unnamed::Term::Var(var) => Var {
+                position: var.position,
+                index: var.index,
+            }
+            .into(),
+            unnamed::Term::Abs(abs) => {
+                let unnamed::Abs { position, body, .. } = *abs;
+                Term::compile(body, basis).abstract_var_zero(position, basis)
+            }
+            unnamed::Term::App(a) => {
+                let unnamed::App {
+                    position,
+                    callee,
+                    arg,
+                } = *a;
+                app(
+                    position,
+                    Term::compile(callee, basis),
+                    Term::compile(arg, basis),
+                )
+            }
+        }
+    }
+
+    /// Translates back to a lambda term in a context of length `context_length`,
+    /// replacing each combinator with its definition.
+    /// The result is beta-equivalent to the term that was compiled.
+    pub fn into_unnamed(self, context_length: usize) -> unnamed::Term {
+        match self {
+            Term::Var(var) => unnamed::Var {
+                position: var.position,
+                index: var.index,
+                context_length,
+            }
+            .into(),
+            Term::Const(constant) => definition(constant, context_length),
+            Term::App(a) => unnamed::App {
+                position: a.position,
+                callee: a.callee.into_unnamed(context_length),
+                arg: a.arg.into_unnamed(context_length),
+            }
+            .into(),
+        }
+    }
+}
+
+/// The lambda term a combinator stands for, e.g., `\x. \y. x` for `K`.
+fn definition(constant: Const, context_length: usize) -> unnamed::Term {
+    let position = constant.position;
+    let arity = constant.combinator.arity();
+    let var = |index: usize| -> unnamed::Term {
+        unnamed::Var {
+            position,
+            index,
+            context_length: context_length + arity,
+        }
+        .into()
+    };
+    let app = |callee: unnamed::Term, arg: unnamed::Term| -> unnamed::Term {
+        unnamed::App {
+            position,
+            callee,
+            arg,
+        }
+        .into()
+    };
+    // The parameters are `x`, `y` and `z`, so `x` has the largest index.
+    let x = arity - 1;
+    let body = match constant.combinator {
+        Combinator::S => app(app(var(x), var(0)), app(var(1), var(0))),
+        Combinator::K => var(x),
+        Combinator::I => var(x),
+        Combinator::B => app(var(x), app(var(1), var(0))),
+        Combinator::C => app(app(var(x), var(0)), var(1)),
+    };
+    ["x", "y", "z"][..arity]
+        .iter()
+        .rev()
+        .fold(body, |body, param_name| {
+            unnamed::Abs {
+                position,
+                param_name: param_name.to_string(),
+                body,
+            }
+            .into()
+        })
+}
+
+/// Contracts `term` if it is a redex, i.e., a combinator applied to exactly its arity of arguments.
+pub fn contract(term: &Term) -> Option<Term> {
+    let mut args = vec![];
+    let mut head = term;
+    while let Term::App(a) = head {
+        args.push(&a.arg);
+        head = &a.callee;
+    }
+    let combinator = match head {
+        Term::Const(constant) if constant.combinator.arity() == args.len() => constant.combinator,
+        _ => return None,
+    };
+    args.reverse();
+    let position = term.position();
+    let arg = |i: usize| args[i].clone();
+    Some(match combinator {
+        Combinator::I => arg(0),
+        Combinator::K => arg(0),
+        Combinator::S => app(
+            position,
+            app(position, arg(0), arg(2)),
+            app(position, arg(1), arg(2)),
+        ),
+        Combinator::B => app(position, arg(0), app(position, arg(1), arg(2))),
+        Combinator::C => app(position, app(position, arg(0), arg(2)), arg(1)),
+    })
+}
+
+/// Contracts the leftmost outermost redex, also inside arguments,
+/// so a term is only stuck once it is in normal form.
+pub fn eval1(term: &Term) -> Option<Term> {
+    if let Some(contracted) = contract(term) {
+        return Some(contracted);
+    }
+    if let Term::App(a) = term {
+        if let Some(callee) = eval1(&a.callee) {
+            return Some(
+                App {
+                    callee,
+                    arg: a.arg.clone(),
+                    position: a.position,
+                }
+                .into(),
+            );
+        }
+        eval1(&a.arg).map(|arg| {
+            App {
+                callee: a.callee.clone(),
+                arg,
+                position: a.position,
+            }
+            .into()
+        })
+    } else {
+        None
+    }
+}
+
+pub type OutOfFuelErr = evaluator::OutOfFuelErr<Term>;
+
+/// Like `evaluator::eval_count_with_fuel`, but on combinator terms.
+pub fn eval_count_with_fuel(term: Term, fuel: usize) -> Result<(Term, usize), OutOfFuelErr> {
+    evaluator::count_steps_with_fuel(term, fuel, eval1)
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(var) => write!(f, "{}", var.index),
+            Term::Const(constant) => write!(f, "{}", constant.combinator.name()),
+            Term::App(app) => write!(f, "({} {})", app.callee, app.arg),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use super::{eval_count_with_fuel, Basis, Term};
+use crate::chapter_7::{
+    conversion::beta_eta_equivalent,
+    eval, parse,
+    term::unnamed::{self, Context},
+    test_util::load,
+};
+
+/// The encodings that `PROGRAMS` use besides those of `church::PRELUDE`.
+const DEFINITIONS: &str = r"
+and = \b. \c. b c fls;
+not = \b. b fls tru;
+c1 = \s. \z. s z;
+c2 = \s. \z. s (s z);
+plus = \m. \n. \s. \z. m s (n s z);
+times = \m. \n. m (plus n) c0;
+";
+
+const PROGRAMS: [&str; 8] = [
+    "not (not tru)",
+    "and tru (not fls)",
+    "scc c1",
+    "plus c2 c1",
+    "times c2 c2",
+    "iszro (prd c1)",
+    "snd (pair c1 c2)",
+    "prd (times c2 c2)",
+];
+
+fn unnamed(src: &str, ctx: &Context) -> unnamed::Term {
+    unnamed::Term::from_named(parse(src).expect("Cannot parse."), ctx)
+        .expect("Cannot remove names.")
+}
+
+#[test]
+fn bracket_abstraction() {
+    let ctx = Context::from_strs(&["f"]);
+    let examples = [
+        (r"\x. x", "I", "I"),
+        (r"\x. \y. x", "((S (K K)) I)", "K"),
+        (r"\x. f x", "((S (K 0)) I)", "0"),
+        (r"\x. \y. y x", "((S (K (S I))) ((S (K K)) I))", "(C I)"),
+        (r"\x. f (f x)", "((S (K 0)) ((S (K 0)) I))", "((B 0) 0)"),
+    ];
+    for (src, ski, skibc) in examples.iter() {
+        let term = unnamed(src, &ctx);
+        assert_eq!(Term::compile(term.clone(), Basis::Ski).to_string(), *ski);
+        assert_eq!(Term::compile(term, Basis::Skibc).to_string(), *skibc);
+    }
+}
+
+#[test]
+fn reduction_applies_combinators() {
+    let ctx = Context::from_strs(&["a", "b", "c"]);
+    let examples = [
+        (r"(\x. \y. \z. x z (y z)) a b c", "((2 0) (1 0))"),
+        (r"(\x. \y. x) a b", "2"),
+        (r"(\x. \y. \z. x (y z)) a b c", "(2 (1 0))"),
+        (r"(\x. \y. \z. x z y) a b c", "((2 0) 1)"),
+    ];
+    for (src, expected) in examples.iter() {
+        for &basis in [Basis::Ski, Basis::Skibc].iter() {
+            let term = Term::compile(unnamed(src, &ctx), basis);
+            let (actual, _) = eval_count_with_fuel(term, 100).expect("Ran out of fuel.");
+            assert_eq!(actual.to_string(), *expected);
+        }
+    }
+}
+
+#[test]
+fn reduction_runs_out_of_fuel_on_omega() {
+    let term = Term::compile(
+        unnamed(r"(\x. x x) (\x. x x)", &Context::empty()),
+        Basis::Ski,
+    );
+    let err = eval_count_with_fuel(term, 20).expect_err("Omega has no normal form.");
+    assert_eq!(err.steps, 20);
+}
+
+#[test]
+fn into_unnamed_is_beta_eta_equivalent() {
+    let ctx = Context::from_strs(&["f", "g"]);
+    let examples = [
+        r"\x. \y. \z. x z (y z)",
+        r"\x. f (g x) x",
+        r"\x. \y. y (x f)",
+    ];
+    for src in examples.iter() {
+        let term = unnamed(src, &ctx);
+        for &basis in [Basis::Ski, Basis::Skibc].iter() {
+            let back = Term::compile(term.clone(), basis).into_unnamed(ctx.len());
+            assert_eq!(back.context_length(), ctx.len());
+            assert_eq!(
+                beta_eta_equivalent(&back, &term, 100),
+                Ok(true),
+                "`{}`",
+                src
+            );
+        }
+    }
+}
+
+#[test]
+fn reduction_matches_eval_on_prelude_programs() {
+    let (defs, _) = load(DEFINITIONS);
+    for program in PROGRAMS.iter() {
+        let named = parse(program).expect("Cannot parse.");
+        let term = defs.resolve(named).expect("Cannot resolve.");
+        let expected = eval(term.clone());
+        let mut sizes = vec![];
+        for &basis in [Basis::Ski, Basis::Skibc].iter() {
+            let compiled = Term::compile(term.clone(), basis);
+            sizes.push(compiled.size());
+            let (reduced, _) = eval_count_with_fuel(compiled, 100_000).expect("Ran out of fuel.");
+            let actual = reduced.into_unnamed(0);
+            assert_eq!(
+                beta_eta_equivalent(&actual, &expected, 10_000),
+                Ok(true),
+                "`{}`",
+                program
+            );
+        }
+        assert!(sizes[1] < sizes[0], "`{}`: {:?}", program, sizes);
+    }
+}
@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{eval_count_with_fuel, OutOfFuelErr, Strategy};
+use super::term::unnamed::Term;
+
+/// Whether `a` and `b` are convertible by beta and eta steps.
+///
+/// Both terms are reduced to beta normal form under normal order evaluation,
+/// taking at most `fuel` steps each, and then to eta normal form.
+/// Terms without a normal form cannot be decided and run out of fuel.
+/// Both terms must be in the same context.
+pub fn beta_eta_equivalent(a: &Term, b: &Term, fuel: usize) -> Result<bool, OutOfFuelErr> {
+    let (a, _) = eval_count_with_fuel(a.clone(), Strategy::NormalOrder, fuel)?;
+    let (b, _) = eval_count_with_fuel(b.clone(), Strategy::NormalOrder, fuel)?;
+    Ok(a.eta_reduce().is_alpha_equivalent(&b.eta_reduce()))
+}
@@ -0,0 +1,91 @@
+use super::beta_eta_equivalent;
+use crate::chapter_7::{
+    parse,
+    term::unnamed::{Context, Term},
+};
+
+fn unnamed(src: &str) -> Term {
+    let ctx = Context::from_strs(&["g", "f"]);
+    Term::from_named(parse(src).expect("Cannot parse."), &ctx).expect("Cannot remove names.")
+}
+
+#[test]
+fn eta_reduce_contracts_nested_redexes() {
+    assert_eq!(
+        unnamed(r"\x. f x").eta_reduce().to_string(),
+        unnamed("f").to_string()
+    );
+    assert_eq!(
+        unnamed(r"\x. \y. g x y").eta_reduce().to_string(),
+        unnamed("g").to_string()
+    );
+    assert_eq!(
+        unnamed(r"\x. (\y. f y) x").eta_reduce().to_string(),
+        unnamed("f").to_string()
+    );
+}
+
+#[test]
+fn eta_reduce_keeps_bound_callees() {
+    // `x` is free in the callee `x`, so `\x. x x` is not an eta redex.
+    let term = unnamed(r"\x. x x");
+    assert_eq!(term.clone().eta_reduce(), term);
+    let term = unnamed(r"\x. \y. y x");
+    assert_eq!(term.clone().eta_reduce(), term);
+}
+
+#[test]
+fn eta_expand_is_undone_by_eta_reduce() {
+    for src in [r"f", r"\x. x", r"g (\y. f y y)"].iter() {
+        let term = unnamed(src);
+        let expanded = term.clone().eta_expand("z".to_string());
+        assert!(expanded.is_abs());
+        assert_eq!(expanded.context_length(), term.context_length());
+        assert_eq!(
+            expanded.eta_reduce().to_string(),
+            term.eta_reduce().to_string()
+        );
+    }
+    assert_eq!(
+        unnamed("f").eta_expand("z".to_string()).to_string(),
+        unnamed(r"\z. f z").to_string()
+    );
+}
+
+#[test]
+fn beta_eta_equivalence() {
+    let equivalent = [
+        (r"\x. f x", "f"),
+        (r"(\h. \x. h x) g", "g"),
+        // Successor of one, with and without an eta-expanded argument.
+        (r"(\n. \s. \z. s (n s z)) (\s. \z. s z)", r"\s. \z. s (s z)"),
+        (r"\s. (\n. \s. \z. s (n s z)) (\s. s) s", r"\s. \z. s (s z)"),
+    ];
+    for (a, b) in equivalent.iter() {
+        assert_eq!(
+            beta_eta_equivalent(&unnamed(a), &unnamed(b), 100),
+            Ok(true),
+            "`{}` and `{}`",
+            a,
+            b
+        );
+    }
+
+    let different = [("f", "g"), (r"\x. \y. x", r"\x. \y. y"), (r"\x. x f", "f")];
+    for (a, b) in different.iter() {
+        assert_eq!(
+            beta_eta_equivalent(&unnamed(a), &unnamed(b), 100),
+            Ok(false),
+            "`{}` and `{}`",
+            a,
+            b
+        );
+    }
+}
+
+#[test]
+fn beta_eta_equivalence_needs_normal_forms() {
+    let omega = unnamed(r"(\x. x x) (\x. x x)");
+    let err = beta_eta_equivalent(&omega, &omega, 10).expect_err("Omega has no normal form.");
+    assert_eq!(err.steps, 10);
+}
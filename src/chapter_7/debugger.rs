@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{eval_count_with_fuel, OutOfFuelErr, Strategy};
+use super::statement::Definitions;
+use super::term::unnamed::Term;
+use super::trace::{self, Step};
+use crate::file_position::{FilePosition, FilePositionRange, Position};
+
+/// A condition under which `Debugger::resume` stops.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Triggers when the value of a definition is applied,
+    /// i.e., when the active redex is the value itself
+    /// or has the value as its callee.
+    Def {
+        name: String,
+        position: FilePositionRange,
+    },
+    /// Triggers when the active redex lies within the range.
+    Range(FilePositionRange),
+}
+
+impl Breakpoint {
+    /// Creates a breakpoint on the most recent definition of `name`.
+    ///
+    /// The value is recognized by its position, so definitions and terms
+    /// parsed from separate sources should be numbered apart with `parser::parse_at`.
+    pub fn def(defs: &Definitions, name: &str) -> Option<Breakpoint> {
+        defs.get(name).map(|value| Breakpoint::Def {
+            name: name.to_string(),
+            position: value.position(),
+        })
+    }
+
+    pub fn is_triggered_by(&self, step: &Step) -> bool {
+        match self {
+            Breakpoint::Def { position, .. } => {
+                step.redex_position == *position
+                    || match &step.redex {
+                        Term::App(app) => (&app.callee).position() == *position,
+                        _ => false,
+                    }
+            }
+            Breakpoint::Range(range) => {
+                contains(range.start, range.end, step.redex_position.start)
+                    && contains(range.start, range.end, step.redex_position.end)
+            }
+        }
+    }
+}
+
+fn contains(start: FilePosition, end: FilePosition, position: FilePosition) -> bool {
+    let key = |p: FilePosition| (p.line, p.column);
+    key(start) <= key(position) && key(position) <= key(end)
+}
+
+/// The reason `Debugger::step_over` or `Debugger::resume` stopped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The step at the given breakpoint index is about to be taken.
+    Breakpoint(usize),
+    /// The term is in normal form.
+    Finished,
+    OutOfFuel,
+    /// `step_over` reduced the active subterm to a value.
+    SteppedOver {
+        steps: usize,
+    },
+}
+
+/// Steps through the evaluation of a term, as performed by `evaluator::eval1`.
+#[derive(Clone, Debug)]
+pub struct Debugger {
+    term: Term,
+    steps: usize,
+    fuel: usize,
+    breakpoints: Vec<Option<Breakpoint>>,
+}
+
+impl Debugger {
+    /// `fuel` is the maximum number of steps `step_over` and `resume`
+    /// may take before stopping.
+    pub fn new(term: Term, fuel: usize) -> Debugger {
+        Debugger {
+            term,
+            steps: 0,
+            fuel,
+            breakpoints: vec![],
+        }
+    }
+
+    pub fn term(&self) -> &Term {
+        &self.term
+    }
+
+    pub fn into_term(self) -> Term {
+        self.term
+    }
+
+    /// The number of steps taken so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_step().is_none()
+    }
+
+    /// The step that will be taken next, whose redex is the active redex.
+    pub fn next_step(&self) -> Option<Step> {
+        trace::step(&self.term)
+    }
+
+    /// Adds a breakpoint, returning its index.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(Some(breakpoint));
+        self.breakpoints.len() - 1
+    }
+
+    /// Removes the breakpoint with the given index,
+    /// returning `None` if there is no such breakpoint.
+    pub fn remove_breakpoint(&mut self, index: usize) -> Option<Breakpoint> {
+        self.breakpoints.get_mut(index).and_then(Option::take)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = (usize, &Breakpoint)> {
+        self.breakpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(i, breakpoint)| breakpoint.as_ref().map(|breakpoint| (i, breakpoint)))
+    }
+
+    /// Takes a single step, ignoring breakpoints.
+    pub fn step(&mut self) -> Option<Step> {
+        let step = self.next_step()?;
+        self.term = step.after.clone();
+        self.steps += 1;
+        Some(step)
+    }
+
+    /// Reduces the active redex, and whatever it reduces to, to a value
+    /// in one go, ignoring breakpoints.
+    pub fn step_over(&mut self) -> Stop {
+        let step = match self.next_step() {
+            Some(step) => step,
+            None => return Stop::Finished,
+        };
+        let active = step.redex.clone();
+        match eval_count_with_fuel(active, Strategy::CallByValue, self.fuel) {
+            Ok((value, steps)) => {
+                self.term = step.before.replace_subterm(&step.path, value);
+                self.steps += steps;
+                Stop::SteppedOver { steps }
+            }
+            Err(OutOfFuelErr { term, steps }) => {
+                self.term = step.before.replace_subterm(&step.path, term);
+                self.steps += steps;
+                Stop::OutOfFuel
+            }
+        }
+    }
+
+    /// Takes steps until the active redex triggers a breakpoint
+    /// or the term is in normal form.
+    /// At least one step is taken, so resuming from a breakpoint
+    /// does not immediately stop at the same breakpoint.
+    pub fn resume(&mut self) -> Stop {
+        for _ in 0..self.fuel {
+            if self.step().is_none() {
+                return Stop::Finished;
+            }
+            match self.next_step() {
+                Some(next) => {
+                    if let Some(index) = self.triggered_breakpoint(&next) {
+                        return Stop::Breakpoint(index);
+                    }
+                }
+                None => return Stop::Finished,
+            }
+        }
+        Stop::OutOfFuel
+    }
+
+    fn triggered_breakpoint(&self, step: &Step) -> Option<usize> {
+        self.breakpoints()
+            .find(|(_, breakpoint)| breakpoint.is_triggered_by(step))
+            .map(|(i, _)| i)
+    }
+}
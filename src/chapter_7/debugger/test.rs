@@ -0,0 +1,126 @@
+use super::{Breakpoint, Debugger, Stop};
+use crate::chapter_7::{
+    eval_count, parse_statements,
+    statement::{Definitions, Statement},
+    term::unnamed::Term,
+    test_util,
+};
+use crate::file_position::{FilePosition, FilePositionRange};
+
+/// Loaded after `church::PRELUDE`.
+const PROGRAM: &str = r"
+not = \b. b fls tru;
+not (not tru)
+";
+
+/// Returns the definitions and the final term of `PROGRAM`.
+fn load() -> (Definitions, Term) {
+    let (defs, term) = test_util::load(PROGRAM);
+    (defs, term.expect("No term."))
+}
+
+fn named(term: &Term) -> String {
+    term.clone().into_unpositioned_named().to_string()
+}
+
+#[test]
+fn step_matches_eval() {
+    let (_, term) = load();
+    let (normal_form, count) = eval_count(term.clone());
+    let mut debugger = Debugger::new(term, 1000);
+    while debugger.step().is_some() {}
+    assert_eq!(debugger.steps(), count);
+    assert_eq!(debugger.term(), &normal_form);
+    assert!(debugger.is_finished());
+}
+
+#[test]
+fn resume_without_breakpoints_finishes() {
+    let (_, term) = load();
+    let mut debugger = Debugger::new(term, 1000);
+    assert_eq!(debugger.resume(), Stop::Finished);
+    assert_eq!(named(debugger.term()), r"(\t. (\f. t))");
+}
+
+#[test]
+fn def_breakpoint_stops_at_each_call() {
+    let (defs, term) = load();
+    let mut debugger = Debugger::new(term, 1000);
+    let breakpoint = Breakpoint::def(&defs, "not").expect("No `not`.");
+    let not = debugger.add_breakpoint(breakpoint.clone());
+
+    // Call-by-value evaluates the inner `not` first.
+    let inner = debugger.next_step().expect("No step.");
+    assert!(breakpoint.is_triggered_by(&inner));
+
+    assert_eq!(debugger.resume(), Stop::Breakpoint(not));
+    let outer = debugger.next_step().expect("No step.");
+    assert_eq!(outer.path, vec![]);
+    assert_eq!(
+        named(&outer.redex),
+        r"((\b. ((b (\t. (\f. f))) (\t. (\f. t)))) (\t. (\f. f)))"
+    );
+    assert_eq!(debugger.resume(), Stop::Finished);
+}
+
+#[test]
+fn range_breakpoint() {
+    let (_, term) = load();
+    let mut debugger = Debugger::new(term, 1000);
+    // The range of `not tru` in `not (not tru)`, on the last line of `PROGRAM`.
+    let range = FilePositionRange {
+        start: FilePosition {
+            line: 16,
+            column: 5,
+            index: 0,
+        },
+        end: FilePosition {
+            line: 16,
+            column: 12,
+            index: 0,
+        },
+    };
+    let breakpoint = Breakpoint::Range(range);
+
+    let inner = debugger.next_step().expect("No step.");
+    assert!(breakpoint.is_triggered_by(&inner));
+    debugger.step();
+    let outer = debugger.next_step().expect("No step.");
+    assert!(!breakpoint.is_triggered_by(&outer));
+
+    let index = debugger.add_breakpoint(breakpoint.clone());
+    assert_eq!(debugger.remove_breakpoint(index), Some(breakpoint));
+    assert_eq!(debugger.remove_breakpoint(index), None);
+    assert_eq!(debugger.breakpoints().count(), 0);
+}
+
+#[test]
+fn step_over_reduces_active_redex_to_value() {
+    let (_, term) = load();
+    let mut debugger = Debugger::new(term, 1000);
+    let inner = debugger.next_step().expect("No step.");
+    assert_eq!(inner.path.len(), 1);
+    assert_eq!(debugger.step_over(), Stop::SteppedOver { steps: 3 });
+    assert_eq!(debugger.steps(), 3);
+    let outer = debugger.next_step().expect("No step.");
+    assert_eq!(outer.path, vec![]);
+    assert_eq!(
+        named(&outer.contractum),
+        r"(((\t. (\f. f)) (\t. (\f. f))) (\t. (\f. t)))"
+    );
+}
+
+#[test]
+fn resume_runs_out_of_fuel() {
+    let statements = parse_statements(r"(\x. x x) \x. x x").expect("Cannot parse.");
+    let term = match statements.into_iter().next() {
+        Some(Statement::Eval(named)) => Definitions::empty()
+            .resolve(named)
+            .expect("Cannot resolve."),
+        _ => panic!("Expected a term."),
+    };
+    let mut debugger = Debugger::new(term, 50);
+    assert_eq!(debugger.resume(), Stop::OutOfFuel);
+    assert_eq!(debugger.steps(), 50);
+    assert_eq!(debugger.step_over(), Stop::OutOfFuel);
+}
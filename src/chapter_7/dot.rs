@@ -0,0 +1,320 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{contract_at, redex_paths, Strategy};
+use super::term::{
+    named::{self, Term as NamedTerm},
+    path::Direction,
+    unnamed::{self, Term},
+};
+use std::fmt::Write;
+
+/// Renders the syntax tree of a named term as a Graphviz digraph.
+///
+/// Each bound variable has a dashed edge back to the abstraction that binds it.
+/// Free variables have no such edge and are drawn with a dashed outline.
+pub fn named_tree(term: &NamedTerm) -> String {
+    let mut tree = Tree::new();
+    tree.add_named(term, &mut vec![]);
+    tree.finish()
+}
+
+/// Renders the syntax tree of a nameless term as a Graphviz digraph.
+///
+/// Each variable whose index refers to an enclosing abstraction
+/// has a dashed edge back to it. Free variables have no such edge
+/// and are drawn with a dashed outline.
+pub fn unnamed_tree(term: &Term) -> String {
+    let mut tree = Tree::new();
+    tree.add_unnamed(term, &mut vec![]);
+    tree.finish()
+}
+
+struct Tree {
+    body: String,
+    next_id: usize,
+}
+
+impl Tree {
+    fn new() -> Tree {
+        Tree {
+            body: String::new(),
+            next_id: 0,
+        }
+    }
+
+    fn finish(self) -> String {
+        format!(
+            "digraph {{\n  node [shape=plaintext];\n  edge [arrowhead=none];\n{}}}\n",
+            self.body
+        )
+    }
+
+    fn node(&mut self, label: &str, is_free: bool) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        let style = if is_free {
+            ", shape=box, style=dashed"
+        } else {
+            ""
+        };
+        writeln!(
+            self.body,
+            "  n{} [label=\"{}\"{}];",
+            id,
+            escape(label),
+            style
+        )
+        .unwrap();
+        id
+    }
+
+    fn edge(&mut self, parent: usize, child: usize, label: &str) {
+        writeln!(
+            self.body,
+            "  n{} -> n{} [label=\"{}\"];",
+            parent, child, label
+        )
+        .unwrap();
+    }
+
+    fn back_edge(&mut self, var: usize, binder: usize) {
+        writeln!(
+            self.body,
+            "  n{} -> n{} [style=dashed, arrowhead=normal, constraint=false];",
+            var, binder
+        )
+        .unwrap();
+    }
+
+    /// `binders` holds the names and node ids of the enclosing abstractions,
+    /// innermost last.
+    fn add_named(&mut self, term: &NamedTerm, binders: &mut Vec<(String, usize)>) -> usize {
+        match term {
+            NamedTerm::Var(var) => {
+                let binder = binders
+                    .iter()
+                    .rev()
+                    .find(|(name, _)| *name == var.name)
+                    .map(|(_, id)| *id);
+                let id = self.node(&var.name, binder.is_none());
+                if let Some(binder) = binder {
+                    self.back_edge(id, binder);
+                }
+                id
+            }
+            NamedTerm::Abs(abs) => {
+                let named::Abs { param, body, .. } = &**abs;
+                let id = self.node(&format!("λ{}", param.name), false);
+                binders.push((param.name.clone(), id));
+                let body = self.add_named(body, binders);
+                binders.pop();
+                self.edge(id, body, "");
+                id
+            }
+            NamedTerm::App(app) => {
+                let id = self.node("@", false);
+                let callee = self.add_named(&app.callee, binders);
+                let arg = self.add_named(&app.arg, binders);
+                self.edge(id, callee, "callee");
+                self.edge(id, arg, "arg");
+                id
+            }
+        }
+    }
+
+    /// `binders` holds the node ids of the enclosing abstractions, innermost last.
+    fn add_unnamed(&mut self, term: &Term, binders: &mut Vec<usize>) -> usize {
+        match term {
+            Term::Var(var) => {
+                let binder = binders.len().checked_sub(var.index + 1).map(|i| binders[i]);
+                let id = self.node(&var.index.to_string(), binder.is_none());
+                if let Some(binder) = binder {
+                    self.back_edge(id, binder);
+                }
+                id
+            }
+            Term::Abs(abs) => {
+                let unnamed::Abs { body, .. } = &**abs;
+                let id = self.node("λ", false);
+                binders.push(id);
+                let body = self.add_unnamed(body, binders);
+                binders.pop();
+                self.edge(id, body, "");
+                id
+            }
+            Term::App(app) => {
+                let id = self.node("@", false);
+                let callee = self.add_unnamed(&app.callee, binders);
+                let arg = self.add_unnamed(&app.arg, binders);
+                self.edge(id, callee, "callee");
+                self.edge(id, arg, "arg");
+                id
+            }
+        }
+    }
+}
+
+/// A single beta-reduction from one term of a `ReductionGraph` to another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    /// The path from the `from` term to the contracted redex.
+    pub path: Vec<Direction>,
+    /// The strategies that would contract this redex next.
+    pub strategies: Vec<Strategy>,
+}
+
+/// The terms reachable from a term by contracting any redex, in any order.
+///
+/// Terms are identified up to alpha-equivalence,
+/// and the first term is the one the graph was built from.
+#[derive(Clone, Debug)]
+pub struct ReductionGraph {
+    terms: Vec<Term>,
+    edges: Vec<Edge>,
+    is_complete: bool,
+}
+
+impl ReductionGraph {
+    /// Explores the terms reachable from `term` breadth-first,
+    /// stopping once `max_terms` terms have been found.
+    pub fn new(term: Term, max_terms: usize) -> ReductionGraph {
+        let mut graph = ReductionGraph {
+            terms: vec![],
+            edges: vec![],
+            is_complete: true,
+        };
+        graph.terms.push(term);
+
+        let mut next = 0;
+        while next < graph.terms.len() {
+            let from = next;
+            next += 1;
+            let term = graph.terms[from].clone();
+            for path in redex_paths(&term) {
+                let reduct = contract_at(term.clone(), &path)
+                    .expect("A path returned by `redex_paths` should lead to a redex.");
+                let known = graph
+                    .terms
+                    .iter()
+                    .position(|known| known.is_alpha_equivalent(&reduct));
+                let to = match known {
+                    Some(to) => to,
+                    None if graph.terms.len() < max_terms => {
+                        graph.terms.push(reduct);
+                        graph.terms.len() - 1
+                    }
+                    None => {
+                        graph.is_complete = false;
+                        continue;
+                    }
+                };
+                let strategies = Strategy::ALL
+                    .iter()
+                    .copied()
+                    .filter(|strategy| strategy.redex_path(&term).as_ref() == Some(&path))
+                    .collect();
+                graph.edges.push(Edge {
+                    from,
+                    to,
+                    path,
+                    strategies,
+                });
+            }
+        }
+
+        graph
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Whether every reachable term was found,
+    /// i.e., `max_terms` was never exceeded.
+    pub fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    /// The indices of the terms that contain no redexes.
+    pub fn normal_forms(&self) -> Vec<usize> {
+        (0..self.terms.len())
+            .filter(|&i| redex_paths(&self.terms[i]).is_empty())
+            .collect()
+    }
+
+    /// The indices of the terms visited by `strategy`, starting from the first term.
+    /// The path stops when it reaches a normal form, revisits a term,
+    /// or leaves the explored part of the graph.
+    pub fn strategy_path(&self, strategy: Strategy) -> Vec<usize> {
+        let mut path = vec![0];
+        let mut current = 0;
+        while let Some(edge) = self
+            .edges
+            .iter()
+            .find(|edge| edge.from == current && edge.strategies.contains(&strategy))
+        {
+            let is_revisit = path.contains(&edge.to);
+            path.push(edge.to);
+            if is_revisit {
+                break;
+            }
+            current = edge.to;
+        }
+        path
+    }
+
+    /// Renders the graph as a Graphviz digraph.
+    ///
+    /// The first term is drawn in bold and normal forms have a double outline.
+    /// Each edge is labelled with the strategies that take it.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n  node [shape=box];\n");
+        let normal_forms = self.normal_forms();
+        for (i, term) in self.terms.iter().enumerate() {
+            let label = term.clone().into_unpositioned_named().to_string();
+            let mut attributes = format!("label=\"{}\"", escape(&label));
+            if i == 0 {
+                attributes.push_str(", style=bold");
+            }
+            if normal_forms.contains(&i) {
+                attributes.push_str(", peripheries=2");
+            }
+            writeln!(out, "  t{} [{}];", i, attributes).unwrap();
+        }
+        for edge in &self.edges {
+            let label = edge
+                .strategies
+                .iter()
+                .map(|strategy| strategy.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let style = if edge.strategies.is_empty() {
+                ", color=gray"
+            } else {
+                ""
+            };
+            writeln!(
+                out,
+                "  t{} -> t{} [label=\"{}\"{}];",
+                edge.from, edge.to, label, style
+            )
+            .unwrap();
+        }
+        if !self.is_complete {
+            out.push_str("  more [label=\"...\", shape=plaintext];\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
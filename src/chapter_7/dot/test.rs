@@ -0,0 +1,122 @@
+use super::{named_tree, unnamed_tree, ReductionGraph};
+use crate::chapter_7::{evaluator::Strategy, parse, term::unnamed::Term};
+use std::convert::TryFrom;
+
+fn unnamed(src: &str) -> Term {
+    Term::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.")
+}
+
+fn named(graph: &ReductionGraph, i: usize) -> String {
+    graph.terms()[i]
+        .clone()
+        .into_unpositioned_named()
+        .to_string()
+}
+
+#[test]
+fn named_tree_has_back_edges() {
+    let term = parse(r"\x. \y. x y z").expect("Cannot parse.");
+    let actual = named_tree(&term);
+    let expected = r#"digraph {
+  node [shape=plaintext];
+  edge [arrowhead=none];
+  n0 [label="λx"];
+  n1 [label="λy"];
+  n2 [label="@"];
+  n3 [label="@"];
+  n4 [label="x"];
+  n4 -> n0 [style=dashed, arrowhead=normal, constraint=false];
+  n5 [label="y"];
+  n5 -> n1 [style=dashed, arrowhead=normal, constraint=false];
+  n3 -> n4 [label="callee"];
+  n3 -> n5 [label="arg"];
+  n6 [label="z", shape=box, style=dashed];
+  n2 -> n3 [label="callee"];
+  n2 -> n6 [label="arg"];
+  n1 -> n2 [label=""];
+  n0 -> n1 [label=""];
+}
+"#;
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn unnamed_tree_matches_named_tree() {
+    let src = r"\x. (\x. x) x";
+    let named = named_tree(&parse(src).expect("Cannot parse."));
+    let unnamed = unnamed_tree(&unnamed(src));
+    let back_edges = |dot: &str| {
+        dot.lines()
+            .filter(|line| line.contains("style=dashed"))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        back_edges(&unnamed),
+        vec![
+            "  n3 -> n2 [style=dashed, arrowhead=normal, constraint=false];",
+            "  n4 -> n0 [style=dashed, arrowhead=normal, constraint=false];",
+        ]
+    );
+    assert_eq!(back_edges(&named), back_edges(&unnamed));
+}
+
+#[test]
+fn reduction_graph_is_confluent() {
+    let term = unnamed(r"(\f. f f) ((\x. x) \y. (\z. z) y)");
+    let graph = ReductionGraph::new(term, 100);
+    assert!(graph.is_complete());
+    let normal_forms = graph.normal_forms();
+    assert_eq!(normal_forms.len(), 1);
+    assert_eq!(named(&graph, normal_forms[0]), r"(\y. y)");
+
+    // Every term can still reach the normal form, whichever redexes were contracted.
+    for start in 0..graph.terms().len() {
+        let mut reachable = vec![start];
+        let mut i = 0;
+        while i < reachable.len() {
+            let from = reachable[i];
+            for edge in graph.edges().iter().filter(|edge| edge.from == from) {
+                if !reachable.contains(&edge.to) {
+                    reachable.push(edge.to);
+                }
+            }
+            i += 1;
+        }
+        assert!(reachable.contains(&normal_forms[0]));
+    }
+
+    let normal_order = graph.strategy_path(Strategy::NormalOrder);
+    assert_eq!(normal_order.last(), Some(&normal_forms[0]));
+    // Call-by-value stops at `\y. (\z. z) y`, since it never reduces inside abstractions.
+    let call_by_value = graph.strategy_path(Strategy::CallByValue);
+    let stopped_at = *call_by_value.last().expect("Expected a path.");
+    assert_eq!(named(&graph, stopped_at), r"(\y. ((\z. z) y))");
+}
+
+#[test]
+fn strategies_differ_on_omega_arg() {
+    let term = unnamed(r"(\x. \y. y) ((\x. x x) \x. x x)");
+    let graph = ReductionGraph::new(term, 100);
+    assert!(graph.is_complete());
+    assert_eq!(graph.terms().len(), 2);
+    assert_eq!(graph.strategy_path(Strategy::CallByValue), vec![0, 0]);
+    assert_eq!(graph.strategy_path(Strategy::CallByName), vec![0, 1]);
+    assert_eq!(graph.strategy_path(Strategy::NormalOrder), vec![0, 1]);
+
+    let dot = graph.to_dot();
+    assert!(dot.contains(r#"t0 -> t1 [label="cbn, normal"];"#));
+    assert!(dot.contains(r#"t0 -> t0 [label="cbv"];"#));
+    assert!(dot.contains(r#"t1 [label="(\\y. y)", peripheries=2];"#));
+}
+
+#[test]
+fn reduction_graph_is_bounded() {
+    // Each step of `(\x. x x x) (\x. x x x)` produces a larger term.
+    let term = unnamed(r"(\x. x x x) \x. x x x");
+    let graph = ReductionGraph::new(term, 5);
+    assert!(!graph.is_complete());
+    assert_eq!(graph.terms().len(), 5);
+    assert!(graph.normal_forms().is_empty());
+    assert!(graph.to_dot().contains("more"));
+}
@@ -1,4 +1,10 @@
-use super::term::unnamed::{App, Term};
+#[cfg(test)]
+mod test;
+
+use super::term::{
+    path::Direction,
+    unnamed::{App, Term},
+};
 
 pub fn eval1(term: Term) -> Option<Term> {
     if let Term::App(app) = term {
@@ -50,3 +56,249 @@ pub fn eval_count(mut term: Term) -> (Term, usize) {
     }
     (term, i)
 }
+
+/// The evaluation strategies described in Section 5.1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// Only outermost redexes whose argument is a value are reduced.
+    /// This is the strategy used by `eval1`.
+    CallByValue,
+    /// The leftmost, outermost redex is reduced, but never inside an abstraction.
+    CallByName,
+    /// The leftmost, outermost redex is reduced, even inside an abstraction.
+    NormalOrder,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [
+        Strategy::CallByValue,
+        Strategy::CallByName,
+        Strategy::NormalOrder,
+    ];
+
+    /// Finds the path to the redex this strategy would contract next.
+    pub fn redex_path(self, term: &Term) -> Option<Vec<Direction>> {
+        match self {
+            Strategy::CallByValue => call_by_value_redex_path(term),
+            Strategy::CallByName => call_by_name_redex_path(term),
+            Strategy::NormalOrder => normal_order_redex_path(term),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::CallByValue => "cbv",
+            Strategy::CallByName => "cbn",
+            Strategy::NormalOrder => "normal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Strategy> {
+        Strategy::ALL
+            .iter()
+            .copied()
+            .find(|strategy| strategy.name() == name)
+    }
+}
+
+fn call_by_value_redex_path(term: &Term) -> Option<Vec<Direction>> {
+    if let Term::App(app) = term {
+        let App { callee, arg, .. } = &**app;
+
+        if callee.is_app() {
+            call_by_value_redex_path(callee).map(|path| prepend(Direction::Callee, path))
+        } else if callee.is_val() && arg.is_app() {
+            call_by_value_redex_path(arg).map(|path| prepend(Direction::Arg, path))
+        } else if arg.is_val() && callee.is_abs() {
+            Some(vec![])
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+fn call_by_name_redex_path(term: &Term) -> Option<Vec<Direction>> {
+    if let Term::App(app) = term {
+        if app.callee.is_abs() {
+            Some(vec![])
+        } else {
+            call_by_name_redex_path(&app.callee).map(|path| prepend(Direction::Callee, path))
+        }
+    } else {
+        None
+    }
+}
+
+fn normal_order_redex_path(term: &Term) -> Option<Vec<Direction>> {
+    match term {
+        Term::Var(_) => None,
+        Term::Abs(abs) => {
+            normal_order_redex_path(&abs.body).map(|path| prepend(Direction::Body, path))
+        }
+        Term::App(app) => {
+            if app.callee.is_abs() {
+                Some(vec![])
+            } else if let Some(path) = normal_order_redex_path(&app.callee) {
+                Some(prepend(Direction::Callee, path))
+            } else {
+                normal_order_redex_path(&app.arg).map(|path| prepend(Direction::Arg, path))
+            }
+        }
+    }
+}
+
+/// Finds the paths to every beta-redex in `term`, including those inside
+/// abstractions, in leftmost-outermost order.
+pub fn redex_paths(term: &Term) -> Vec<Vec<Direction>> {
+    match term {
+        Term::Var(_) => vec![],
+        Term::Abs(abs) => redex_paths(&abs.body)
+            .into_iter()
+            .map(|path| prepend(Direction::Body, path))
+            .collect(),
+        Term::App(app) => {
+            let here = if app.callee.is_abs() {
+                vec![vec![]]
+            } else {
+                vec![]
+            };
+            let in_callee = redex_paths(&app.callee)
+                .into_iter()
+                .map(|path| prepend(Direction::Callee, path));
+            let in_arg = redex_paths(&app.arg)
+                .into_iter()
+                .map(|path| prepend(Direction::Arg, path));
+            here.into_iter().chain(in_callee).chain(in_arg).collect()
+        }
+    }
+}
+
+fn prepend(direction: Direction, mut path: Vec<Direction>) -> Vec<Direction> {
+    path.insert(0, direction);
+    path
+}
+
+/// Contracts the redex at `path`, returning `None` if
+/// there is no beta-redex at that path.
+pub fn contract_at(term: Term, path: &[Direction]) -> Option<Term> {
+    let contractum = contract(term.subterm(path)?)?;
+    Some(term.replace_subterm(path, contractum))
+}
+
+/// Contracts `redex`, returning `None` if it is not a beta-redex.
+pub fn contract(redex: &Term) -> Option<Term> {
+    if let Term::App(app) = redex {
+        if let Term::Abs(abs) = &app.callee {
+            return Some((**abs).clone().apply(&app.arg));
+        }
+    }
+    None
+}
+
+pub fn eval1_with(term: Term, strategy: Strategy) -> Option<Term> {
+    let path = strategy.redex_path(&term)?;
+    contract_at(term, &path)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutOfFuelErr<T = Term> {
+    /// The term reached when the fuel ran out.
+    pub term: T,
+    pub steps: usize,
+}
+
+/// Like `eval_count`, but uses the given strategy and gives up after `fuel` steps.
+pub fn eval_count_with_fuel(
+    term: Term,
+    strategy: Strategy,
+    fuel: usize,
+) -> Result<(Term, usize), OutOfFuelErr> {
+    count_steps_with_fuel(term, fuel, |term| eval1_with(term.clone(), strategy))
+}
+
+/// Takes steps with `step` until it finds none, giving up after `fuel` steps.
+/// The evaluators of the other representations of terms count their steps with this.
+pub fn count_steps_with_fuel<T>(
+    mut term: T,
+    fuel: usize,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Result<(T, usize), OutOfFuelErr<T>> {
+    let mut steps = 0;
+    while let Some(evaluated) = step(&term) {
+        if steps == fuel {
+            return Err(OutOfFuelErr { term, steps });
+        }
+        steps += 1;
+        term = evaluated;
+    }
+    Ok((term, steps))
+}
+
+/// A representation of the terms of the pure lambda calculus,
+/// which `step_with` evaluates under each strategy.
+pub trait Lambda: Clone {
+    fn is_val(&self) -> bool;
+
+    /// The callee and argument if `self` is an application.
+    fn as_app(&self) -> Option<(&Self, &Self)>;
+
+    /// The application `self` with its callee and argument replaced, keeping its position.
+    fn with_app(&self, callee: Self, arg: Self) -> Self;
+
+    /// Substitutes `arg` for the parameter of `self` if `self` is an abstraction.
+    fn apply(&self, arg: &Self) -> Option<Self>;
+
+    /// Steps the body of `self` with `step` if `self` is an abstraction.
+    fn step_body(&self, step: fn(&Self) -> Option<Self>) -> Option<Self>;
+}
+
+/// Takes a single step of `strategy`, finding the redex as `Strategy::redex_path` does.
+pub fn step_with<T: Lambda>(term: &T, strategy: Strategy) -> Option<T> {
+    match strategy {
+        Strategy::CallByValue => call_by_value_step(term),
+        Strategy::CallByName => call_by_name_step(term),
+        Strategy::NormalOrder => normal_order_step(term),
+    }
+}
+
+fn call_by_value_step<T: Lambda>(term: &T) -> Option<T> {
+    let (callee, arg) = term.as_app()?;
+    if callee.as_app().is_some() {
+        call_by_value_step(callee)
+            .map(|evaluated_callee| term.with_app(evaluated_callee, arg.clone()))
+    } else if callee.is_val() && arg.as_app().is_some() {
+        call_by_value_step(arg)
+            .map(|evaluated_argument| term.with_app(callee.clone(), evaluated_argument))
+    } else if arg.is_val() {
+        callee.apply(arg)
+    } else {
+        None
+    }
+}
+
+fn call_by_name_step<T: Lambda>(term: &T) -> Option<T> {
+    let (callee, arg) = term.as_app()?;
+    callee.apply(arg).or_else(|| {
+        call_by_name_step(callee)
+            .map(|evaluated_callee| term.with_app(evaluated_callee, arg.clone()))
+    })
+}
+
+fn normal_order_step<T: Lambda>(term: &T) -> Option<T> {
+    let (callee, arg) = match term.as_app() {
+        Some(app) => app,
+        None => return term.step_body(normal_order_step),
+    };
+    callee
+        .apply(arg)
+        .or_else(|| {
+            normal_order_step(callee)
+                .map(|evaluated_callee| term.with_app(evaluated_callee, arg.clone()))
+        })
+        .or_else(|| {
+            normal_order_step(arg)
+                .map(|evaluated_argument| term.with_app(callee.clone(), evaluated_argument))
+        })
+}
@@ -0,0 +1,98 @@
+use super::{
+    count_steps_with_fuel, eval1_with, eval_count, eval_count_with_fuel, redex_paths, OutOfFuelErr,
+    Strategy,
+};
+use crate::chapter_7::term::path::Direction;
+use crate::chapter_7::{parse, term::unnamed::Term};
+use std::convert::TryFrom;
+
+fn unnamed(src: &str) -> Term {
+    Term::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.")
+}
+
+fn eval_named(src: &str, strategy: Strategy) -> String {
+    let (normal_form, _) =
+        eval_count_with_fuel(unnamed(src), strategy, 100).expect("Ran out of fuel.");
+    normal_form.into_unpositioned_named().to_string()
+}
+
+#[test]
+fn call_by_value_matches_eval1() {
+    let term = unnamed(r"(\a. a \b. a) ((\x. x) \b. b)");
+    let (expected, expected_count) = eval_count(term.clone());
+    let actual = eval_count_with_fuel(term, Strategy::CallByValue, 100);
+    assert_eq!(actual, Ok((expected, expected_count)));
+}
+
+#[test]
+fn call_by_name_does_not_evaluate_args() {
+    let term = unnamed(r"(\x. \y. y) ((\x. x x) \x. x x)");
+    let actual = eval_count_with_fuel(term, Strategy::CallByName, 100).map(|(_, count)| count);
+    assert_eq!(actual, Ok(1));
+}
+
+#[test]
+fn call_by_value_diverges_on_omega_arg() {
+    let term = unnamed(r"(\x. \y. y) ((\x. x x) \x. x x)");
+    let actual = eval_count_with_fuel(term, Strategy::CallByValue, 100);
+    assert!(matches!(actual, Err(OutOfFuelErr { steps: 100, .. })));
+}
+
+#[test]
+fn fuel_counts_steps_of_any_kind() {
+    let countdown = |n: &u32| n.checked_sub(1);
+    assert_eq!(count_steps_with_fuel(3, 3, countdown), Ok((0, 3)));
+    assert_eq!(
+        count_steps_with_fuel(3, 2, countdown),
+        Err(OutOfFuelErr { term: 1, steps: 2 })
+    );
+}
+
+#[test]
+fn call_by_name_stops_at_abs() {
+    let actual = eval_named(r"\x. (\y. y) x", Strategy::CallByName);
+    assert_eq!(actual, r"(\x. ((\y. y) x))");
+}
+
+#[test]
+fn normal_order_reduces_under_abs() {
+    let actual = eval_named(r"\x. (\y. y) x", Strategy::NormalOrder);
+    assert_eq!(actual, r"(\x. x)");
+}
+
+#[test]
+fn normal_order_reduces_args_of_stuck_app() {
+    let actual = eval_named(r"\f. f ((\y. y) f)", Strategy::NormalOrder);
+    assert_eq!(actual, r"(\f. (f f))");
+}
+
+#[test]
+fn eval1_with_value() {
+    for strategy in &Strategy::ALL {
+        assert_eq!(eval1_with(unnamed(r"\x. x"), *strategy), None);
+    }
+}
+
+#[test]
+fn strategy_names() {
+    for strategy in &Strategy::ALL {
+        assert_eq!(Strategy::from_name(strategy.name()), Some(*strategy));
+    }
+    assert_eq!(Strategy::from_name("lazy"), None);
+}
+
+#[test]
+fn redex_paths_are_leftmost_outermost() {
+    let term = unnamed(r"(\x. (\y. y) x) ((\z. z) \w. w)");
+    let actual = redex_paths(&term);
+    let expected = vec![
+        vec![],
+        vec![Direction::Callee, Direction::Body],
+        vec![Direction::Arg],
+    ];
+    assert_eq!(actual, expected);
+    for strategy in Strategy::ALL.iter() {
+        let path = strategy.redex_path(&term).expect("Expected a redex.");
+        assert!(actual.contains(&path));
+    }
+}
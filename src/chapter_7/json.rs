@@ -0,0 +1,585 @@
+#[cfg(test)]
+mod test;
+
+use super::parser::err::{
+    ExpectedToken, ParseErr, PositionedToken, Token, TokenOrEof, TokenizationErr,
+    UnexpectedTokenOrEofErr,
+};
+use super::statement::{Def, Statement};
+use super::term::{
+    named::{self, Term as NamedTerm},
+    unnamed::{self, CannotFindVarInCtxErr, Context, Term as UnnamedTerm},
+};
+use crate::file_position::FilePositionRange;
+use crate::json::{FromJson, FromJsonErr, Json, ToJson};
+
+pub const FORMAT: &str = "tapl-rust";
+
+/// The current version of the encoding.
+/// It is incremented whenever a change is made that old readers cannot handle.
+pub const VERSION: usize = 1;
+
+/// A versioned, self-describing JSON document.
+///
+/// Every document is an object of the form
+///
+/// ```text
+/// {"format": "tapl-rust", "version": 1, "kind": <kind>, ...}
+/// ```
+///
+/// where the remaining fields depend on the kind:
+///
+/// - `"named_term"`: `"term"` is a named term.
+/// - `"unnamed_term"`: `"context"` is an array of the names of the free
+///   variables (outermost first, so the last name has index `0`),
+///   and `"term"` is a nameless term in that context.
+/// - `"statements"`: `"statements"` is an array of statements, each either
+///   `{"type": "def", "name": <named var>, "value": <named term>, "position": ...}`
+///   or `{"type": "eval", "term": <named term>}`.
+/// - `"evaluation"`: `"term"` is the closed nameless term a statement evaluated to,
+///   `"named"` is the same term with names (ignored when importing),
+///   and `"steps"` is the number of steps taken, or `null` if not counted.
+/// - `"parse_error"`: `"error"` is a parse error.
+/// - `"naming_error"`: `"error"` is a naming error.
+/// - `"evaluation_error"`: `"message"` describes why evaluation failed,
+///   and `"position"` is the position of the term being evaluated.
+/// - `"import_error"`: `"message"` describes why a JSON input could not be imported.
+///
+/// Terms are objects with a `"type"` of `"var"`, `"abs"` or `"app"`,
+/// and a `"position"`:
+///
+/// - named var: `{"type": "var", "name": "x", "position": ...}`
+/// - named abs: `{"type": "abs", "param": <named var>, "body": ..., "position": ...}`
+/// - nameless var: `{"type": "var", "index": 0, "context_length": 1, "position": ...}`
+/// - nameless abs: `{"type": "abs", "param_name": "x", "body": ..., "position": ...}`
+/// - app: `{"type": "app", "callee": ..., "arg": ..., "position": ...}`
+///
+/// Positions are `{"start": ..., "end": ...}`, where each end is
+/// `{"line": 1, "column": 0, "index": 0}` (lines are one-based, columns are zero-based).
+///
+/// Parse errors are either
+/// `{"type": "tokenization", "unrecognized": "..."}` or
+/// `{"type": "unexpected_token_or_eof", "expected": [...], "actual": ...}`,
+/// where `"expected"` contains names like `"ident"` and `"lambda"`, and
+/// `"actual"` is `null` (end of input) or `{"token": ..., "position": ...}`.
+/// Tokens are `{"type": "ident", "name": "x"}`, `{"type": "number", "value": "3"}`
+/// (a string, since a JSON number cannot hold every `u64` exactly) or `{"type": <name>}`.
+///
+/// Naming errors are `{"type": "unbound_var", "var": <named var>}`.
+///
+/// When importing a nameless term, every variable's `"context_length"` must
+/// equal the length of the context plus the number of enclosing abstractions,
+/// and its `"index"` must be less than its `"context_length"`.
+#[derive(Clone, Debug)]
+pub enum Document {
+    NamedTerm(NamedTerm),
+    UnnamedTerm {
+        context: Context,
+        term: UnnamedTerm,
+    },
+    Statements(Vec<Statement>),
+    Evaluation {
+        term: UnnamedTerm,
+        steps: Option<usize>,
+    },
+    ParseErr(ParseErr),
+    NamingErr(CannotFindVarInCtxErr),
+    EvaluationErr {
+        message: String,
+        position: FilePositionRange,
+    },
+    ImportErr {
+        message: String,
+    },
+}
+
+impl Document {
+    /// The value of the `"kind"` field.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Document::NamedTerm(_) => "named_term",
+            Document::UnnamedTerm { .. } => "unnamed_term",
+            Document::Statements(_) => "statements",
+            Document::Evaluation { .. } => "evaluation",
+            Document::ParseErr(_) => "parse_error",
+            Document::NamingErr(_) => "naming_error",
+            Document::EvaluationErr { .. } => "evaluation_error",
+            Document::ImportErr { .. } => "import_error",
+        }
+    }
+}
+
+impl ToJson for Document {
+    fn to_json(&self) -> Json {
+        let mut fields = vec![
+            ("format", Json::String(FORMAT.to_string())),
+            ("version", VERSION.to_json()),
+            ("kind", Json::String(self.kind().to_string())),
+        ];
+        match self {
+            Document::NamedTerm(term) => fields.push(("term", term.to_json())),
+            Document::UnnamedTerm { context, term } => {
+                fields.push(("context", context.names().to_vec().to_json()));
+                fields.push(("term", term.to_json()));
+            }
+            Document::Statements(statements) => fields.push(("statements", statements.to_json())),
+            Document::Evaluation { term, steps } => {
+                fields.push(("term", term.to_json()));
+                fields.push((
+                    "named",
+                    Json::String(term.clone().into_unpositioned_named().to_string()),
+                ));
+                fields.push(("steps", steps.map_or(Json::Null, |steps| steps.to_json())));
+            }
+            Document::ParseErr(err) => fields.push(("error", err.to_json())),
+            Document::NamingErr(err) => fields.push(("error", err.to_json())),
+            Document::EvaluationErr { message, position } => {
+                fields.push(("message", message.to_json()));
+                fields.push(("position", position.to_json()));
+            }
+            Document::ImportErr { message } => fields.push(("message", message.to_json())),
+        }
+        Json::object(fields)
+    }
+}
+
+impl FromJson for Document {
+    fn from_json(json: &Json) -> Result<Document, FromJsonErr> {
+        let format: String = json.decode_field("format")?;
+        if format != FORMAT {
+            return Err(FromJsonErr::new(format!("Unknown format `{}`.", format)).within("format"));
+        }
+        let version: usize = json.decode_field("version")?;
+        if version != VERSION {
+            return Err(FromJsonErr::new(format!(
+                "Unsupported version {}. Expected version {}.",
+                version, VERSION
+            ))
+            .within("version"));
+        }
+        let kind: String = json.decode_field("kind")?;
+        match kind.as_str() {
+            "named_term" => json.decode_field("term").map(Document::NamedTerm),
+            "unnamed_term" => {
+                let names: Vec<String> = json.decode_field("context")?;
+                let context = Context::from_strings(&names);
+                let term = json
+                    .field("term")
+                    .and_then(|term| unnamed_from_json(term, context.len(), 0))
+                    .map_err(|err| err.within("term"))?;
+                Ok(Document::UnnamedTerm { context, term })
+            }
+            "statements" => json.decode_field("statements").map(Document::Statements),
+            "evaluation" => Ok(Document::Evaluation {
+                term: json.decode_field("term")?,
+                steps: match json.field("steps")? {
+                    Json::Null => None,
+                    _ => Some(json.decode_field("steps")?),
+                },
+            }),
+            "parse_error" => json.decode_field("error").map(Document::ParseErr),
+            "naming_error" => json.decode_field("error").map(Document::NamingErr),
+            "evaluation_error" => Ok(Document::EvaluationErr {
+                message: json.decode_field("message")?,
+                position: json.decode_field("position")?,
+            }),
+            "import_error" => Ok(Document::ImportErr {
+                message: json.decode_field("message")?,
+            }),
+            _ => Err(FromJsonErr::new(format!("Unknown kind `{}`.", kind)).within("kind")),
+        }
+    }
+}
+
+fn tagged(tag: &str, mut fields: Vec<(&str, Json)>) -> Json {
+    fields.insert(0, ("type", Json::String(tag.to_string())));
+    Json::object(fields)
+}
+
+fn tag(json: &Json) -> Result<String, FromJsonErr> {
+    json.decode_field("type")
+}
+
+fn unknown_tag(tag: &str) -> FromJsonErr {
+    FromJsonErr::new(format!("Unknown type `{}`.", tag)).within("type")
+}
+
+impl ToJson for NamedTerm {
+    fn to_json(&self) -> Json {
+        match self {
+            NamedTerm::Var(var) => var.to_json(),
+            NamedTerm::Abs(abs) => tagged(
+                "abs",
+                vec![
+                    ("param", abs.param.to_json()),
+                    ("body", abs.body.to_json()),
+                    ("position", abs.position.to_json()),
+                ],
+            ),
+            NamedTerm::App(app) => tagged(
+                "app",
+                vec![
+                    ("callee", app.callee.to_json()),
+                    ("arg", app.arg.to_json()),
+                    ("position", app.position.to_json()),
+                ],
+            ),
+        }
+    }
+}
+
+impl FromJson for NamedTerm {
+    fn from_json(json: &Json) -> Result<NamedTerm, FromJsonErr> {
+        match tag(json)?.as_str() {
+            "var" => named::Var::from_json(json).map(Into::into),
+            "abs" => Ok(named::Abs {
+                param: json.decode_field("param")?,
+                body: json.decode_field("body")?,
+                position: json.decode_field("position")?,
+            }
+            .into()),
+            "app" => Ok(named::App {
+                callee: json.decode_field("callee")?,
+                arg: json.decode_field("arg")?,
+                position: json.decode_field("position")?,
+            }
+            .into()),
+            other => Err(unknown_tag(other)),
+        }
+    }
+}
+
+impl ToJson for named::Var {
+    fn to_json(&self) -> Json {
+        tagged(
+            "var",
+            vec![
+                ("name", self.name.to_json()),
+                ("position", self.position.to_json()),
+            ],
+        )
+    }
+}
+
+impl FromJson for named::Var {
+    fn from_json(json: &Json) -> Result<named::Var, FromJsonErr> {
+        Ok(named::Var {
+            name: json.decode_field("name")?,
+            position: json.decode_field("position")?,
+        })
+    }
+}
+
+impl ToJson for UnnamedTerm {
+    fn to_json(&self) -> Json {
+        match self {
+            UnnamedTerm::Var(var) => tagged(
+                "var",
+                vec![
+                    ("index", var.index.to_json()),
+                    ("context_length", var.context_length.to_json()),
+                    ("position", var.position.to_json()),
+                ],
+            ),
+            UnnamedTerm::Abs(abs) => tagged(
+                "abs",
+                vec![
+                    ("param_name", abs.param_name.to_json()),
+                    ("body", abs.body.to_json()),
+                    ("position", abs.position.to_json()),
+                ],
+            ),
+            UnnamedTerm::App(app) => tagged(
+                "app",
+                vec![
+                    ("callee", app.callee.to_json()),
+                    ("arg", app.arg.to_json()),
+                    ("position", app.position.to_json()),
+                ],
+            ),
+        }
+    }
+}
+
+/// Decodes a closed nameless term.
+/// Use `Document` to decode terms with free variables.
+impl FromJson for UnnamedTerm {
+    fn from_json(json: &Json) -> Result<UnnamedTerm, FromJsonErr> {
+        unnamed_from_json(json, 0, 0)
+    }
+}
+
+/// Decodes a nameless term under `depth` abstractions
+/// in a context of length `context_length`.
+fn unnamed_from_json(
+    json: &Json,
+    context_length: usize,
+    depth: usize,
+) -> Result<UnnamedTerm, FromJsonErr> {
+    let expected_context_length = context_length + depth;
+    match tag(json)?.as_str() {
+        "var" => {
+            let index: usize = json.decode_field("index")?;
+            let actual_context_length: usize = json.decode_field("context_length")?;
+            if actual_context_length != expected_context_length {
+                return Err(FromJsonErr::new(format!(
+                    "Expected a context length of {}, but found {}.",
+                    expected_context_length, actual_context_length
+                ))
+                .within("context_length"));
+            }
+            if index >= actual_context_length {
+                return Err(FromJsonErr::new(format!(
+                    "Index {} is out of range for a context of length {}.",
+                    index, actual_context_length
+                ))
+                .within("index"));
+            }
+            Ok(unnamed::Var {
+                index,
+                context_length: actual_context_length,
+                position: json.decode_field("position")?,
+            }
+            .into())
+        }
+        "abs" => Ok(unnamed::Abs {
+            param_name: json.decode_field("param_name")?,
+            body: json
+                .field("body")
+                .and_then(|body| unnamed_from_json(body, context_length, depth + 1))
+                .map_err(|err| err.within("body"))?,
+            position: json.decode_field("position")?,
+        }
+        .into()),
+        "app" => Ok(unnamed::App {
+            callee: json
+                .field("callee")
+                .and_then(|callee| unnamed_from_json(callee, context_length, depth))
+                .map_err(|err| err.within("callee"))?,
+            arg: json
+                .field("arg")
+                .and_then(|arg| unnamed_from_json(arg, context_length, depth))
+                .map_err(|err| err.within("arg"))?,
+            position: json.decode_field("position")?,
+        }
+        .into()),
+        other => Err(unknown_tag(other)),
+    }
+}
+
+impl ToJson for Statement {
+    fn to_json(&self) -> Json {
+        match self {
+            Statement::Def(def) => tagged(
+                "def",
+                vec![
+                    ("name", def.name.to_json()),
+                    ("value", def.value.to_json()),
+                    ("position", def.position.to_json()),
+                ],
+            ),
+            Statement::Eval(term) => tagged("eval", vec![("term", term.to_json())]),
+        }
+    }
+}
+
+impl FromJson for Statement {
+    fn from_json(json: &Json) -> Result<Statement, FromJsonErr> {
+        match tag(json)?.as_str() {
+            "def" => Ok(Statement::Def(Def {
+                name: json.decode_field("name")?,
+                value: json.decode_field("value")?,
+                position: json.decode_field("position")?,
+            })),
+            "eval" => json.decode_field("term").map(Statement::Eval),
+            other => Err(unknown_tag(other)),
+        }
+    }
+}
+
+impl ToJson for ParseErr {
+    fn to_json(&self) -> Json {
+        match self {
+            ParseErr::Tokenization(err) => tagged(
+                "tokenization",
+                vec![("unrecognized", Json::String(err.str().to_string()))],
+            ),
+            ParseErr::UnexpectedTokenOrEof(err) => tagged(
+                "unexpected_token_or_eof",
+                vec![
+                    ("expected", err.expected.to_json()),
+                    (
+                        "actual",
+                        match &err.actual {
+                            TokenOrEof::Token(token) => token.to_json(),
+                            TokenOrEof::Eof => Json::Null,
+                        },
+                    ),
+                ],
+            ),
+        }
+    }
+}
+
+impl FromJson for ParseErr {
+    fn from_json(json: &Json) -> Result<ParseErr, FromJsonErr> {
+        match tag(json)?.as_str() {
+            "tokenization" => Ok(ParseErr::Tokenization(TokenizationErr::from_string(
+                json.decode_field("unrecognized")?,
+            ))),
+            "unexpected_token_or_eof" => {
+                let actual = match json.field("actual")? {
+                    Json::Null => TokenOrEof::Eof,
+                    _ => TokenOrEof::Token(json.decode_field("actual")?),
+                };
+                Ok(ParseErr::UnexpectedTokenOrEof(UnexpectedTokenOrEofErr {
+                    expected: json.decode_field("expected")?,
+                    actual,
+                }))
+            }
+            other => Err(unknown_tag(other)),
+        }
+    }
+}
+
+impl ToJson for PositionedToken {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("token", self.token.to_json()),
+            ("position", self.position.to_json()),
+        ])
+    }
+}
+
+impl FromJson for PositionedToken {
+    fn from_json(json: &Json) -> Result<PositionedToken, FromJsonErr> {
+        Ok(PositionedToken {
+            token: json.decode_field("token")?,
+            position: json.decode_field("position")?,
+        })
+    }
+}
+
+impl ToJson for Token {
+    fn to_json(&self) -> Json {
+        let name = match self {
+            Token::Ident(name) => return tagged("ident", vec![("name", name.to_json())]),
+            // As a string, since a JSON number cannot hold every `u64` exactly.
+            Token::Number(n) => {
+                return tagged("number", vec![("value", Json::String(n.to_string()))])
+            }
+            Token::Lambda => "lambda",
+            Token::Dot => "dot",
+            Token::LParen => "lparen",
+            Token::RParen => "rparen",
+            Token::Equals => "equals",
+            Token::Semicolon => "semicolon",
+            Token::Letrec => "letrec",
+            Token::In => "in",
+            Token::Colon => "colon",
+            Token::Arrow => "arrow",
+            Token::LBrace => "lbrace",
+            Token::RBrace => "rbrace",
+            Token::Comma => "comma",
+            Token::LAngle => "langle",
+            Token::RAngle => "rangle",
+            Token::Pipe => "pipe",
+            Token::FatArrow => "fat_arrow",
+            Token::Plus => "plus",
+            Token::LBracket => "lbracket",
+            Token::RBracket => "rbracket",
+            Token::Bang => "bang",
+            Token::ColonEquals => "colon_equals",
+        };
+        tagged(name, vec![])
+    }
+}
+
+impl FromJson for Token {
+    fn from_json(json: &Json) -> Result<Token, FromJsonErr> {
+        match tag(json)?.as_str() {
+            "ident" => json.decode_field("name").map(Token::Ident),
+            "number" => {
+                let value: String = json.decode_field("value")?;
+                value.parse().map(Token::Number).map_err(|_| {
+                    FromJsonErr::new(format!("Expected a number, but found `{}`.", value))
+                        .within("value")
+                })
+            }
+            "lambda" => Ok(Token::Lambda),
+            "dot" => Ok(Token::Dot),
+            "lparen" => Ok(Token::LParen),
+            "rparen" => Ok(Token::RParen),
+            "equals" => Ok(Token::Equals),
+            "semicolon" => Ok(Token::Semicolon),
+            "letrec" => Ok(Token::Letrec),
+            "in" => Ok(Token::In),
+            "colon" => Ok(Token::Colon),
+            "arrow" => Ok(Token::Arrow),
+            "lbrace" => Ok(Token::LBrace),
+            "rbrace" => Ok(Token::RBrace),
+            "comma" => Ok(Token::Comma),
+            "langle" => Ok(Token::LAngle),
+            "rangle" => Ok(Token::RAngle),
+            "pipe" => Ok(Token::Pipe),
+            "fat_arrow" => Ok(Token::FatArrow),
+            "plus" => Ok(Token::Plus),
+            "lbracket" => Ok(Token::LBracket),
+            "rbracket" => Ok(Token::RBracket),
+            "bang" => Ok(Token::Bang),
+            "colon_equals" => Ok(Token::ColonEquals),
+            other => Err(unknown_tag(other)),
+        }
+    }
+}
+
+impl ToJson for ExpectedToken {
+    fn to_json(&self) -> Json {
+        let name = match self {
+            ExpectedToken::Eof => "eof",
+            ExpectedToken::Ident => "ident",
+            ExpectedToken::Lambda => "lambda",
+            ExpectedToken::Dot => "dot",
+            ExpectedToken::LParen => "lparen",
+            ExpectedToken::RParen => "rparen",
+            ExpectedToken::Equals => "equals",
+            ExpectedToken::Semicolon => "semicolon",
+            ExpectedToken::Letrec => "letrec",
+            ExpectedToken::In => "in",
+        };
+        Json::String(name.to_string())
+    }
+}
+
+impl FromJson for ExpectedToken {
+    fn from_json(json: &Json) -> Result<ExpectedToken, FromJsonErr> {
+        match json.as_str()? {
+            "eof" => Ok(ExpectedToken::Eof),
+            "ident" => Ok(ExpectedToken::Ident),
+            "lambda" => Ok(ExpectedToken::Lambda),
+            "dot" => Ok(ExpectedToken::Dot),
+            "lparen" => Ok(ExpectedToken::LParen),
+            "rparen" => Ok(ExpectedToken::RParen),
+            "equals" => Ok(ExpectedToken::Equals),
+            "semicolon" => Ok(ExpectedToken::Semicolon),
+            "letrec" => Ok(ExpectedToken::Letrec),
+            "in" => Ok(ExpectedToken::In),
+            name => Err(FromJsonErr::new(format!("Unknown token `{}`.", name))),
+        }
+    }
+}
+
+impl ToJson for CannotFindVarInCtxErr {
+    fn to_json(&self) -> Json {
+        tagged("unbound_var", vec![("var", self.0.to_json())])
+    }
+}
+
+impl FromJson for CannotFindVarInCtxErr {
+    fn from_json(json: &Json) -> Result<CannotFindVarInCtxErr, FromJsonErr> {
+        match tag(json)?.as_str() {
+            "unbound_var" => json.decode_field("var").map(CannotFindVarInCtxErr),
+            other => Err(unknown_tag(other)),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use super::Document;
+use crate::chapter_7::{
+    parse, parse_statements,
+    parser::{
+        err::{ExpectedToken, PositionedToken},
+        lexer::{tokenize, Token},
+    },
+    term::{
+        named::Term as NamedTerm,
+        unnamed::{Context, Term as UnnamedTerm},
+    },
+};
+use crate::json::{FromJson, Json, ToJson};
+use std::convert::TryFrom;
+
+const SRC: &str = r"(\a. a \b. a) \b.b";
+
+fn round_trip(document: &Document) -> Document {
+    let printed = document.to_json().to_string();
+    let parsed = Json::parse(&printed).expect("Cannot parse JSON.");
+    Document::from_json(&parsed).expect("Cannot decode document.")
+}
+
+#[test]
+fn named_term_round_trips() {
+    let term = parse(SRC).expect("Cannot parse.");
+    match round_trip(&Document::NamedTerm(term.clone())) {
+        Document::NamedTerm(actual) => assert_eq!(actual, term),
+        other => panic!("Expected a named term, but found {:?}.", other),
+    }
+}
+
+#[test]
+fn unnamed_term_round_trips() {
+    let context = Context::from_strs(&["f", "g"]);
+    let term = UnnamedTerm::from_named(parse(r"\x. f (g x)").expect("Cannot parse."), &context)
+        .expect("Cannot remove names.");
+    match round_trip(&Document::UnnamedTerm {
+        context: context.clone(),
+        term: term.clone(),
+    }) {
+        Document::UnnamedTerm {
+            context: actual_context,
+            term: actual,
+        } => {
+            assert_eq!(actual, term);
+            assert_eq!(actual_context.names(), context.names());
+        }
+        other => panic!("Expected an unnamed term, but found {:?}.", other),
+    }
+}
+
+#[test]
+fn var_encoding() {
+    let term = parse("x").expect("Cannot parse.");
+    assert_eq!(
+        term.to_json().to_string(),
+        r#"{"type":"var","name":"x","position":{"start":{"line":1,"column":0,"index":0},"end":{"line":1,"column":1,"index":1}}}"#
+    );
+}
+
+#[test]
+fn closed_unnamed_term_decodes_directly() {
+    let term =
+        UnnamedTerm::try_from(parse(SRC).expect("Cannot parse.")).expect("Cannot remove names.");
+    assert_eq!(UnnamedTerm::from_json(&term.to_json()), Ok(term));
+}
+
+#[test]
+fn index_out_of_range_is_rejected() {
+    let json = Json::parse(
+        r#"{"type": "abs", "param_name": "x", "position": null, "body":
+            {"type": "var", "index": 1, "context_length": 1, "position": null}}"#,
+    )
+    .expect("Cannot parse JSON.");
+    let err = UnnamedTerm::from_json(&json).expect_err("Decoded an open term.");
+    assert_eq!(
+        err.to_string(),
+        "/body/index: Index 1 is out of range for a context of length 1."
+    );
+}
+
+#[test]
+fn inconsistent_context_length_is_rejected() {
+    let term = UnnamedTerm::from_named(
+        parse(r"\x. y").expect("Cannot parse."),
+        &Context::from_strs(&["y"]),
+    )
+    .expect("Cannot remove names.");
+    let json = Document::UnnamedTerm {
+        context: Context::from_strs(&["y", "z"]),
+        term,
+    }
+    .to_json();
+    let err = Document::from_json(&json).expect_err("Decoded an inconsistent term.");
+    assert_eq!(
+        err.to_string(),
+        "/term/body/context_length: Expected a context length of 3, but found 2."
+    );
+}
+
+#[test]
+fn parse_errors_round_trip() {
+    for src in &[
+        r"\x.",
+        r"x )",
+        r"x $",
+        r"letrec f = \x. x f",
+        r"\x:y. x",
+        r"\x. 1",
+        r"\x. {x}",
+        r"x => y",
+        r"[x]",
+        r"x := y",
+    ] {
+        let err = parse(src).expect_err("Parsed invalid source.");
+        let document = Document::ParseErr(err.clone());
+        match round_trip(&document) {
+            Document::ParseErr(actual) => {
+                assert_eq!(actual.to_json(), err.to_json());
+                assert_eq!(actual.to_string(), err.to_string());
+            }
+            other => panic!("Expected a parse error, but found {:?}.", other),
+        }
+    }
+}
+
+#[test]
+fn naming_errors_round_trip() {
+    let err = UnnamedTerm::try_from(parse(r"\x. y").expect("Cannot parse."))
+        .expect_err("Removed names from an open term.");
+    match round_trip(&Document::NamingErr(err.clone())) {
+        Document::NamingErr(actual) => assert_eq!(actual.0, err.0),
+        other => panic!("Expected a naming error, but found {:?}.", other),
+    }
+}
+
+#[test]
+fn unsupported_versions_are_rejected() {
+    let json = Json::parse(r#"{"format": "tapl-rust", "version": 2, "kind": "named_term"}"#)
+        .expect("Cannot parse JSON.");
+    let err = Document::from_json(&json).expect_err("Decoded a future version.");
+    assert_eq!(
+        err.to_string(),
+        "/version: Unsupported version 2. Expected version 1."
+    );
+    let json = Json::parse(
+        r#"{"format": "tapl-rust", "version": 1, "kind": "named_term", "term": {"type": "lam"}}"#,
+    )
+    .expect("Cannot parse JSON.");
+    let err = NamedTerm::from_json(json.get("term").expect("No term."))
+        .expect_err("Decoded an unknown type.");
+    assert_eq!(err.to_string(), "/type: Unknown type `lam`.");
+}
+
+#[test]
+fn statements_round_trip() {
+    let statements = parse_statements(r"id = \x. x; id id").expect("Cannot parse.");
+    match round_trip(&Document::Statements(statements.clone())) {
+        Document::Statements(actual) => assert_eq!(actual, statements),
+        other => panic!("Expected statements, but found {:?}.", other),
+    }
+}
+
+#[test]
+fn evaluation_round_trips() {
+    let term = UnnamedTerm::try_from(parse(r"\x. x").expect("Cannot parse."))
+        .expect("Cannot remove names.");
+    let document = Document::Evaluation {
+        term: term.clone(),
+        steps: Some(3),
+    };
+    assert!(document
+        .to_json()
+        .to_string()
+        .contains(r#""named":"(\\x. x)","steps":3"#));
+    match round_trip(&document) {
+        Document::Evaluation {
+            term: actual,
+            steps,
+        } => {
+            assert_eq!(actual, term);
+            assert_eq!(steps, Some(3));
+        }
+        other => panic!("Expected an evaluation, but found {:?}.", other),
+    }
+}
+
+#[test]
+fn tokens_round_trip() {
+    let tokens =
+        tokenize(r"x \ . ( ) = ; letrec in : -> 0 18446744073709551615 { } , < > | => + [ ] ! :=")
+            .expect("Cannot tokenize.");
+    for token in tokens {
+        assert_eq!(PositionedToken::from_json(&token.to_json()), Ok(token));
+    }
+    let expected_tokens = [
+        ExpectedToken::Eof,
+        ExpectedToken::Ident,
+        ExpectedToken::Lambda,
+        ExpectedToken::Dot,
+        ExpectedToken::LParen,
+        ExpectedToken::RParen,
+        ExpectedToken::Equals,
+        ExpectedToken::Semicolon,
+        ExpectedToken::Letrec,
+        ExpectedToken::In,
+    ];
+    for expected in expected_tokens.iter() {
+        assert_eq!(ExpectedToken::from_json(&expected.to_json()), Ok(*expected));
+    }
+}
+
+#[test]
+fn empty_token_names_are_rejected() {
+    let json = Json::parse(r#"{"type": ""}"#).expect("Cannot parse JSON.");
+    let err = Token::from_json(&json).expect_err("Decoded an empty token name.");
+    assert_eq!(err.to_string(), "/type: Unknown type ``.");
+    let json = Json::parse(r#""""#).expect("Cannot parse JSON.");
+    let err = ExpectedToken::from_json(&json).expect_err("Decoded an empty token name.");
+    assert_eq!(err.to_string(), "Unknown token ``.");
+}
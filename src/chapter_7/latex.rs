@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod test;
+
+use super::term::{named::Term as NamedTerm, path::Direction, unnamed::Term};
+use super::trace::Step;
+use crate::latex::{align_steps, ident, Chunk, Derivation};
+
+/// Typesets a term in math mode, e.g., `\lambda x.\,x\;y`.
+///
+/// Unlike `Display`, only the parentheses required by the usual conventions
+/// are printed: application associates to the left,
+/// and the body of an abstraction extends as far to the right as possible.
+pub fn term(t: &NamedTerm) -> String {
+    render(t, None).latex
+}
+
+/// Typesets the steps of a trace from `start`, one per line, with each redex underlined,
+/// followed by the final term, which is `start` itself if there are no steps.
+/// Terms longer than `width` characters are broken
+/// between the arguments of their outermost application.
+pub fn trace(start: &Term, steps: &[Step], width: usize) -> String {
+    let mut terms = vec![];
+    for step in steps {
+        let before = step.before.clone().into_unpositioned_named();
+        terms.push(chunks(&before, Some(&step.path)));
+    }
+    let last = steps.last().map_or(start, |step| &step.after);
+    terms.push(chunks(&last.clone().into_unpositioned_named(), None));
+    align_steps(&terms, width)
+}
+
+/// Builds the derivation of a step using the rules of Figure 5-3,
+/// together with `E-Abs` for steps inside an abstraction,
+/// which only normal order evaluation takes.
+pub fn step_derivation(step: &Step) -> Derivation {
+    let before = step.before.clone().into_unpositioned_named();
+    let after = step.after.clone().into_unpositioned_named();
+    derive(&before, &after, &step.path)
+}
+
+fn derive(before: &NamedTerm, after: &NamedTerm, path: &[Direction]) -> Derivation {
+    let conclusion = format!(r"{} \longrightarrow {}", term(before), term(after));
+    let (rule, premise) = match (before, after, path.split_first()) {
+        (_, _, None) => return Derivation::axiom("E-AppAbs", conclusion),
+        (NamedTerm::App(before), NamedTerm::App(after), Some((Direction::Callee, rest))) => {
+            ("E-App1", derive(&before.callee, &after.callee, rest))
+        }
+        (NamedTerm::App(before), NamedTerm::App(after), Some((Direction::Arg, rest))) => {
+            ("E-App2", derive(&before.arg, &after.arg, rest))
+        }
+        (NamedTerm::Abs(before), NamedTerm::Abs(after), Some((Direction::Body, rest))) => {
+            ("E-Abs", derive(&before.body, &after.body, rest))
+        }
+        (_, _, Some((direction, _))) => panic!("Cannot follow {:?} in path.", direction),
+    };
+    Derivation {
+        rule: rule.to_string(),
+        premises: vec![premise],
+        conclusion,
+    }
+}
+
+/// Splits a term into the callee and arguments of its outermost application,
+/// which may be placed on separate lines.
+/// An application containing the highlighted subterm as a whole is kept together.
+fn chunks(t: &NamedTerm, highlight: Option<&[Direction]>) -> Vec<Chunk> {
+    match t {
+        NamedTerm::App(app) if highlight.is_none_or(|path| !path.is_empty()) => {
+            let mut chunks = if app.callee.is_app() {
+                chunks(&app.callee, follow(highlight, Direction::Callee))
+            } else {
+                vec![operand(&app.callee, follow(highlight, Direction::Callee))]
+            };
+            chunks.push(operand(&app.arg, follow(highlight, Direction::Arg)));
+            chunks
+        }
+        _ => vec![render(t, highlight)],
+    }
+}
+
+/// Renders the callee or argument of an application.
+/// Abstractions are always parenthesized, as are applications in argument position;
+/// applications in callee position never reach this function.
+fn operand(t: &NamedTerm, highlight: Option<&[Direction]>) -> Chunk {
+    let chunk = render(t, highlight);
+    if t.is_var() {
+        chunk
+    } else {
+        Chunk {
+            latex: format!("({})", chunk.latex),
+            plain: format!("({})", chunk.plain),
+        }
+    }
+}
+
+fn render(t: &NamedTerm, highlight: Option<&[Direction]>) -> Chunk {
+    let chunk = match t {
+        NamedTerm::Var(var) => Chunk {
+            latex: ident(&var.name),
+            plain: var.name.clone(),
+        },
+        NamedTerm::Abs(abs) => {
+            let body = render(&abs.body, follow(highlight, Direction::Body));
+            Chunk {
+                latex: format!(r"\lambda {}.\,{}", ident(&abs.param.name), body.latex),
+                plain: format!(r"\{}. {}", abs.param.name, body.plain),
+            }
+        }
+        NamedTerm::App(_) => {
+            let chunks = chunks(t, highlight.filter(|path| !path.is_empty()));
+            Chunk {
+                latex: chunks
+                    .iter()
+                    .map(|chunk| chunk.latex.as_str())
+                    .collect::<Vec<_>>()
+                    .join(r"\;"),
+                plain: chunks
+                    .iter()
+                    .map(|chunk| chunk.plain.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            }
+        }
+    };
+    match highlight {
+        Some([]) => Chunk {
+            latex: format!(r"\underline{{{}}}", chunk.latex),
+            plain: chunk.plain,
+        },
+        _ => chunk,
+    }
+}
+
+/// The part of the highlighted path below the given child,
+/// or `None` if the highlighted subterm is not within that child.
+fn follow(highlight: Option<&[Direction]>, direction: Direction) -> Option<&[Direction]> {
+    match highlight?.split_first() {
+        Some((first, rest)) if *first == direction => Some(rest),
+        _ => None,
+    }
+}
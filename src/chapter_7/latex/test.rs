@@ -0,0 +1,87 @@
+use super::{step_derivation, term, trace};
+use crate::chapter_7::{
+    evaluator::Strategy,
+    parse,
+    term::unnamed::Term,
+    trace::{steps, steps_with},
+};
+use crate::latex::Style;
+use std::convert::TryFrom;
+
+fn unnamed(src: &str) -> Term {
+    Term::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.")
+}
+
+#[test]
+fn only_necessary_parens_are_printed() {
+    let actual = term(&parse(r"(\x. x y) (\y. y) (z w) z1").expect("Cannot parse."));
+    let expected = r"(\lambda x.\,x\;y)\;(\lambda y.\,y)\;(z\;w)\;z_{1}";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn traces_underline_redexes() {
+    let start = unnamed(r"(\x. x) ((\y. y) \z. z)");
+    let steps: Vec<_> = steps(start.clone()).collect();
+    let actual = trace(&start, &steps, 80);
+    let expected = r"\begin{align*}
+&(\lambda x.\,x)\;(\underline{(\lambda y.\,y)\;(\lambda z.\,z)}) \\
+\longrightarrow{} &\underline{(\lambda x.\,x)\;(\lambda z.\,z)} \\
+\longrightarrow{} &\lambda z.\,z
+\end{align*}";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn traces_without_steps_show_the_start() {
+    let actual = trace(&unnamed(r"\x. x"), &[], 60);
+    let expected = r"\begin{align*}
+&\lambda x.\,x
+\end{align*}";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn traces_break_between_arguments() {
+    let start = unnamed(r"(\a. \b. \c. a) (\x. x) (\y. y) (\z. z)");
+    let steps: Vec<_> = steps(start.clone()).collect();
+    let actual = trace(&start, &steps[..1], 20);
+    let expected = r"\begin{align*}
+&\underline{(\lambda a.\,\lambda b.\,\lambda c.\,a)\;(\lambda x.\,x)} \\
+&\quad (\lambda y.\,y)\;(\lambda z.\,z) \\
+\longrightarrow{} &(\lambda b.\,\lambda c.\,\lambda x.\,x) \\
+&\quad (\lambda y.\,y)\;(\lambda z.\,z)
+\end{align*}";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn step_derivations() {
+    let step = steps(unnamed(r"(\x. x) ((\y. y) \z. z)"))
+        .next()
+        .expect("Expected a step.");
+    let expected = r"\begin{prooftree}
+\AxiomC{}
+\RightLabel{\scriptsize\textsc{E-AppAbs}}
+\UnaryInfC{$(\lambda y.\,y)\;(\lambda z.\,z) \longrightarrow \lambda z.\,z$}
+\RightLabel{\scriptsize\textsc{E-App2}}
+\UnaryInfC{$(\lambda x.\,x)\;((\lambda y.\,y)\;(\lambda z.\,z)) \longrightarrow (\lambda x.\,x)\;(\lambda z.\,z)$}
+\end{prooftree}";
+    assert_eq!(
+        step_derivation(&step)
+            .to_latex(Style::Bussproofs)
+            .expect("Cannot typeset."),
+        expected
+    );
+
+    let step = steps_with(unnamed(r"\x. (\y. y) x"), Strategy::NormalOrder)
+        .next()
+        .expect("Expected a step.");
+    let derivation = step_derivation(&step);
+    assert_eq!(derivation.rule, "E-Abs");
+    assert_eq!(derivation.premises[0].rule, "E-AppAbs");
+    assert_eq!(
+        derivation.premises[0].conclusion,
+        r"(\lambda y.\,y)\;x \longrightarrow x"
+    );
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{self, Strategy};
+use super::locally_nameless_evaluator::Fresh;
+use super::term::levels::{Abs, Free, Term};
+
+impl Fresh for Free {
+    fn fresh(abs: &Abs) -> usize {
+        abs.body.next_level()
+    }
+}
+
+/// Takes a single call-by-value step, like `evaluator::eval1`.
+pub fn eval1(term: &Term) -> Option<Term> {
+    eval1_with(term, Strategy::CallByValue)
+}
+
+/// Takes a single step. Terms must be locally closed. No step shifts any variable.
+pub fn eval1_with(term: &Term, strategy: Strategy) -> Option<Term> {
+    evaluator::step_with(term, strategy)
+}
+
+pub type OutOfFuelErr = evaluator::OutOfFuelErr<Term>;
+
+/// Like `evaluator::eval_count_with_fuel`, but on terms with de Bruijn levels.
+pub fn eval_count_with_fuel(
+    term: Term,
+    strategy: Strategy,
+    fuel: usize,
+) -> Result<(Term, usize), OutOfFuelErr> {
+    evaluator::count_steps_with_fuel(term, fuel, |term| eval1_with(term, strategy))
+}
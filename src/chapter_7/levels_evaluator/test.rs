@@ -0,0 +1,84 @@
+use super::{eval1_with, eval_count_with_fuel};
+use crate::chapter_7::{
+    evaluator::{self, Strategy},
+    named_evaluator::free_vars,
+    parse,
+    term::{
+        levels::Term,
+        unnamed::{self, Context},
+    },
+};
+use std::convert::TryFrom;
+
+fn levels(src: &str) -> Term {
+    Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.")
+}
+
+#[test]
+fn beta_reduction_substitutes_without_shifting() {
+    let term = levels(r"(\x. \y. x y) (\z. z)");
+    let actual = eval1_with(&term, Strategy::CallByValue).expect("Expected a step.");
+    assert_eq!(actual.to_string(), r"(\. ((\. 0) 0))");
+}
+
+#[test]
+fn normal_order_steps_under_binders() {
+    let term = levels(r"\y. (\x. \y. x y) y");
+    let (actual, steps) =
+        eval_count_with_fuel(term, Strategy::NormalOrder, 10).expect("Ran out of fuel.");
+    assert_eq!(steps, 1);
+    assert_eq!(actual.to_string(), r"(\. (\. (1 0)))");
+    assert!(actual.is_locally_closed());
+}
+
+#[test]
+fn agrees_with_de_bruijn_evaluator() {
+    let examples = [
+        r"(\x. \y. x) y",
+        r"(\f. \x. f (f x)) (\f. \x. f (f x))",
+        r"(\x. x x) (\x. \y. x y)",
+        r"(\x. \y. y) ((\x. x x) \x. x x)",
+        r"\y. (\x. \y. x y) y",
+        r"(\n. \f. \x. f (n f x)) (\f. \x. f x) a b",
+        r"\a. (\x. \y. y x a b) (\z. z a b)",
+    ];
+    for example in examples.iter() {
+        let named = parse(example).expect("Cannot parse.");
+        let vars: Vec<_> = free_vars(&named).into_iter().collect();
+        let ctx = Context::from_strings(&vars);
+        for &strategy in Strategy::ALL.iter() {
+            let mut unnamed =
+                unnamed::Term::from_named(named.clone(), &ctx).expect("Cannot remove names.");
+            let mut term = Term::try_from(unnamed.clone()).expect("Cannot convert.");
+            for _ in 0..50 {
+                match (
+                    eval1_with(&term, strategy),
+                    evaluator::eval1_with(unnamed.clone(), strategy),
+                ) {
+                    (Some(next), Some(next_unnamed)) => {
+                        let converted = next
+                            .clone()
+                            .into_unnamed(ctx.len())
+                            .expect("Cannot convert.");
+                        assert_eq!(
+                            converted,
+                            next_unnamed,
+                            "`{}` under {}",
+                            example,
+                            strategy.name()
+                        );
+                        term = next;
+                        unnamed = next_unnamed;
+                    }
+                    (None, None) => break,
+                    _ => panic!(
+                        "`{}` under {} disagrees on whether to step.",
+                        example,
+                        strategy.name()
+                    ),
+                }
+            }
+        }
+    }
+}
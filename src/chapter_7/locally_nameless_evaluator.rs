@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{self, Lambda, Strategy};
+use super::named_evaluator::fresh_name;
+use super::term::locally_nameless::{Abs, App, Free, FreeVar, Term};
+use crate::file_position::Position;
+
+/// The free variables an abstraction can be opened with to step under its binder.
+pub trait Fresh: FreeVar {
+    /// A free variable that does not occur in the body of `abs`.
+    fn fresh(abs: &Abs<Self>) -> Self::Id;
+}
+
+impl Fresh for Free {
+    fn fresh(abs: &Abs) -> String {
+        fresh_name(&abs.param_name, &abs.body.free_vars())
+    }
+}
+
+impl<F: Fresh> Lambda for Term<F> {
+    fn is_val(&self) -> bool {
+        Term::is_val(self)
+    }
+
+    fn as_app(&self) -> Option<(&Term<F>, &Term<F>)> {
+        match self {
+            Term::App(app) => Some((&app.callee, &app.arg)),
+            _ => None,
+        }
+    }
+
+    fn with_app(&self, callee: Term<F>, arg: Term<F>) -> Term<F> {
+        App {
+            callee,
+            arg,
+            position: self.position(),
+        }
+        .into()
+    }
+
+    fn apply(&self, arg: &Term<F>) -> Option<Term<F>> {
+        match self {
+            Term::Abs(abs) => Some((**abs).clone().open(arg)),
+            _ => None,
+        }
+    }
+
+    // To step under the binder, the body is opened with a fresh free variable,
+    // which is closed again afterwards.
+    fn step_body(&self, step: fn(&Term<F>) -> Option<Term<F>>) -> Option<Term<F>> {
+        match self {
+            Term::Abs(abs) => {
+                let id = F::fresh(abs);
+                let opened = abs.body.clone().open_free_at(0, id.clone());
+                step(&opened).map(|evaluated_body| {
+                    Abs {
+                        body: evaluated_body.close_at(0, &id),
+                        param_name: abs.param_name.clone(),
+                        position: abs.position,
+                    }
+                    .into()
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Takes a single call-by-value step, like `evaluator::eval1`.
+pub fn eval1(term: &Term) -> Option<Term> {
+    eval1_with(term, Strategy::CallByValue)
+}
+
+/// Takes a single step. Terms must be locally closed.
+pub fn eval1_with(term: &Term, strategy: Strategy) -> Option<Term> {
+    evaluator::step_with(term, strategy)
+}
+
+pub type OutOfFuelErr = evaluator::OutOfFuelErr<Term>;
+
+/// Like `evaluator::eval_count_with_fuel`, but on locally nameless terms.
+pub fn eval_count_with_fuel(
+    term: Term,
+    strategy: Strategy,
+    fuel: usize,
+) -> Result<(Term, usize), OutOfFuelErr> {
+    evaluator::count_steps_with_fuel(term, fuel, |term| eval1_with(term, strategy))
+}
@@ -0,0 +1,77 @@
+use super::{eval1_with, eval_count_with_fuel};
+use crate::chapter_7::{
+    evaluator::{self, Strategy},
+    parse,
+    term::{
+        locally_nameless::Term,
+        unnamed::{self, Context},
+    },
+};
+
+fn locally_nameless(src: &str) -> Term {
+    Term::from(parse(src).expect("Cannot parse."))
+}
+
+#[test]
+fn beta_reduction_opens_the_body() {
+    let term = locally_nameless(r"(\x. \y. x y) (\z. z)");
+    let actual = eval1_with(&term, Strategy::CallByValue).expect("Expected a step.");
+    assert_eq!(actual.to_string(), r"(\. ((\. 0) 0))");
+}
+
+#[test]
+fn normal_order_steps_under_binders() {
+    let term = locally_nameless(r"\y. (\x. \y. x y) y");
+    let (actual, steps) =
+        eval_count_with_fuel(term, Strategy::NormalOrder, 10).expect("Ran out of fuel.");
+    assert_eq!(steps, 1);
+    assert_eq!(actual.to_string(), r"(\. (\. (1 0)))");
+    assert!(actual.is_locally_closed());
+}
+
+#[test]
+fn agrees_with_de_bruijn_evaluator() {
+    let examples = [
+        r"(\x. \y. x) y",
+        r"(\f. \x. f (f x)) (\f. \x. f (f x))",
+        r"(\x. x x) (\x. \y. x y)",
+        r"(\x. \y. y) ((\x. x x) \x. x x)",
+        r"\y. (\x. \y. x y) y",
+        r"(\n. \f. \x. f (n f x)) (\f. \x. f x) a b",
+    ];
+    for example in examples.iter() {
+        let named = parse(example).expect("Cannot parse.");
+        let vars: Vec<_> = Term::from(named.clone()).free_vars().into_iter().collect();
+        let ctx = Context::from_strings(&vars);
+        for &strategy in Strategy::ALL.iter() {
+            let mut term = Term::from(named.clone());
+            let mut unnamed =
+                unnamed::Term::from_named(named.clone(), &ctx).expect("Cannot remove names.");
+            for _ in 0..50 {
+                match (
+                    eval1_with(&term, strategy),
+                    evaluator::eval1_with(unnamed.clone(), strategy),
+                ) {
+                    (Some(next), Some(next_unnamed)) => {
+                        let converted = next.clone().into_unnamed(&ctx).expect("Cannot convert.");
+                        assert_eq!(
+                            converted,
+                            next_unnamed,
+                            "`{}` under {}",
+                            example,
+                            strategy.name()
+                        );
+                        term = next;
+                        unnamed = next_unnamed;
+                    }
+                    (None, None) => break,
+                    _ => panic!(
+                        "`{}` under {} disagrees on whether to step.",
+                        example,
+                        strategy.name()
+                    ),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{self, Strategy};
+use super::term::mixed::{Abs, App, Bool, If, Nat, Op, Term};
+use crate::file_position::Position;
+
+/// Takes a single call-by-value step, like `evaluator::eval1`.
+pub fn eval1(term: &Term) -> Option<Term> {
+    eval1_with(term, Strategy::CallByValue)
+}
+
+/// Takes a single step. Besides beta reduction, a primitive applied to as many
+/// numbers as its arity is replaced by its result, and `if` with a boolean guard
+/// by the chosen branch. Primitives evaluate their arguments under every strategy.
+pub fn eval1_with(term: &Term, strategy: Strategy) -> Option<Term> {
+    match term {
+        Term::Var(_) | Term::Nat(_) | Term::Bool(_) | Term::Prim(_) => None,
+        Term::Abs(abs) => {
+            if strategy == Strategy::NormalOrder {
+                eval1_with(&abs.body, strategy).map(|body| {
+                    Abs {
+                        body,
+                        param_name: abs.param_name.clone(),
+                        position: abs.position,
+                    }
+                    .into()
+                })
+            } else {
+                None
+            }
+        }
+        Term::If(if_) => match &if_.guard {
+            Term::Bool(guard) => Some(if guard.value {
+                if_.then.clone()
+            } else {
+                if_.else_.clone()
+            }),
+            guard => {
+                if let Some(guard) = eval1_with(guard, strategy) {
+                    return Some(
+                        If {
+                            guard,
+                            ..(**if_).clone()
+                        }
+                        .into(),
+                    );
+                }
+                if strategy != Strategy::NormalOrder {
+                    return None;
+                }
+                if let Some(then) = eval1_with(&if_.then, strategy) {
+                    return Some(
+                        If {
+                            then,
+                            ..(**if_).clone()
+                        }
+                        .into(),
+                    );
+                }
+                eval1_with(&if_.else_, strategy).map(|else_| {
+                    If {
+                        else_,
+                        ..(**if_).clone()
+                    }
+                    .into()
+                })
+            }
+        },
+        Term::App(app) => {
+            if let Term::Abs(callee) = &app.callee {
+                if strategy != Strategy::CallByValue || app.arg.is_val() {
+                    return Some((**callee).clone().apply(&app.arg));
+                }
+            } else if let Some(result) = delta(term) {
+                return Some(result);
+            } else if let Some(callee) = eval1_with(&app.callee, strategy) {
+                return Some(
+                    App {
+                        callee,
+                        arg: app.arg.clone(),
+                        position: app.position,
+                    }
+                    .into(),
+                );
+            }
+
+            let steps_arg = match strategy {
+                Strategy::CallByValue => app.callee.is_val(),
+                Strategy::CallByName => is_waiting_for_arg(&app.callee),
+                Strategy::NormalOrder => true,
+            };
+            if steps_arg {
+                eval1_with(&app.arg, strategy).map(|arg| {
+                    App {
+                        callee: app.callee.clone(),
+                        arg,
+                        position: app.position,
+                    }
+                    .into()
+                })
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Whether `term` is a primitive that needs another argument.
+fn is_waiting_for_arg(term: &Term) -> bool {
+    term.partial_prim()
+        .is_some_and(|(prim, args)| args.len() < prim.op.arity())
+}
+
+/// Applies a primitive to numbers, e.g., `plus #1 #2` to `#3`.
+/// A result too large for a `u64` leaves the application stuck.
+fn delta(term: &Term) -> Option<Term> {
+    let (prim, args) = term.partial_prim()?;
+    if args.len() != prim.op.arity() {
+        return None;
+    }
+    let args = args
+        .iter()
+        .map(|arg| arg.as_nat())
+        .collect::<Option<Vec<_>>>()?;
+    let position = term.position();
+    Some(match prim.op {
+        Op::Succ => Nat {
+            position,
+            value: args[0].checked_add(1)?,
+        }
+        .into(),
+        Op::Pred => Nat {
+            position,
+            value: args[0].saturating_sub(1),
+        }
+        .into(),
+        Op::IsZero => Bool {
+            position,
+            value: args[0] == 0,
+        }
+        .into(),
+        Op::Plus => Nat {
+            position,
+            value: args[0].checked_add(args[1])?,
+        }
+        .into(),
+    })
+}
+
+pub type OutOfFuelErr = evaluator::OutOfFuelErr<Term>;
+
+/// Like `evaluator::eval_count_with_fuel`, but with native numbers and booleans.
+pub fn eval_count_with_fuel(
+    term: Term,
+    strategy: Strategy,
+    fuel: usize,
+) -> Result<(Term, usize), OutOfFuelErr> {
+    evaluator::count_steps_with_fuel(term, fuel, |term| eval1_with(term, strategy))
+}
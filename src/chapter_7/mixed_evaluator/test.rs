@@ -0,0 +1,187 @@
+use super::{eval1_with, eval_count_with_fuel};
+use crate::chapter_7::evaluator::Strategy;
+use crate::chapter_7::{
+    eval_count, evaluator, parse,
+    term::{
+        mixed::{
+            church_bool, church_nat, churchbool, churchnat, realbool, realnat, App, Nat, Term,
+        },
+        unnamed::{self, Context},
+    },
+    test_util::position,
+};
+use std::convert::TryFrom;
+
+fn mixed(src: &str) -> Term {
+    Term::from_named(parse(src).expect("Cannot parse."), &Context::empty())
+        .expect("Cannot remove names.")
+}
+
+fn app(callee: Term, arg: Term) -> Term {
+    App {
+        position: position(),
+        callee,
+        arg,
+    }
+    .into()
+}
+
+fn eval(term: Term) -> Term {
+    let (term, _) =
+        eval_count_with_fuel(term, Strategy::CallByValue, 10_000).expect("Ran out of fuel.");
+    term
+}
+
+#[test]
+fn delta_rules() {
+    let examples = [
+        ("succ (succ zero)", "#2"),
+        ("pred zero", "#0"),
+        ("pred (succ (succ zero))", "#1"),
+        ("iszero zero", "true"),
+        ("iszero (succ zero)", "false"),
+        ("plus (succ zero) (succ (succ zero))", "#3"),
+        (r"(\x. plus x x) (succ zero)", "#2"),
+        ("if (iszero zero) (succ zero) zero", "#1"),
+        ("plus (succ zero)", "(plus #1)"),
+    ];
+    for &strategy in Strategy::ALL.iter() {
+        for (src, expected) in examples.iter() {
+            let (actual, _) =
+                eval_count_with_fuel(mixed(src), strategy, 100).expect("Ran out of fuel.");
+            assert_eq!(
+                actual.to_string(),
+                *expected,
+                "`{}` under {}",
+                src,
+                strategy.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn if_only_evaluates_the_chosen_branch() {
+    let term = mixed(r"if true zero ((\x. x x) (\x. x x))");
+    assert_eq!(eval(term).to_string(), "#0");
+}
+
+#[test]
+fn ill_typed_primitives_are_stuck() {
+    for src in ["succ true", "if zero zero zero", "plus zero (\\x. x)"].iter() {
+        let term = eval(mixed(src));
+        assert!(!term.is_val(), "`{}` evaluated to the value {}.", src, term);
+        assert_eq!(eval1_with(&term, Strategy::CallByValue), None);
+    }
+}
+
+#[test]
+fn overflowing_primitives_are_stuck() {
+    let max = || -> Term {
+        Nat {
+            position: position(),
+            value: u64::MAX,
+        }
+        .into()
+    };
+    let examples = [
+        (app(mixed("succ"), max()), "(succ #18446744073709551615)"),
+        (
+            app(app(mixed("plus"), max()), mixed("succ zero")),
+            "((plus #18446744073709551615) #1)",
+        ),
+    ];
+    for (term, expected) in examples.iter() {
+        let term = eval(term.clone());
+        assert_eq!(term.to_string(), *expected);
+        assert_eq!(eval1_with(&term, Strategy::CallByValue), None);
+    }
+    let term = app(app(mixed("plus"), max()), mixed("zero"));
+    assert_eq!(eval(term).to_string(), format!("#{}", u64::MAX));
+}
+
+#[test]
+fn native_arithmetic_takes_fewer_steps() {
+    let ctx = Context::empty();
+    let church = unnamed::Term::from_named(
+        parse(r"(\m. \n. \s. \z. m s (n s z)) (\s. \z. s (s (s z))) (\s. \z. s (s z)) (\x. x) (\x. x)")
+            .expect("Cannot parse."),
+        &ctx,
+    )
+    .expect("Cannot remove names.");
+    let (_, church_steps) = eval_count(church);
+    let (native, native_steps) = eval_count_with_fuel(
+        mixed("plus (succ (succ (succ zero))) (succ (succ zero))"),
+        Strategy::CallByValue,
+        100,
+    )
+    .expect("Ran out of fuel.");
+    assert_eq!(native.as_nat(), Some(5));
+    assert!(
+        native_steps < church_steps,
+        "{} >= {}",
+        native_steps,
+        church_steps
+    );
+}
+
+#[test]
+fn church_numerals_round_trip() {
+    for n in 0..6 {
+        let church: Term = church_nat(n, position()).into();
+        let real = eval(app(realnat(position()), church));
+        assert_eq!(real.as_nat(), Some(n));
+
+        let back = eval(app(churchnat(position()), real));
+        let real_again = eval(app(realnat(position()), back));
+        assert_eq!(real_again.as_nat(), Some(n));
+    }
+}
+
+#[test]
+fn church_booleans_round_trip() {
+    for &b in [true, false].iter() {
+        let church: Term = church_bool(b, position()).into();
+        let real = eval(app(realbool(position()), church));
+        assert_eq!(real.as_bool(), Some(b));
+
+        let back = eval(app(churchbool(position()), real));
+        assert_eq!(back.to_string(), church_bool(b, position()).to_string());
+    }
+}
+
+#[test]
+fn agrees_with_evaluator_on_pure_terms() {
+    let examples = [
+        r"(\x. \y. x) (\z. z)",
+        r"(\f. \x. f (f x)) (\f. \x. f (f x))",
+        r"(\x. \y. y) ((\x. x x) \x. x x)",
+        r"\y. (\x. \y. x y) y",
+    ];
+    for example in examples.iter() {
+        let unnamed = unnamed::Term::try_from(parse(example).expect("Cannot parse."))
+            .expect("Cannot remove names.");
+        for &strategy in Strategy::ALL.iter() {
+            let mut expected = unnamed.clone();
+            let mut actual = Term::from(unnamed.clone());
+            for _ in 0..20 {
+                match (
+                    eval1_with(&actual, strategy),
+                    evaluator::eval1_with(expected.clone(), strategy),
+                ) {
+                    (Some(next), Some(next_expected)) => {
+                        assert_eq!(next, Term::from(next_expected.clone()));
+                        actual = next;
+                        expected = next_expected;
+                    }
+                    (None, None) => break,
+                    _ => panic!(
+                        "`{}` under {} disagrees on whether to step.",
+                        example,
+                        strategy.name()
+                    ),
+                }
+            }
+        }
+    }
+}
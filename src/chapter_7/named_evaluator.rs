@@ -0,0 +1,228 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{self, Lambda, Strategy};
+use super::term::{
+    named::{Abs, App, Term, Var},
+    unnamed::{self, Context},
+};
+use crate::file_position::Position;
+use std::collections::BTreeSet;
+
+/// The names of the variables that occur free in `term`.
+pub fn free_vars(term: &Term) -> BTreeSet<String> {
+    match term {
+        Term::Var(var) => std::iter::once(var.name.clone()).collect(),
+        Term::Abs(abs) => {
+            let mut vars = free_vars(&abs.body);
+            vars.remove(&abs.param.name);
+            vars
+        }
+        Term::App(app) => {
+            let mut vars = free_vars(&app.callee);
+            vars.extend(free_vars(&app.arg));
+            vars
+        }
+    }
+}
+
+/// The names of every variable in `term`, whether free, bound or a parameter.
+fn names(term: &Term) -> BTreeSet<String> {
+    match term {
+        Term::Var(var) => std::iter::once(var.name.clone()).collect(),
+        Term::Abs(abs) => {
+            let mut vars = names(&abs.body);
+            vars.insert(abs.param.name.clone());
+            vars
+        }
+        Term::App(app) => {
+            let mut vars = names(&app.callee);
+            vars.extend(names(&app.arg));
+            vars
+        }
+    }
+}
+
+/// Adds primes to `name` until it is not in `avoid`,
+/// like `named::Term::unpositioned_from_unnamed` does.
+pub fn fresh_name(name: &str, avoid: &BTreeSet<String>) -> String {
+    let mut fresh = name.to_string();
+    while avoid.contains(&fresh) {
+        fresh.push('\'');
+    }
+    fresh
+}
+
+/// Computes `[name ↦ replacer] term`,
+/// renaming bound variables of `term` where they would capture free variables of `replacer`.
+pub fn subst(term: Term, name: &str, replacer: &Term) -> Term {
+    match term {
+        Term::Var(var) => {
+            if var.name == name {
+                replacer.clone()
+            } else {
+                Term::Var(var)
+            }
+        }
+        Term::Abs(abs) => {
+            let Abs {
+                position,
+                param,
+                body,
+            } = *abs;
+            if param.name == name || !free_vars(&body).contains(name) {
+                return Abs {
+                    position,
+                    param,
+                    body,
+                }
+                .into();
+            }
+
+            let replacer_vars = free_vars(replacer);
+            let (param, body) = if replacer_vars.contains(&param.name) {
+                let mut avoid = replacer_vars;
+                avoid.extend(names(&body));
+                avoid.insert(name.to_string());
+                let fresh = fresh_name(&param.name, &avoid);
+                let body = rename(body, &param.name, &fresh);
+                (
+                    Var {
+                        name: fresh,
+                        ..param
+                    },
+                    body,
+                )
+            } else {
+                (param, body)
+            };
+
+            Abs {
+                position,
+                param,
+                body: subst(body, name, replacer),
+            }
+            .into()
+        }
+        Term::App(app) => App {
+            callee: subst(app.callee, name, replacer),
+            arg: subst(app.arg, name, replacer),
+            ..*app
+        }
+        .into(),
+    }
+}
+
+/// Renames the free occurrences of `from` in `term` to `to`,
+/// keeping their positions. `to` must not occur anywhere in `term`.
+fn rename(term: Term, from: &str, to: &str) -> Term {
+    match term {
+        Term::Var(var) => Term::Var(if var.name == from {
+            Var {
+                name: to.to_string(),
+                ..var
+            }
+        } else {
+            var
+        }),
+        Term::Abs(abs) => {
+            if abs.param.name == from {
+                Term::Abs(abs)
+            } else {
+                Abs {
+                    body: rename(abs.body, from, to),
+                    ..*abs
+                }
+                .into()
+            }
+        }
+        Term::App(app) => App {
+            callee: rename(app.callee, from, to),
+            arg: rename(app.arg, from, to),
+            ..*app
+        }
+        .into(),
+    }
+}
+
+/// Whether `a` and `b` differ only in the names of their bound variables.
+pub fn alpha_equivalent(a: &Term, b: &Term) -> bool {
+    let mut vars = free_vars(a);
+    vars.extend(free_vars(b));
+    let vars: Vec<_> = vars.into_iter().collect();
+    let ctx = Context::from_strings(&vars);
+    match (
+        unnamed::Term::from_named(a.clone(), &ctx),
+        unnamed::Term::from_named(b.clone(), &ctx),
+    ) {
+        (Ok(a), Ok(b)) => a.is_alpha_equivalent(&b),
+        _ => unreachable!("Every free variable is in the context."),
+    }
+}
+
+fn apply(abs: &Abs, arg: &Term) -> Term {
+    subst(abs.body.clone(), &abs.param.name, arg)
+}
+
+impl Lambda for Term {
+    fn is_val(&self) -> bool {
+        Term::is_val(self)
+    }
+
+    fn as_app(&self) -> Option<(&Term, &Term)> {
+        match self {
+            Term::App(app) => Some((&app.callee, &app.arg)),
+            _ => None,
+        }
+    }
+
+    fn with_app(&self, callee: Term, arg: Term) -> Term {
+        App {
+            callee,
+            arg,
+            position: self.position(),
+        }
+        .into()
+    }
+
+    fn apply(&self, arg: &Term) -> Option<Term> {
+        match self {
+            Term::Abs(abs) => Some(apply(abs, arg)),
+            _ => None,
+        }
+    }
+
+    fn step_body(&self, step: fn(&Term) -> Option<Term>) -> Option<Term> {
+        match self {
+            Term::Abs(abs) => step(&abs.body).map(|evaluated_body| {
+                Abs {
+                    body: evaluated_body,
+                    param: abs.param.clone(),
+                    position: abs.position,
+                }
+                .into()
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Takes a single call-by-value step, like `evaluator::eval1`.
+pub fn eval1(term: &Term) -> Option<Term> {
+    eval1_with(term, Strategy::CallByValue)
+}
+
+pub fn eval1_with(term: &Term, strategy: Strategy) -> Option<Term> {
+    evaluator::step_with(term, strategy)
+}
+
+pub type OutOfFuelErr = evaluator::OutOfFuelErr<Term>;
+
+/// Like `evaluator::eval_count_with_fuel`, but on named terms.
+pub fn eval_count_with_fuel(
+    term: Term,
+    strategy: Strategy,
+    fuel: usize,
+) -> Result<(Term, usize), OutOfFuelErr> {
+    evaluator::count_steps_with_fuel(term, fuel, |term| eval1_with(term, strategy))
+}
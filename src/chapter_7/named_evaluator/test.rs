@@ -0,0 +1,181 @@
+use super::{alpha_equivalent, eval1_with, eval_count_with_fuel, free_vars, fresh_name, subst};
+use crate::chapter_7::{
+    evaluator::{self, Strategy},
+    parse,
+    term::{
+        named::{Abs, App, Term, Var},
+        unnamed::{self, Context},
+    },
+    test_util::{position, Random},
+};
+
+fn parsed(src: &str) -> Term {
+    parse(src).expect("Cannot parse.")
+}
+
+fn assert_alpha_equivalent(actual: &Term, expected: &str) {
+    assert!(
+        alpha_equivalent(actual, &parsed(expected)),
+        "Expected `{}` to be alpha-equivalent to `{}`.",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn free_vars_exclude_bound_vars() {
+    let actual: Vec<_> = free_vars(&parsed(r"\x. x y (\y. y z)"))
+        .into_iter()
+        .collect();
+    assert_eq!(actual, vec!["y", "z"]);
+}
+
+#[test]
+fn fresh_names_add_primes() {
+    let avoid = ["x", "x'"].iter().map(ToString::to_string).collect();
+    assert_eq!(fresh_name("x", &avoid), "x''");
+    assert_eq!(fresh_name("y", &avoid), "y");
+}
+
+#[test]
+fn subst_avoids_capture() {
+    let var = parsed("x");
+    let actual = subst(parsed(r"\x. y"), "y", &var);
+    assert_eq!(actual.to_string(), r"(\x'. x)");
+
+    // The fresh name must not clash with names already used in the body.
+    let actual = subst(parsed(r"\x. \x'. y x x'"), "y", &var);
+    assert_eq!(actual.to_string(), r"(\x''. (\x'. ((x x'') x')))");
+}
+
+#[test]
+fn subst_stops_at_shadowing_binders() {
+    let actual = subst(parsed(r"(\x. x) x"), "x", &parsed("y"));
+    assert_eq!(actual.to_string(), r"((\x. x) y)");
+}
+
+#[test]
+fn alpha_equivalence() {
+    assert!(alpha_equivalent(
+        &parsed(r"\x. \y. x y"),
+        &parsed(r"\a. \b. a b")
+    ));
+    assert!(!alpha_equivalent(
+        &parsed(r"\x. \y. x y"),
+        &parsed(r"\a. \b. b a")
+    ));
+    assert!(!alpha_equivalent(&parsed(r"\x. y"), &parsed(r"\x. z")));
+}
+
+#[test]
+fn eval_renames_when_needed() {
+    // Reducing under the abstraction substitutes `y` into a body that binds `y`.
+    let term = parsed(r"\y. (\x. \y. x y) y");
+    let actual = eval1_with(&term, Strategy::NormalOrder).expect("Expected a step.");
+    assert_eq!(actual.to_string(), r"(\y. (\y'. (y y')))");
+}
+
+#[test]
+fn church_numerals() {
+    let two = r"(\f. \x. f (f x))";
+    let src = format!(r"{} {}", two, two);
+    let (actual, _) =
+        eval_count_with_fuel(parsed(&src), Strategy::NormalOrder, 100).expect("Ran out of fuel.");
+    assert_alpha_equivalent(&actual, r"\f. \x. f (f (f (f x)))");
+}
+
+/// Checks that the named and de Bruijn evaluators take the same steps
+/// under every strategy, up to alpha-equivalence, for at most `fuel` steps.
+/// Returns the total number of steps taken.
+fn assert_agrees(term: &Term, fuel: usize) -> usize {
+    let mut steps = 0;
+    let vars: Vec<_> = free_vars(term).into_iter().collect();
+    let ctx = Context::from_strings(&vars);
+    let to_unnamed = |term: &Term| {
+        unnamed::Term::from_named(term.clone(), &ctx)
+            .expect("Every free variable is in the context.")
+    };
+
+    for &strategy in Strategy::ALL.iter() {
+        let mut named = term.clone();
+        let mut nameless = to_unnamed(term);
+        for _ in 0..fuel {
+            match (
+                eval1_with(&named, strategy),
+                evaluator::eval1_with(nameless.clone(), strategy),
+            ) {
+                (Some(next_named), Some(next_nameless)) => {
+                    assert_eq!(
+                        to_unnamed(&next_named).to_string(),
+                        next_nameless.to_string(),
+                        "`{}` stepped differently under {}.",
+                        named,
+                        strategy.name()
+                    );
+                    named = next_named;
+                    nameless = next_nameless;
+                    steps += 1;
+                }
+                (None, None) => break,
+                (named_step, nameless_step) => panic!(
+                    "`{}` under {}: named step {:?}, but nameless step {:?}.",
+                    named,
+                    strategy.name(),
+                    named_step.map(|term| term.to_string()),
+                    nameless_step.map(|term| term.to_string())
+                ),
+            }
+        }
+    }
+    steps
+}
+
+#[test]
+fn agrees_with_de_bruijn_evaluator_on_examples() {
+    let examples = [
+        r"(\x. \y. x) y",
+        r"(\x. \y. \y'. x y y') (y y')",
+        r"(\f. \x. f (f x)) (\f. \x. f (f x))",
+        r"(\x. x x) (\x. \y. x y)",
+        r"(\x. \y. y) ((\x. x x) \x. x x)",
+        r"(\t. \f. t) ((\x. x) \z. z) \w. w",
+        r"\y. (\x. \y. x y) y",
+    ];
+    for example in examples.iter() {
+        assert_agrees(&parsed(example), 50);
+    }
+}
+
+/// Generates a term over a few names, so that shadowing and capture are common.
+fn random_term(random: &mut Random, depth: usize) -> Term {
+    const NAMES: [&str; 3] = ["x", "y", "z"];
+    let var = |random: &mut Random| Var {
+        name: NAMES[random.below(3) as usize].to_string(),
+        position: position(),
+    };
+    match if depth == 0 { 0 } else { random.below(5) } {
+        0 => var(random).into(),
+        1 | 2 => Abs {
+            param: var(random),
+            body: random_term(random, depth - 1),
+            position: position(),
+        }
+        .into(),
+        _ => App {
+            callee: random_term(random, depth - 1),
+            arg: random_term(random, depth - 1),
+            position: position(),
+        }
+        .into(),
+    }
+}
+
+#[test]
+fn agrees_with_de_bruijn_evaluator_on_random_terms() {
+    let mut random = Random(7);
+    let steps: usize = (0..2000)
+        .map(|_| assert_agrees(&random_term(&mut random, 6), 30))
+        .sum();
+    // Make sure the generated terms are not mostly normal forms.
+    assert!(steps > 2000, "Only {} steps were taken.", steps);
+}
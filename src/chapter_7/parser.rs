@@ -1,13 +1,13 @@
-mod lexer;
+pub(crate) mod lexer;
 mod parse_tree;
 
 #[cfg(test)]
 mod test;
 
 pub mod err {
-    pub use super::lexer::TokenizationErr;
+    pub use super::lexer::{PositionedToken, Token, TokenizationErr};
 
-    use super::lexer::{PositionedToken, Token};
+    use std::fmt;
 
     #[derive(Clone, Debug)]
     pub enum ParseErr {
@@ -44,6 +44,10 @@ pub mod err {
         Dot,
         LParen,
         RParen,
+        Equals,
+        Semicolon,
+        Letrec,
+        In,
     }
 
     impl ExpectedToken {
@@ -53,11 +57,7 @@ pub mod err {
             }
 
             fn match_ident(t: &Token) -> bool {
-                if let Token::Ident(_) = t {
-                    true
-                } else {
-                    false
-                }
+                matches!(t, Token::Ident(_))
             }
 
             fn match_lambda(t: &Token) -> bool {
@@ -76,6 +76,22 @@ pub mod err {
                 *t == Token::RParen
             }
 
+            fn match_equals(t: &Token) -> bool {
+                *t == Token::Equals
+            }
+
+            fn match_semicolon(t: &Token) -> bool {
+                *t == Token::Semicolon
+            }
+
+            fn match_letrec(t: &Token) -> bool {
+                *t == Token::Letrec
+            }
+
+            fn match_in(t: &Token) -> bool {
+                *t == Token::In
+            }
+
             match self {
                 ExpectedToken::Eof => return_false,
                 ExpectedToken::Ident => match_ident,
@@ -83,31 +99,104 @@ pub mod err {
                 ExpectedToken::Dot => match_dot,
                 ExpectedToken::LParen => match_lparen,
                 ExpectedToken::RParen => match_rparen,
+                ExpectedToken::Equals => match_equals,
+                ExpectedToken::Semicolon => match_semicolon,
+                ExpectedToken::Letrec => match_letrec,
+                ExpectedToken::In => match_in,
+            }
+        }
+    }
+
+    impl fmt::Display for ParseErr {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ParseErr::Tokenization(err) => write!(f, "{}", err),
+                ParseErr::UnexpectedTokenOrEof(err) => write!(f, "{}", err),
+            }
+        }
+    }
+
+    impl fmt::Display for UnexpectedTokenOrEofErr {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let expected: Vec<String> = self.expected.iter().map(ToString::to_string).collect();
+            match &self.actual {
+                TokenOrEof::Token(actual) => write!(
+                    f,
+                    "{}: Expected {}, but found `{}`.",
+                    actual.position.start,
+                    expected.join(" or "),
+                    actual.token
+                ),
+                TokenOrEof::Eof => write!(
+                    f,
+                    "Expected {}, but found end of input.",
+                    expected.join(" or ")
+                ),
+            }
+        }
+    }
+
+    impl fmt::Display for ExpectedToken {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ExpectedToken::Eof => write!(f, "end of input"),
+                ExpectedToken::Ident => write!(f, "identifier"),
+                ExpectedToken::Lambda => write!(f, "`\\`"),
+                ExpectedToken::Dot => write!(f, "`.`"),
+                ExpectedToken::LParen => write!(f, "`(`"),
+                ExpectedToken::RParen => write!(f, "`)`"),
+                ExpectedToken::Equals => write!(f, "`=`"),
+                ExpectedToken::Semicolon => write!(f, "`;`"),
+                ExpectedToken::Letrec => write!(f, "`letrec`"),
+                ExpectedToken::In => write!(f, "`in`"),
             }
         }
     }
 }
 
+use super::statement::{Def, Statement};
 use super::term::named;
-use crate::file_position::FilePositionRange;
+use crate::file_position::{FilePositionRange, Position};
 use err::*;
-use lexer::{tokenize, PositionedToken, Token};
+use lexer::{tokenize_at, PositionedToken, Token};
 use named::Term as NamedTerm;
 use parse_tree::*;
 
 pub fn parse(src: &str) -> Result<NamedTerm, ParseErr> {
-    match tokenize(src) {
+    parse_at(src, 0)
+}
+
+/// Like `parse`, but numbers the characters of `src` from `index`,
+/// so that terms parsed from different sources have different positions.
+/// Lines and columns are unaffected.
+pub fn parse_at(src: &str, index: usize) -> Result<NamedTerm, ParseErr> {
+    match tokenize_at(src, index) {
         Err(err) => Err(ParseErr::Tokenization(err)),
         Ok(tokens) => TokenParser::from_tokens(&tokens).parse().map(Into::into),
     }
 }
 
+/// Parses a sequence of `;`-separated statements,
+/// each of which is either a definition `name = term` or a term.
+/// The final `;` is optional.
+pub fn parse_statements(src: &str) -> Result<Vec<Statement>, ParseErr> {
+    parse_statements_at(src, 0)
+}
+
+/// Like `parse_statements`, but numbers the characters of `src` from `index`, like `parse_at`.
+pub fn parse_statements_at(src: &str, index: usize) -> Result<Vec<Statement>, ParseErr> {
+    match tokenize_at(src, index) {
+        Err(err) => Err(ParseErr::Tokenization(err)),
+        Ok(tokens) => TokenParser::from_tokens(&tokens).parse_statements(),
+    }
+}
+
 pub struct TokenParser<'a> {
     tokens: &'a [PositionedToken],
 }
 
 impl<'a> TokenParser<'a> {
-    pub fn from_tokens(tokens: &[PositionedToken]) -> TokenParser {
+    pub fn from_tokens(tokens: &[PositionedToken]) -> TokenParser<'_> {
         TokenParser { tokens }
     }
 
@@ -124,8 +213,45 @@ impl<'a> TokenParser<'a> {
         }
     }
 
+    pub fn parse_statements(mut self) -> Result<Vec<Statement>, ParseErr> {
+        let mut statements = vec![];
+        while !self.is_exhausted() {
+            statements.push(self.consume_statement()?);
+            if self.consume_opt_token(ExpectedToken::Semicolon).is_none() && !self.is_exhausted() {
+                return Err(
+                    self.expected_tokens_err(vec![ExpectedToken::Semicolon, ExpectedToken::Eof])
+                );
+            }
+        }
+        Ok(statements)
+    }
+
+    fn consume_statement(&mut self) -> Result<Statement, ParseErr> {
+        let is_def = self.tokens.len() >= 2
+            && self.tokens[0].token.is_ident()
+            && self.tokens[1].token.is_equals();
+        if is_def {
+            let name = self.consume_var()?;
+            self.consume_token(ExpectedToken::Equals)?;
+            let value: NamedTerm = self.consume_term()?.into();
+            let position = FilePositionRange {
+                start: name.position.start,
+                end: value.position().end,
+            };
+            Ok(Statement::Def(Def {
+                position,
+                name: name.into(),
+                value,
+            }))
+        } else {
+            self.consume_term().map(|term| Statement::Eval(term.into()))
+        }
+    }
+
     fn consume_term(&mut self) -> Result<Term, ParseErr> {
-        if let Some(abs_res) = self.consume_opt_abs() {
+        if let Some(letrec_res) = self.consume_opt_letrec() {
+            letrec_res.map(Into::into)
+        } else if let Some(abs_res) = self.consume_opt_abs() {
             abs_res.map(Into::into)
         } else if let Some(callable_res) = self.consume_opt_callable() {
             callable_res.and_then(|callable| {
@@ -145,8 +271,8 @@ impl<'a> TokenParser<'a> {
     }
 
     fn consume_opt_abs(&mut self) -> Option<Result<Abs, ParseErr>> {
-        if let Some(lambda) = self.consume_opt_token(ExpectedToken::Lambda) {
-            Some(match self.consume_var() {
+        self.consume_opt_token(ExpectedToken::Lambda)
+            .map(|lambda| match self.consume_var() {
                 Ok(param) => match self.consume_token(ExpectedToken::Dot) {
                     Ok(_) => match self.consume_term() {
                         Ok(body) => Ok(constructors::build_abs(&lambda, param, body)),
@@ -156,9 +282,20 @@ impl<'a> TokenParser<'a> {
                 },
                 Err(err) => Err(err),
             })
-        } else {
-            None
-        }
+    }
+
+    /// Consumes `letrec name = value in body`,
+    /// where `body` extends as far to the right as possible, like the body of an abstraction.
+    fn consume_opt_letrec(&mut self) -> Option<Result<Letrec, ParseErr>> {
+        self.consume_opt_token(ExpectedToken::Letrec)
+            .map(|keyword| {
+                let name = self.consume_var()?;
+                self.consume_token(ExpectedToken::Equals)?;
+                let value = self.consume_term()?;
+                self.consume_token(ExpectedToken::In)?;
+                let body = self.consume_term()?;
+                Ok(constructors::build_letrec(&keyword, name, value, body))
+            })
     }
 
     fn consume_opt_callable(&mut self) -> Option<Result<Callable, ParseErr>> {
@@ -186,10 +323,9 @@ impl<'a> TokenParser<'a> {
     fn consume_opt_arg(&mut self) -> Option<Result<Arg, ParseErr>> {
         if let Some(var) = self.consume_opt_var() {
             Some(Ok(Arg::Var(var)))
-        } else if let Some(paren_exp_res) = self.consume_opt_paren_exp() {
-            Some(paren_exp_res.map(|inner| Arg::Parenthesized(Box::new(inner))))
         } else {
-            None
+            self.consume_opt_paren_exp()
+                .map(|paren_exp_res| paren_exp_res.map(|inner| Arg::Parenthesized(Box::new(inner))))
         }
     }
 
@@ -219,7 +355,7 @@ impl<'a> TokenParser<'a> {
     }
 
     fn consume_opt_paren_exp(&mut self) -> Option<Result<Term, ParseErr>> {
-        if let Some(_) = self.consume_opt_token(ExpectedToken::LParen) {
+        if self.consume_opt_token(ExpectedToken::LParen).is_some() {
             Some(match self.consume_term() {
                 Ok(term) => match self.consume_token(ExpectedToken::RParen) {
                     Ok(_) => Ok(term),
@@ -272,7 +408,6 @@ impl<'a> TokenParser<'a> {
 
 mod constructors {
     use super::*;
-    use crate::file_position::Position;
 
     pub fn build_abs(lambda: &PositionedToken, param: Var, body: Term) -> Abs {
         let end = body.position().end;
@@ -285,4 +420,18 @@ mod constructors {
             },
         }
     }
+
+    pub fn build_letrec(keyword: &PositionedToken, name: Var, value: Term, body: Term) -> Letrec {
+        let end = body.position().end;
+        Letrec {
+            keyword: keyword.position,
+            name,
+            value,
+            body,
+            position: FilePositionRange {
+                start: keyword.position.start,
+                end,
+            },
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::file_position::{FilePosition, FilePositionRange};
 use matchers::{Match, MATCHERS};
+use std::fmt;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Token {
@@ -8,15 +9,30 @@ pub enum Token {
     Dot,
     LParen,
     RParen,
+    Equals,
+    Semicolon,
+    Letrec,
+    In,
+    Colon,
+    Arrow,
+    Number(u64),
+    LBrace,
+    RBrace,
+    Comma,
+    LAngle,
+    RAngle,
+    Pipe,
+    FatArrow,
+    Plus,
+    LBracket,
+    RBracket,
+    Bang,
+    ColonEquals,
 }
 
 impl Token {
     pub fn is_ident(&self) -> bool {
-        if let Token::Ident(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Token::Ident(_))
     }
 
     pub fn is_lambda(&self) -> bool {
@@ -34,6 +50,41 @@ impl Token {
     pub fn is_r_paren(&self) -> bool {
         *self == Token::RParen
     }
+
+    pub fn is_equals(&self) -> bool {
+        *self == Token::Equals
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "{}", name),
+            Token::Lambda => write!(f, "\\"),
+            Token::Dot => write!(f, "."),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::Equals => write!(f, "="),
+            Token::Semicolon => write!(f, ";"),
+            Token::Letrec => write!(f, "letrec"),
+            Token::In => write!(f, "in"),
+            Token::Colon => write!(f, ":"),
+            Token::Arrow => write!(f, "->"),
+            Token::Number(n) => write!(f, "{}", n),
+            Token::LBrace => write!(f, "{{"),
+            Token::RBrace => write!(f, "}}"),
+            Token::Comma => write!(f, ","),
+            Token::LAngle => write!(f, "<"),
+            Token::RAngle => write!(f, ">"),
+            Token::Pipe => write!(f, "|"),
+            Token::FatArrow => write!(f, "=>"),
+            Token::Plus => write!(f, "+"),
+            Token::LBracket => write!(f, "["),
+            Token::RBracket => write!(f, "]"),
+            Token::Bang => write!(f, "!"),
+            Token::ColonEquals => write!(f, ":="),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -45,12 +96,29 @@ pub struct PositionedToken {
 mod matchers {
     use super::*;
 
-    pub static MATCHERS: [fn(&str) -> Option<Match>; 5] = [
+    pub static MATCHERS: [fn(&str) -> Option<Match>; 22] = [
         match_ident,
         match_lambda,
         match_dot,
         match_lparen,
         match_rparen,
+        match_fat_arrow,
+        match_equals,
+        match_semicolon,
+        match_colon_equals,
+        match_colon,
+        match_arrow,
+        match_number,
+        match_lbrace,
+        match_rbrace,
+        match_comma,
+        match_l_angle,
+        match_r_angle,
+        match_pipe,
+        match_plus,
+        match_l_bracket,
+        match_r_bracket,
+        match_bang,
     ];
 
     #[derive(Clone, PartialEq, Eq, Debug)]
@@ -74,22 +142,21 @@ mod matchers {
         for c in s.chars() {
             let valid = c.is_ascii_alphabetic()
                 || c == '_'
-                || (!name.is_empty() && (c == '\'' || c.is_digit(10)));
+                || (!name.is_empty() && (c == '\'' || c.is_ascii_digit()));
             if valid {
                 name.push(c)
             } else {
                 break;
             }
         }
-        if name.is_empty() {
-            None
-        } else {
-            let len = name.len();
-            Some(Match {
-                token: Token::Ident(name),
-                len,
-            })
-        }
+        let len = name.len();
+        let token = match name.as_str() {
+            "" => return None,
+            "letrec" => Token::Letrec,
+            "in" => Token::In,
+            _ => Token::Ident(name),
+        };
+        Some(Match { token, len })
     }
 
     fn match_lambda(s: &str) -> Option<Match> {
@@ -135,6 +202,193 @@ mod matchers {
             None
         }
     }
+
+    fn match_equals(s: &str) -> Option<Match> {
+        if s.starts_with("=") {
+            Some(Match {
+                token: Token::Equals,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_semicolon(s: &str) -> Option<Match> {
+        if s.starts_with(";") {
+            Some(Match {
+                token: Token::Semicolon,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_colon(s: &str) -> Option<Match> {
+        if s.starts_with(":") {
+            Some(Match {
+                token: Token::Colon,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Must come before `match_colon`.
+    fn match_colon_equals(s: &str) -> Option<Match> {
+        if s.starts_with(":=") {
+            Some(Match {
+                token: Token::ColonEquals,
+                len: 2,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_arrow(s: &str) -> Option<Match> {
+        if s.starts_with("->") {
+            Some(Match {
+                token: Token::Arrow,
+                len: 2,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_number(s: &str) -> Option<Match> {
+        let len = s.chars().take_while(char::is_ascii_digit).count();
+        let n = s[..len].parse().ok()?;
+        Some(Match {
+            token: Token::Number(n),
+            len,
+        })
+    }
+
+    fn match_lbrace(s: &str) -> Option<Match> {
+        if s.starts_with("{") {
+            Some(Match {
+                token: Token::LBrace,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_rbrace(s: &str) -> Option<Match> {
+        if s.starts_with("}") {
+            Some(Match {
+                token: Token::RBrace,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_comma(s: &str) -> Option<Match> {
+        if s.starts_with(",") {
+            Some(Match {
+                token: Token::Comma,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_l_angle(s: &str) -> Option<Match> {
+        if s.starts_with("<") {
+            Some(Match {
+                token: Token::LAngle,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_r_angle(s: &str) -> Option<Match> {
+        if s.starts_with(">") {
+            Some(Match {
+                token: Token::RAngle,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_pipe(s: &str) -> Option<Match> {
+        if s.starts_with("|") {
+            Some(Match {
+                token: Token::Pipe,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Must come before `match_equals`.
+    fn match_fat_arrow(s: &str) -> Option<Match> {
+        if s.starts_with("=>") {
+            Some(Match {
+                token: Token::FatArrow,
+                len: 2,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_plus(s: &str) -> Option<Match> {
+        if s.starts_with("+") {
+            Some(Match {
+                token: Token::Plus,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_l_bracket(s: &str) -> Option<Match> {
+        if s.starts_with("[") {
+            Some(Match {
+                token: Token::LBracket,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_r_bracket(s: &str) -> Option<Match> {
+        if s.starts_with("]") {
+            Some(Match {
+                token: Token::RBracket,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn match_bang(s: &str) -> Option<Match> {
+        if s.starts_with("!") {
+            Some(Match {
+                token: Token::Bang,
+                len: 1,
+            })
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -150,15 +404,27 @@ impl TokenizationErr {
     }
 }
 
-pub fn tokenize(mut src: &str) -> Result<Vec<PositionedToken>, TokenizationErr> {
+impl fmt::Display for TokenizationErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unrecognized = self.0.lines().next().unwrap_or("");
+        write!(f, "Unrecognized input: `{}`.", unrecognized)
+    }
+}
+
+pub fn tokenize(src: &str) -> Result<Vec<PositionedToken>, TokenizationErr> {
+    tokenize_at(src, 0)
+}
+
+/// Like `tokenize`, but numbers the characters of `src` from `index`.
+pub fn tokenize_at(mut src: &str, index: usize) -> Result<Vec<PositionedToken>, TokenizationErr> {
     let mut out = Vec::new();
-    let mut tracker = PositionTracker::new("");
+    let mut tracker = PositionTracker::new(index);
 
     while !src.is_empty() {
         if src.starts_with(char::is_whitespace) {
             tracker.update(&src[0..1]);
             src = &src[1..];
-        } else if let Some(match_) = get_leading_token_match(&src) {
+        } else if let Some(match_) = get_leading_token_match(src) {
             let start = tracker.current_position();
             tracker.update(&src[0..match_.len()]);
             let end = tracker.current_position();
@@ -192,16 +458,14 @@ struct PositionTracker {
 }
 
 impl PositionTracker {
-    fn new(s: &str) -> PositionTracker {
-        let mut tracker = PositionTracker {
+    fn new(index: usize) -> PositionTracker {
+        PositionTracker {
             position: FilePosition {
-                index: 0,
+                index,
                 line: 1,
                 column: 0,
             },
-        };
-        tracker.update(s);
-        tracker
+        }
     }
 
     fn update(&mut self, s: &str) {
@@ -7,6 +7,7 @@ pub enum Term {
     Abs(Box<Abs>),
     Callable(Box<Callable>),
     CallableAbs(Box<(Callable, Abs)>),
+    Letrec(Box<Letrec>),
 }
 
 #[derive(Clone, Debug)]
@@ -16,6 +17,17 @@ pub struct Abs {
     pub position: FilePositionRange,
 }
 
+/// `letrec name = value in body`.
+#[derive(Clone, Debug)]
+pub struct Letrec {
+    /// The position of the `letrec` keyword.
+    pub keyword: FilePositionRange,
+    pub name: Var,
+    pub value: Term,
+    pub body: Term,
+    pub position: FilePositionRange,
+}
+
 #[derive(Clone, Debug)]
 pub struct Var {
     pub name: String,
@@ -46,6 +58,7 @@ impl Position for &Term {
                     end: a.position().end,
                 }
             }
+            Term::Letrec(l) => l.position(),
         }
     }
 }
@@ -68,12 +81,24 @@ impl From<(Callable, Abs)> for Term {
     }
 }
 
+impl From<Letrec> for Term {
+    fn from(letrec: Letrec) -> Term {
+        Term::Letrec(Box::new(letrec))
+    }
+}
+
 impl Position for &Abs {
     fn position(self) -> FilePositionRange {
         self.position
     }
 }
 
+impl Position for &Letrec {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
 impl Position for &Var {
     fn position(self) -> FilePositionRange {
         self.position
@@ -102,10 +127,10 @@ impl Position for &Arg {
     }
 }
 
-impl Into<NamedTerm> for Term {
-    fn into(self) -> NamedTerm {
-        let position = self.position();
-        match self {
+impl From<Term> for NamedTerm {
+    fn from(term: Term) -> NamedTerm {
+        let position = term.position();
+        match term {
             Term::Abs(abs) => (*abs).into(),
             Term::Callable(callable) => (*callable).into(),
             Term::CallableAbs(ca) => {
@@ -117,50 +142,121 @@ impl Into<NamedTerm> for Term {
                 }
                 .into()
             }
+            Term::Letrec(letrec) => (*letrec).into(),
         }
     }
 }
 
-impl Into<named::Abs> for Abs {
-    fn into(self) -> named::Abs {
+/// The call-by-value fixed-point combinator of Section 5.2.
+const Z: &str = r"\f. (\x. f (\y. x x y)) (\x. f (\y. x x y))";
+
+thread_local! {
+    /// `Z`, parsed once per thread rather than on every `letrec`.
+    static Z_TERM: NamedTerm = super::parse(Z).expect("The Z combinator is a term.");
+}
+
+/// Desugars `letrec f = t in b` to `(\f. b) (Z (\f. t))`.
+/// The nodes of `Z` take the position of the `letrec` keyword,
+/// and the other introduced nodes the position of the text they replace,
+/// so `f`, `t` and `b` keep their positions.
+impl From<Letrec> for NamedTerm {
+    fn from(letrec: Letrec) -> NamedTerm {
+        let Letrec {
+            keyword,
+            name,
+            value,
+            body,
+            position,
+        } = letrec;
+        let value_position = FilePositionRange {
+            start: name.position().start,
+            end: value.position().end,
+        };
+        let fix = named::App {
+            position: value_position,
+            callee: Z_TERM.with(|z| reposition(z.clone(), keyword)),
+            arg: named::Abs {
+                position: value_position,
+                param: name.clone().into(),
+                body: value.into(),
+            }
+            .into(),
+        };
+        named::App {
+            position,
+            callee: named::Abs {
+                position,
+                param: name.into(),
+                body: body.into(),
+            }
+            .into(),
+            arg: fix.into(),
+        }
+        .into()
+    }
+}
+
+fn reposition(term: NamedTerm, position: FilePositionRange) -> NamedTerm {
+    match term {
+        NamedTerm::Var(var) => named::Var { position, ..var }.into(),
+        NamedTerm::Abs(abs) => named::Abs {
+            position,
+            param: named::Var {
+                position,
+                ..abs.param
+            },
+            body: reposition(abs.body, position),
+        }
+        .into(),
+        NamedTerm::App(app) => named::App {
+            position,
+            callee: reposition(app.callee, position),
+            arg: reposition(app.arg, position),
+        }
+        .into(),
+    }
+}
+
+impl From<Abs> for named::Abs {
+    fn from(abs: Abs) -> named::Abs {
         named::Abs {
-            position: self.position(),
-            param: self.param.into(),
-            body: self.body.into(),
+            position: abs.position(),
+            param: abs.param.into(),
+            body: abs.body.into(),
         }
     }
 }
 
-impl Into<NamedTerm> for Abs {
-    fn into(self) -> NamedTerm {
-        let named_abs: named::Abs = self.into();
+impl From<Abs> for NamedTerm {
+    fn from(abs: Abs) -> NamedTerm {
+        let named_abs: named::Abs = abs.into();
         named_abs.into()
     }
 }
 
-impl Into<named::Var> for Var {
-    fn into(self) -> named::Var {
+impl From<Var> for named::Var {
+    fn from(var: Var) -> named::Var {
         named::Var {
-            position: self.position(),
-            name: self.name,
+            position: var.position(),
+            name: var.name,
         }
     }
 }
 
-impl Into<NamedTerm> for Var {
-    fn into(self) -> NamedTerm {
-        let named_var: named::Var = self.into();
+impl From<Var> for NamedTerm {
+    fn from(var: Var) -> NamedTerm {
+        let named_var: named::Var = var.into();
         named_var.into()
     }
 }
 
-impl Into<NamedTerm> for Callable {
-    fn into(self) -> NamedTerm {
-        if self.right.is_empty() {
-            self.left.into()
+impl From<Callable> for NamedTerm {
+    fn from(callable: Callable) -> NamedTerm {
+        if callable.right.is_empty() {
+            callable.left.into()
         } else {
-            let mut app: NamedTerm = self.left.into();
-            for arg in self.right {
+            let mut app: NamedTerm = callable.left.into();
+            for arg in callable.right {
                 app = named::App {
                     position: FilePositionRange {
                         start: app.position().start,
@@ -176,9 +272,9 @@ impl Into<NamedTerm> for Callable {
     }
 }
 
-impl Into<NamedTerm> for Arg {
-    fn into(self) -> NamedTerm {
-        match self {
+impl From<Arg> for NamedTerm {
+    fn from(arg: Arg) -> NamedTerm {
+        match arg {
             Arg::Var(var) => var.into(),
             Arg::Parenthesized(inner) => (*inner).into(),
         }
@@ -1,6 +1,12 @@
 mod util;
 
-use super::parse;
+use super::{err::ExpectedToken, lexer::Token};
+use super::{parse, parse_statements};
+use crate::chapter_7::{
+    conversion::beta_eta_equivalent, eval, statement::Statement, term::unnamed, test_util::load,
+};
+use crate::file_position::Position;
+use std::convert::TryFrom;
 use unp::IntoUnpositioned;
 use util::unpositioned as unp;
 
@@ -243,3 +249,134 @@ fn multiple_abs_args_extraneous_parens() {
     ));
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn statements() {
+    let statements = parse_statements(r"id = \x. x; id y").expect("Cannot parse.");
+    assert_eq!(statements.len(), 2);
+    match &statements[0] {
+        Statement::Def(def) => {
+            assert_eq!(def.name.name, "id");
+            assert_eq!(
+                def.value.clone().into_unpositioned(),
+                unp::abs("x", unp::var("x"))
+            );
+        }
+        Statement::Eval(_) => panic!("Expected definition."),
+    }
+    match &statements[1] {
+        Statement::Eval(term) => assert_eq!(
+            term.clone().into_unpositioned(),
+            unp::app(unp::var("id"), unp::var("y"))
+        ),
+        Statement::Def(_) => panic!("Expected term."),
+    }
+}
+
+#[test]
+fn statements_trailing_semicolon() {
+    let statements = parse_statements(r"x; y;").expect("Cannot parse.");
+    assert_eq!(statements.len(), 2);
+}
+
+#[test]
+fn statements_empty() {
+    let statements = parse_statements(r"  ").expect("Cannot parse.");
+    assert!(statements.is_empty());
+}
+
+#[test]
+fn statements_missing_semicolon() {
+    let actual = parse_statements(r"x = y z )")
+        .map(|_| ())
+        .map_err(unp::ParseErr::from);
+    let expected = Err(unp::ParseErr::UnexpectedTokenOrEof(
+        unp::UnexpectedTokenOrEofErr {
+            expected: vec![ExpectedToken::Semicolon, ExpectedToken::Eof],
+            actual: unp::TokenOrEof::Token(Token::RParen),
+        },
+    ));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn equals_is_not_a_term() {
+    let actual = parse(r"x = y").into_unpositioned();
+    let expected: Result<unp::Term, _> = Err(unp::ParseErr::UnexpectedTokenOrEof(
+        unp::UnexpectedTokenOrEofErr {
+            expected: vec![ExpectedToken::Eof],
+            actual: unp::TokenOrEof::Token(Token::Equals),
+        },
+    ));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn err_display() {
+    let err = parse("(x").expect_err("Parsed unbalanced parentheses.");
+    assert_eq!(err.to_string(), "Expected `)`, but found end of input.");
+    let err = parse("x )").expect_err("Parsed unbalanced parentheses.");
+    assert_eq!(
+        err.to_string(),
+        "1:3: Expected end of input, but found `)`."
+    );
+}
+
+#[test]
+fn letrec_desugars_to_z() {
+    let actual = parse(r"letrec f = \n. f n in f").into_unpositioned();
+    let z = parse(r"\f. (\x. f (\y. x x y)) (\x. f (\y. x x y))")
+        .expect("Cannot parse.")
+        .into_unpositioned();
+    let expected: Result<_, unp::ParseErr> = Ok(unp::app(
+        unp::abs("f", unp::var("f")),
+        unp::app(
+            z,
+            unp::abs("f", unp::abs("n", unp::app(unp::var("f"), unp::var("n")))),
+        ),
+    ));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn letrec_keeps_positions() {
+    let src = r"letrec f = \n. g n in f";
+    let term = parse(src).expect("Cannot parse.");
+    assert_eq!(term.position().start.index, 0);
+    assert_eq!(term.position().end.index, src.len());
+    // The free `g` is reported where it was written.
+    let err = unnamed::Term::try_from(term).expect_err("`g` is not defined.");
+    assert_eq!(err.0.name, "g");
+    assert_eq!(err.0.position.start.column, 15);
+}
+
+#[test]
+fn letrec_keywords_are_not_names() {
+    let actual = parse(r"letrec in = x in y").into_unpositioned();
+    let expected: Result<unp::Term, _> = Err(unp::ParseErr::UnexpectedTokenOrEof(
+        unp::UnexpectedTokenOrEofErr {
+            expected: vec![ExpectedToken::Ident],
+            actual: unp::TokenOrEof::Token(Token::In),
+        },
+    ));
+    assert_eq!(actual, expected);
+    let err = parse(r"letrec f = x").expect_err("Missing `in`.");
+    assert_eq!(err.to_string(), "Expected `in`, but found end of input.");
+}
+
+#[test]
+fn letrec_evaluates_with_eval() {
+    let src = r"
+        c1 = \s. \z. s z;
+        plus = \m. \n. \s. \z. m s (n s z);
+        times = \m. \n. m (plus n) c0;
+        factorial = letrec fact = \n. iszro n (\_. c1) (\_. times n (fact (prd n))) c0 in fact;
+        factorial (scc (scc (scc c0)))
+    ";
+    let (_, term) = load(src);
+    let result = eval(term.expect("No term."));
+    let six =
+        unnamed::Term::try_from(parse(r"\s. \z. s (s (s (s (s (s z)))))").expect("Cannot parse."))
+            .expect("Cannot remove names.");
+    assert_eq!(beta_eta_equivalent(&result, &six, 10_000), Ok(true));
+}
@@ -0,0 +1,104 @@
+use super::term::{
+    named::{self, Term as NamedTerm},
+    unnamed::{CannotFindVarInCtxErr, Context, Term},
+};
+use crate::file_position::{FilePositionRange, Position};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Statement {
+    Def(Def),
+    Eval(NamedTerm),
+}
+
+/// A definition `name = value`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Def {
+    pub position: FilePositionRange,
+    pub name: named::Var,
+    pub value: NamedTerm,
+}
+
+impl Position for &Statement {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Statement::Def(def) => def.position(),
+            Statement::Eval(term) => term.position(),
+        }
+    }
+}
+
+impl Position for &Def {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+/// The definitions made so far, along with a naming context
+/// in which terms that refer to them can be made nameless.
+#[derive(Clone, Debug)]
+pub struct Definitions {
+    ctx: Context,
+    /// `values[i]` is the closed value of `ctx.name(ctx.len() - i - 1)`.
+    values: Vec<Term>,
+}
+
+impl Definitions {
+    pub fn empty() -> Definitions {
+        Definitions {
+            ctx: Context::empty(),
+            values: vec![],
+        }
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Adds a definition, shadowing any previous definition with the same name.
+    pub fn define(&mut self, def: Def) -> Result<(), CannotFindVarInCtxErr> {
+        let value = self.resolve(def.value)?;
+        self.ctx = &self.ctx + def.name.name;
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Removes names from `named`, replacing every reference
+    /// to a definition with its value.
+    pub fn resolve(&self, named: NamedTerm) -> Result<Term, CannotFindVarInCtxErr> {
+        Term::from_named(named, &self.ctx).map(|term| self.close(term))
+    }
+
+    /// Replaces every free variable of `term` (which must be nameless
+    /// in this context) with the value of the definition it refers to.
+    pub fn close(&self, mut term: Term) -> Term {
+        for value in self.values.iter().rev() {
+            term = term.subst(0, &value.clone().shift(1)).shift(-1);
+        }
+        term
+    }
+
+    /// Returns the value of the most recent definition of `name`.
+    pub fn get(&self, name: &str) -> Option<&Term> {
+        self.ctx
+            .index(name)
+            .map(|index| &self.values[self.len() - index - 1])
+    }
+
+    /// Returns the definitions in the order they were made,
+    /// including shadowed ones.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Term)> {
+        let len = self.len();
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (self.ctx.name(len - i - 1).unwrap_or(""), value))
+    }
+}
@@ -1,2 +1,6 @@
+pub mod levels;
+pub mod locally_nameless;
+pub mod mixed;
 pub mod named;
+pub mod path;
 pub mod unnamed;
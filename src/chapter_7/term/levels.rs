@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod test;
+
+pub use super::locally_nameless::Bound;
+use super::{
+    locally_nameless::{self, CannotFindIndexInCtxErr, FreeVar},
+    named::{self, Term as NamedTerm},
+    unnamed::{self, CannotFindVarInCtxErr, Context},
+};
+use crate::file_position::FilePositionRange;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A term in which bound variables are de Bruijn indices
+/// and free variables are de Bruijn levels.
+///
+/// A level counts binders from the outermost end of the context,
+/// so the first name added to a `Context` has level `0`.
+/// Unlike an index, a level does not change when a term is moved under a binder,
+/// so substituting a locally closed term never needs shifting,
+/// and a beta step is a single traversal of the body.
+pub type Term = locally_nameless::Term<Free>;
+
+pub type Abs = locally_nameless::Abs<Free>;
+
+pub type App = locally_nameless::App<Free>;
+
+/// A variable of the context, identified by its level.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Free {
+    pub position: FilePositionRange,
+    pub level: usize,
+}
+
+impl FreeVar for Free {
+    type Id = usize;
+
+    fn new(position: FilePositionRange, level: usize) -> Free {
+        Free { position, level }
+    }
+
+    fn id(&self) -> &usize {
+        &self.level
+    }
+
+    fn position(&self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl fmt::Display for Free {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.level)
+    }
+}
+
+impl From<Free> for Term {
+    fn from(free: Free) -> Term {
+        Term::Free(free)
+    }
+}
+
+impl Term {
+    /// The smallest level greater than that of every free variable in `self`.
+    pub fn next_level(&self) -> usize {
+        match self {
+            Term::Bound(_) => 0,
+            Term::Free(free) => free.level + 1,
+            Term::Abs(abs) => abs.body.next_level(),
+            Term::App(app) => app.callee.next_level().max(app.arg.next_level()),
+        }
+    }
+}
+
+/// Converts each variable using its own `context_length`,
+/// so a variable that refers past its enclosing abstractions becomes a level.
+/// Fails if a variable refers past its context as well.
+impl TryFrom<unnamed::Term> for Term {
+    type Error = CannotFindIndexInCtxErr;
+
+    fn try_from(unnamed: unnamed::Term) -> Result<Term, CannotFindIndexInCtxErr> {
+        fn convert(unnamed: unnamed::Term, depth: usize) -> Result<Term, CannotFindIndexInCtxErr> {
+            match unnamed {
+                unnamed::Term::Var(var) => {
+                    if var.index < depth {
+                        Ok(Bound {
+                            position: var.position,
+                            index: var.index,
+                        }
+                        .into())
+                    } else {
+                        match var
+                            .context_length
+                            .checked_sub(var.index)
+                            .and_then(|length| length.checked_sub(1))
+                        {
+                            Some(level) => Ok(Free {
+                                position: var.position,
+                                level,
+                            }
+                            .into()),
+                            None => Err(CannotFindIndexInCtxErr(var)),
+                        }
+                    }
+                }
+                unnamed::Term::Abs(abs) => {
+                    let unnamed::Abs {
+                        position,
+                        param_name,
+                        body,
+                    } = *abs;
+                    Ok(Abs {
+                        position,
+                        param_name,
+                        body: convert(body, depth + 1)?,
+                    }
+                    .into())
+                }
+                unnamed::Term::App(app) => {
+                    let unnamed::App {
+                        position,
+                        callee,
+                        arg,
+                    } = *app;
+                    Ok(App {
+                        position,
+                        callee: convert(callee, depth)?,
+                        arg: convert(arg, depth)?,
+                    }
+                    .into())
+                }
+            }
+        }
+
+        convert(unnamed, 0)
+    }
+}
+
+/// A free variable whose level is not less than the length of the context.
+#[derive(Debug, Clone)]
+pub struct CannotFindLevelInCtxErr(pub Free);
+
+impl fmt::Display for CannotFindLevelInCtxErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: Cannot find level {} in context.",
+            self.0.position.start, self.0.level
+        )
+    }
+}
+
+impl Term {
+    pub fn from_named(named: NamedTerm, ctx: &Context) -> Result<Term, CannotFindVarInCtxErr> {
+        unnamed::Term::from_named(named, ctx)
+            .map(|unnamed| Term::try_from(unnamed).expect("Every variable is within its context."))
+    }
+
+    /// Converts to a nameless term in a context of length `context_length`.
+    pub fn into_unnamed(
+        self,
+        context_length: usize,
+    ) -> Result<unnamed::Term, CannotFindLevelInCtxErr> {
+        fn convert(
+            term: Term,
+            context_length: usize,
+            depth: usize,
+        ) -> Result<unnamed::Term, CannotFindLevelInCtxErr> {
+            match term {
+                Term::Bound(bound) => Ok(unnamed::Term::Var(unnamed::Var {
+                    position: bound.position,
+                    index: bound.index,
+                    context_length: context_length + depth,
+                })),
+                Term::Free(free) => {
+                    if free.level < context_length {
+                        Ok(unnamed::Term::Var(unnamed::Var {
+                            position: free.position,
+                            index: context_length - 1 - free.level + depth,
+                            context_length: context_length + depth,
+                        }))
+                    } else {
+                        Err(CannotFindLevelInCtxErr(free))
+                    }
+                }
+                Term::Abs(abs) => Ok(unnamed::Abs {
+                    position: abs.position,
+                    param_name: abs.param_name,
+                    body: convert(abs.body, context_length, depth + 1)?,
+                }
+                .into()),
+                Term::App(app) => Ok(unnamed::App {
+                    position: app.position,
+                    callee: convert(app.callee, context_length, depth)?,
+                    arg: convert(app.arg, context_length, depth)?,
+                }
+                .into()),
+            }
+        }
+
+        convert(self, context_length, 0)
+    }
+
+    /// Like `unnamed::Term::into_unpositioned_named`, but with the free variables named by `ctx`.
+    pub fn into_unpositioned_named(
+        self,
+        ctx: &Context,
+    ) -> Result<NamedTerm, CannotFindLevelInCtxErr> {
+        self.into_unnamed(ctx.len())
+            .map(|unnamed| named::Term::unpositioned_from_unnamed(unnamed, ctx))
+    }
+}
@@ -0,0 +1,130 @@
+use super::{Abs, Free, Term};
+use crate::chapter_7::{
+    parse,
+    term::{
+        named,
+        unnamed::{self, Context},
+    },
+};
+use crate::file_position::Position;
+use std::convert::TryFrom;
+
+fn unnamed(src: &str, ctx: &Context) -> unnamed::Term {
+    unnamed::Term::from_named(parse(src).expect("Cannot parse."), ctx)
+        .expect("Cannot remove names.")
+}
+
+#[test]
+fn bound_vars_become_indices_and_free_vars_levels() {
+    let ctx = Context::from_strs(&["w", "v"]);
+    let term = Term::try_from(unnamed(r"\x. \y. x y v w", &ctx)).expect("Cannot convert.");
+    assert_eq!(term.to_string(), r"(\. (\. (((1 0) #1) #0)))");
+    assert!(term.is_locally_closed());
+    assert_eq!(term.next_level(), 2);
+}
+
+#[test]
+fn levels_do_not_depend_on_depth() {
+    let ctx = Context::from_strs(&["w"]);
+    let term = Term::try_from(unnamed(r"w (\x. w (\y. w))", &ctx)).expect("Cannot convert.");
+    assert_eq!(term.to_string(), r"(#0 (\. (#0 (\. #0))))");
+}
+
+#[test]
+fn open_does_not_shift() {
+    let ctx = Context::from_strs(&["w"]);
+    let abs = match Term::try_from(unnamed(r"\x. \y. x y", &ctx)).expect("Cannot convert.") {
+        Term::Abs(abs) => *abs,
+        other => panic!("Expected an abstraction, but found {}.", other),
+    };
+    let arg = Term::try_from(unnamed(r"\z. z w", &ctx)).expect("Cannot convert.");
+    assert_eq!(abs.open(&arg).to_string(), r"(\. ((\. (0 #0)) 0))");
+}
+
+#[test]
+fn open_and_close_are_inverses() {
+    let abs = match Term::try_from(unnamed(r"\x. \y. x y", &Context::empty()))
+        .expect("Cannot convert.")
+    {
+        Term::Abs(abs) => *abs,
+        other => panic!("Expected an abstraction, but found {}.", other),
+    };
+    let opened = abs.body.clone().open_free_at(0, 3);
+    assert_eq!(opened.to_string(), r"(\. (#3 0))");
+    assert_eq!(opened.close_at(0, &3), abs.body);
+
+    let free = Free {
+        position: (&abs).position(),
+        level: 0,
+    };
+    assert_eq!(
+        Abs {
+            body: Term::Bound(super::Bound {
+                position: free.position,
+                index: 0,
+            }),
+            ..abs
+        }
+        .open(&free.clone().into()),
+        Term::Free(free)
+    );
+}
+
+#[test]
+fn unnamed_round_trip() {
+    let ctx = Context::from_strs(&["w", "v"]);
+    let examples = [
+        r"\x. x v (\y. w x y)",
+        r"w v",
+        r"(\x. \y. \z. z y x w) \x. x",
+        r"\w. w v",
+    ];
+    for example in examples.iter() {
+        let unnamed = unnamed(example, &ctx);
+        let term = Term::try_from(unnamed.clone()).expect("Cannot convert.");
+        assert_eq!(
+            term.clone()
+                .into_unnamed(ctx.len())
+                .expect("Cannot convert."),
+            unnamed
+        );
+        assert_eq!(
+            term.into_unpositioned_named(&ctx).expect("Cannot convert."),
+            named::Term::unpositioned_from_unnamed(unnamed, &ctx)
+        );
+    }
+}
+
+#[test]
+fn from_named_uses_the_context() {
+    let ctx = Context::from_strs(&["w", "v"]);
+    let named = parse(r"\x. x w").expect("Cannot parse.");
+    let term = Term::from_named(named, &ctx).expect("Cannot remove names.");
+    assert_eq!(term.to_string(), r"(\. (0 #0))");
+    assert!(Term::from_named(parse("u").expect("Cannot parse."), &ctx).is_err());
+}
+
+#[test]
+fn into_unnamed_rejects_levels_outside_the_context() {
+    let ctx = Context::from_strs(&["w", "v"]);
+    let term = Term::try_from(unnamed(r"\x. x v", &ctx)).expect("Cannot convert.");
+    let err = term
+        .into_unnamed(1)
+        .expect_err("Level 1 is not in a context of length 1.");
+    assert_eq!(err.0.level, 1);
+    assert!(err.to_string().ends_with("Cannot find level 1 in context."));
+}
+
+#[test]
+fn conversion_rejects_indices_outside_the_context() {
+    let ctx = Context::from_strs(&["w"]);
+    let mut var = match unnamed("w", &ctx) {
+        unnamed::Term::Var(var) => var,
+        term => panic!("Expected a variable, but found `{}`.", term),
+    };
+    var.context_length = 0;
+    let err = Term::try_from(unnamed::Term::Var(var))
+        .expect_err("Index 0 is not in a context of length 0.");
+    assert_eq!(err.0.index, 0);
+    assert!(err.to_string().ends_with("Cannot find index 0 in context."));
+}
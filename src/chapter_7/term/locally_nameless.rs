@@ -0,0 +1,517 @@
+#[cfg(test)]
+mod test;
+
+use super::{
+    named::{self, Term as NamedTerm},
+    unnamed::{self, CannotFindVarInCtxErr, Context},
+};
+use crate::file_position::{FilePositionRange, Position};
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A term in which bound variables are de Bruijn indices
+/// and free variables are `F`s: names by default, and de Bruijn levels in `levels::Term`.
+///
+/// Since free variables are never indices, substituting a locally closed term
+/// never needs shifting: `open_at` replaces an index with a term, and `close_at`
+/// replaces a free variable with an index.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Term<F = Free> {
+    Bound(Bound),
+    Free(F),
+    Abs(Box<Abs<F>>),
+    App(Box<App<F>>),
+}
+
+/// A variable bound by an enclosing abstraction, identified by its index.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Bound {
+    pub position: FilePositionRange,
+    pub index: usize,
+}
+
+/// A free variable.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Free {
+    pub position: FilePositionRange,
+    pub name: String,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Abs<F = Free> {
+    pub position: FilePositionRange,
+    /// The name the parameter had in the source, used when converting back to named terms.
+    pub param_name: String,
+    pub body: Term<F>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct App<F = Free> {
+    pub position: FilePositionRange,
+    pub callee: Term<F>,
+    pub arg: Term<F>,
+}
+
+/// The free variables of a locally nameless term.
+pub trait FreeVar: Clone + fmt::Display {
+    /// What tells free variables apart, regardless of their positions.
+    type Id: Clone + PartialEq;
+
+    fn new(position: FilePositionRange, id: Self::Id) -> Self;
+
+    fn id(&self) -> &Self::Id;
+
+    fn position(&self) -> FilePositionRange;
+}
+
+impl FreeVar for Free {
+    type Id = String;
+
+    fn new(position: FilePositionRange, name: String) -> Free {
+        Free { position, name }
+    }
+
+    fn id(&self) -> &String {
+        &self.name
+    }
+
+    fn position(&self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl fmt::Display for Free {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<F: FreeVar> Term<F> {
+    pub fn is_val(&self) -> bool {
+        self.is_abs()
+    }
+
+    pub fn is_abs(&self) -> bool {
+        matches!(self, Term::Abs(_))
+    }
+
+    pub fn is_app(&self) -> bool {
+        matches!(self, Term::App(_))
+    }
+
+    /// Replaces the bound variable with index `k` (counting from the outside of `self`)
+    /// with `replacer`, which must be locally closed.
+    pub fn open_at(self, k: usize, replacer: &Term<F>) -> Term<F> {
+        self.replace_bound(k, &|_| replacer.clone())
+    }
+
+    /// Replaces the bound variable with index `k` with the free variable `id`,
+    /// keeping the position of each occurrence.
+    pub fn open_free_at(self, k: usize, id: F::Id) -> Term<F> {
+        self.replace_bound(k, &|bound| Term::Free(F::new(bound.position, id.clone())))
+    }
+
+    fn replace_bound(self, k: usize, replacer: &dyn Fn(&Bound) -> Term<F>) -> Term<F> {
+        match self {
+            Term::Bound(bound) => {
+                if bound.index == k {
+                    replacer(&bound)
+                } else {
+                    Term::Bound(bound)
+                }
+            }
+            Term::Free(free) => Term::Free(free),
+            Term::Abs(abs) => Term::Abs(Box::new(Abs {
+                body: abs.body.replace_bound(k + 1, replacer),
+                ..*abs
+            })),
+            Term::App(app) => Term::App(Box::new(App {
+                callee: app.callee.replace_bound(k, replacer),
+                arg: app.arg.replace_bound(k, replacer),
+                ..*app
+            })),
+        }
+    }
+
+    /// Replaces the free variable `id` with the bound variable with index `k`
+    /// (counting from the outside of `self`).
+    pub fn close_at(self, k: usize, id: &F::Id) -> Term<F> {
+        match self {
+            Term::Bound(bound) => Term::Bound(bound),
+            Term::Free(free) => {
+                if free.id() == id {
+                    Term::Bound(Bound {
+                        position: free.position(),
+                        index: k,
+                    })
+                } else {
+                    Term::Free(free)
+                }
+            }
+            Term::Abs(abs) => Term::Abs(Box::new(Abs {
+                body: abs.body.close_at(k + 1, id),
+                ..*abs
+            })),
+            Term::App(app) => Term::App(Box::new(App {
+                callee: app.callee.close_at(k, id),
+                arg: app.arg.close_at(k, id),
+                ..*app
+            })),
+        }
+    }
+
+    /// Whether every bound variable refers to an enclosing abstraction.
+    pub fn is_locally_closed(&self) -> bool {
+        fn is_closed_at<F>(term: &Term<F>, depth: usize) -> bool {
+            match term {
+                Term::Bound(bound) => bound.index < depth,
+                Term::Free(_) => true,
+                Term::Abs(abs) => is_closed_at(&abs.body, depth + 1),
+                Term::App(app) => is_closed_at(&app.callee, depth) && is_closed_at(&app.arg, depth),
+            }
+        }
+
+        is_closed_at(self, 0)
+    }
+}
+
+impl Term {
+    pub fn free_vars(&self) -> BTreeSet<String> {
+        match self {
+            Term::Bound(_) => BTreeSet::new(),
+            Term::Free(free) => std::iter::once(free.name.clone()).collect(),
+            Term::Abs(abs) => abs.body.free_vars(),
+            Term::App(app) => {
+                let mut vars = app.callee.free_vars();
+                vars.extend(app.arg.free_vars());
+                vars
+            }
+        }
+    }
+}
+
+impl<F: FreeVar> Abs<F> {
+    /// Returns the body with the parameter replaced by `arg`,
+    /// which must be locally closed. Nothing is shifted.
+    pub fn open(self, arg: &Term<F>) -> Term<F> {
+        self.body.open_at(0, arg)
+    }
+}
+
+impl Abs {
+    /// Creates an abstraction whose parameter is the free variable `param_name` of `body`.
+    pub fn close(position: FilePositionRange, param_name: String, body: Term) -> Abs {
+        let body = body.close_at(0, &param_name);
+        Abs {
+            position,
+            param_name,
+            body,
+        }
+    }
+
+    /// Returns the body with the parameter replaced by the free variable `name`.
+    pub fn open_var(self, name: &str) -> Term {
+        self.body.open_free_at(0, name.to_string())
+    }
+}
+
+impl<F> From<Bound> for Term<F> {
+    fn from(bound: Bound) -> Term<F> {
+        Term::Bound(bound)
+    }
+}
+
+impl From<Free> for Term {
+    fn from(free: Free) -> Term {
+        Term::Free(free)
+    }
+}
+
+impl<F> From<Abs<F>> for Term<F> {
+    fn from(abs: Abs<F>) -> Term<F> {
+        Term::Abs(Box::new(abs))
+    }
+}
+
+impl<F> From<App<F>> for Term<F> {
+    fn from(app: App<F>) -> Term<F> {
+        Term::App(Box::new(app))
+    }
+}
+
+impl<F: FreeVar> Position for &Term<F> {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Term::Bound(bound) => bound.position,
+            Term::Free(free) => free.position(),
+            Term::Abs(abs) => abs.position,
+            Term::App(app) => app.position,
+        }
+    }
+}
+
+impl Position for &Bound {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl<F> Position for &Abs<F> {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+impl<F> Position for &App<F> {
+    fn position(self) -> FilePositionRange {
+        self.position
+    }
+}
+
+/// Variables bound in the named term become indices, and free variables keep their names.
+impl From<NamedTerm> for Term {
+    fn from(named: NamedTerm) -> Term {
+        fn convert(named: NamedTerm, binders: &mut Vec<String>) -> Term {
+            match named {
+                NamedTerm::Var(var) => {
+                    match binders.iter().rev().position(|name| *name == var.name) {
+                        Some(index) => Bound {
+                            position: var.position,
+                            index,
+                        }
+                        .into(),
+                        None => Free {
+                            position: var.position,
+                            name: var.name,
+                        }
+                        .into(),
+                    }
+                }
+                NamedTerm::Abs(abs) => {
+                    let named::Abs {
+                        position,
+                        param,
+                        body,
+                    } = *abs;
+                    binders.push(param.name);
+                    let body = convert(body, binders);
+                    let param_name = binders.pop().expect("The parameter was just pushed.");
+                    Abs {
+                        position,
+                        param_name,
+                        body,
+                    }
+                    .into()
+                }
+                NamedTerm::App(app) => {
+                    let named::App {
+                        position,
+                        callee,
+                        arg,
+                    } = *app;
+                    App {
+                        position,
+                        callee: convert(callee, binders),
+                        arg: convert(arg, binders),
+                    }
+                    .into()
+                }
+            }
+        }
+
+        convert(named, &mut vec![])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CannotFindIndexInCtxErr(pub unnamed::Var);
+
+impl fmt::Display for CannotFindIndexInCtxErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: Cannot find index {} in context.",
+            self.0.position.start, self.0.index
+        )
+    }
+}
+
+impl TryFrom<unnamed::Term> for Term {
+    type Error = CannotFindIndexInCtxErr;
+
+    fn try_from(unnamed: unnamed::Term) -> Result<Term, CannotFindIndexInCtxErr> {
+        Term::from_unnamed(unnamed, &Context::empty())
+    }
+}
+
+impl Term {
+    /// Converts a nameless term whose free variables are named by `ctx`.
+    pub fn from_unnamed(
+        unnamed: unnamed::Term,
+        ctx: &Context,
+    ) -> Result<Term, CannotFindIndexInCtxErr> {
+        fn convert(
+            unnamed: unnamed::Term,
+            ctx: &Context,
+            depth: usize,
+        ) -> Result<Term, CannotFindIndexInCtxErr> {
+            match unnamed {
+                unnamed::Term::Var(var) => {
+                    if var.index < depth {
+                        Ok(Bound {
+                            position: var.position,
+                            index: var.index,
+                        }
+                        .into())
+                    } else {
+                        match ctx.name(var.index - depth) {
+                            Some(name) => Ok(Free {
+                                position: var.position,
+                                name: name.to_string(),
+                            }
+                            .into()),
+                            None => Err(CannotFindIndexInCtxErr(var)),
+                        }
+                    }
+                }
+                unnamed::Term::Abs(abs) => {
+                    let unnamed::Abs {
+                        position,
+                        param_name,
+                        body,
+                    } = *abs;
+                    Ok(Abs {
+                        position,
+                        param_name,
+                        body: convert(body, ctx, depth + 1)?,
+                    }
+                    .into())
+                }
+                unnamed::Term::App(app) => {
+                    let unnamed::App {
+                        position,
+                        callee,
+                        arg,
+                    } = *app;
+                    Ok(App {
+                        position,
+                        callee: convert(callee, ctx, depth)?,
+                        arg: convert(arg, ctx, depth)?,
+                    }
+                    .into())
+                }
+            }
+        }
+
+        convert(unnamed, ctx, 0)
+    }
+
+    /// Converts to a nameless term, looking up free variables in `ctx`.
+    pub fn into_unnamed(self, ctx: &Context) -> Result<unnamed::Term, CannotFindVarInCtxErr> {
+        fn convert(
+            term: Term,
+            ctx: &Context,
+            depth: usize,
+        ) -> Result<unnamed::Term, CannotFindVarInCtxErr> {
+            let context_length = ctx.len() + depth;
+            match term {
+                Term::Bound(bound) => Ok(unnamed::Term::Var(unnamed::Var {
+                    position: bound.position,
+                    index: bound.index,
+                    context_length,
+                })),
+                Term::Free(free) => match ctx.index(&free.name) {
+                    Some(index) => Ok(unnamed::Term::Var(unnamed::Var {
+                        position: free.position,
+                        index: index + depth,
+                        context_length,
+                    })),
+                    None => Err(CannotFindVarInCtxErr(named::Var {
+                        position: free.position,
+                        name: free.name,
+                    })),
+                },
+                Term::Abs(abs) => Ok(unnamed::Abs {
+                    position: abs.position,
+                    param_name: abs.param_name,
+                    body: convert(abs.body, ctx, depth + 1)?,
+                }
+                .into()),
+                Term::App(app) => Ok(unnamed::App {
+                    position: app.position,
+                    callee: convert(app.callee, ctx, depth)?,
+                    arg: convert(app.arg, ctx, depth)?,
+                }
+                .into()),
+            }
+        }
+
+        convert(self, ctx, 0)
+    }
+
+    /// Converts to a named term, adding primes to parameter names
+    /// where they would capture a free variable or shadow an enclosing parameter.
+    /// Each parameter takes the position of its abstraction.
+    ///
+    /// Panics if the term is not locally closed.
+    pub fn into_named(self) -> NamedTerm {
+        fn convert(
+            term: Term,
+            free_vars: &BTreeSet<String>,
+            binders: &mut Vec<String>,
+        ) -> NamedTerm {
+            match term {
+                Term::Bound(bound) => named::Var {
+                    position: bound.position,
+                    name: binders[binders.len() - bound.index - 1].clone(),
+                }
+                .into(),
+                Term::Free(free) => named::Var {
+                    position: free.position,
+                    name: free.name,
+                }
+                .into(),
+                Term::Abs(abs) => {
+                    let Abs {
+                        position,
+                        mut param_name,
+                        body,
+                    } = *abs;
+                    while free_vars.contains(&param_name) || binders.contains(&param_name) {
+                        param_name.push('\'');
+                    }
+                    binders.push(param_name);
+                    let body = convert(body, free_vars, binders);
+                    let name = binders.pop().expect("The parameter was just pushed.");
+                    named::Abs {
+                        position,
+                        param: named::Var { position, name },
+                        body,
+                    }
+                    .into()
+                }
+                Term::App(app) => named::App {
+                    position: app.position,
+                    callee: convert(app.callee, free_vars, binders),
+                    arg: convert(app.arg, free_vars, binders),
+                }
+                .into(),
+            }
+        }
+
+        let free_vars = self.free_vars();
+        convert(self, &free_vars, &mut vec![])
+    }
+}
+
+impl<F: FreeVar> fmt::Display for Term<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Bound(bound) => write!(f, "{}", bound.index),
+            Term::Free(free) => write!(f, "{}", free),
+            Term::Abs(abs) => write!(f, "(\\. {})", abs.body),
+            Term::App(app) => write!(f, "({} {})", app.callee, app.arg),
+        }
+    }
+}
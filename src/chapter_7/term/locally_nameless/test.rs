@@ -0,0 +1,88 @@
+use super::{Abs, Free, Term};
+use crate::chapter_7::{
+    named_evaluator::alpha_equivalent,
+    parse,
+    term::unnamed::{self, Context},
+};
+use crate::file_position::Position;
+use std::convert::TryFrom;
+
+fn locally_nameless(src: &str) -> Term {
+    Term::from(parse(src).expect("Cannot parse."))
+}
+
+#[test]
+fn bound_vars_become_indices_and_free_vars_keep_names() {
+    let term = locally_nameless(r"\x. \y. x y z");
+    assert_eq!(term.to_string(), r"(\. (\. ((1 0) z)))");
+    assert!(term.is_locally_closed());
+    assert_eq!(term.free_vars().into_iter().collect::<Vec<_>>(), vec!["z"]);
+}
+
+#[test]
+fn open_and_close_are_inverses() {
+    let abs = match locally_nameless(r"\x. \y. x y") {
+        Term::Abs(abs) => *abs,
+        other => panic!("Expected an abstraction, but found {}.", other),
+    };
+    let position = (&abs).position();
+    let opened = abs.clone().open_var("a");
+    assert_eq!(opened.to_string(), r"(\. (a 0))");
+    let closed = Abs::close(position, "a".to_string(), opened);
+    assert_eq!(closed.body, abs.body);
+}
+
+#[test]
+fn opening_with_a_term_replaces_the_var() {
+    let abs = match locally_nameless(r"\x. x") {
+        Term::Abs(abs) => *abs,
+        other => panic!("Expected an abstraction, but found {}.", other),
+    };
+    let free = Free {
+        position: (&abs).position(),
+        name: "a".to_string(),
+    };
+    assert_eq!(abs.open(&free.clone().into()), Term::Free(free));
+}
+
+#[test]
+fn opening_does_not_shift() {
+    let abs = match locally_nameless(r"\x. \y. x") {
+        Term::Abs(abs) => *abs,
+        other => panic!("Expected an abstraction, but found {}.", other),
+    };
+    let replacer = locally_nameless(r"\z. z w");
+    assert_eq!(abs.open(&replacer).to_string(), r"(\. (\. (0 w)))");
+}
+
+#[test]
+fn unnamed_round_trip() {
+    let ctx = Context::from_strs(&["w", "v"]);
+    let named = parse(r"\x. x v (\y. w x y)").expect("Cannot parse.");
+    let unnamed = unnamed::Term::from_named(named.clone(), &ctx).expect("Cannot remove names.");
+
+    let term = Term::from_unnamed(unnamed.clone(), &ctx).expect("Cannot convert.");
+    assert_eq!(term, Term::from(named));
+    assert_eq!(term.into_unnamed(&ctx).expect("Cannot convert."), unnamed);
+
+    assert!(<Term>::try_from(unnamed).is_err());
+}
+
+#[test]
+fn into_named_avoids_capture() {
+    // The parameter `y` would capture the free `y` if it kept its name.
+    let term = locally_nameless(r"\y. \x. y");
+    let term = match term {
+        Term::Abs(abs) => abs.open(&locally_nameless("y")),
+        other => panic!("Expected an abstraction, but found {}.", other),
+    };
+    let named = term.into_named();
+    assert_eq!(named.to_string(), r"(\x. y)");
+
+    let named = locally_nameless(r"\x. (\x. x) y").into_named();
+    assert!(alpha_equivalent(
+        &named,
+        &parse(r"\a. (\b. b) y").expect("Cannot parse.")
+    ));
+    assert_eq!(named.to_string(), r"(\x. ((\x'. x') y))");
+}
@@ -0,0 +1,510 @@
+#[cfg(test)]
+mod test;
+
+use super::{
+    named::{self, Term as NamedTerm},
+    unnamed::{self, CannotFindVarInCtxErr, Context, Var},
+};
+use crate::file_position::{FilePositionRange, Position};
+use std::fmt;
+
+/// A nameless term that may also contain native numbers and booleans,
+/// like the mixed language of Section 5.3.
+///
+/// Every `unnamed::Term` is also a term of this language.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Term {
+    Var(Var),
+    Abs(Box<Abs>),
+    App(Box<App>),
+    Nat(Nat),
+    Bool(Bool),
+    If(Box<If>),
+    Prim(Prim),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Abs {
+    pub position: FilePositionRange,
+    pub param_name: String,
+    pub body: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct App {
+    pub position: FilePositionRange,
+    pub callee: Term,
+    pub arg: Term,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Nat {
+    pub position: FilePositionRange,
+    pub value: u64,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Bool {
+    pub position: FilePositionRange,
+    pub value: bool,
+}
+
+/// `if guard then then else else_`. Only the chosen branch is evaluated.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct If {
+    pub position: FilePositionRange,
+    pub guard: Term,
+    pub then: Term,
+    pub else_: Term,
+}
+
+/// A primitive function on numbers, which is applied once all its arguments are numbers.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Prim {
+    pub position: FilePositionRange,
+    pub op: Op,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Op {
+    Succ,
+    Pred,
+    IsZero,
+    Plus,
+}
+
+impl Op {
+    pub const ALL: [Op; 4] = [Op::Succ, Op::Pred, Op::IsZero, Op::Plus];
+
+    pub fn arity(self) -> usize {
+        match self {
+            Op::Succ | Op::Pred | Op::IsZero => 1,
+            Op::Plus => 2,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Succ => "succ",
+            Op::Pred => "pred",
+            Op::IsZero => "iszero",
+            Op::Plus => "plus",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Op> {
+        Op::ALL.iter().copied().find(|op| op.name() == name)
+    }
+}
+
+impl Term {
+    pub fn shift(self, amount: i32) -> Term {
+        fn shift_with_cutoff(term: Term, amount: i32, cutoff: usize) -> Term {
+            term.map_vars(cutoff, &|var, cutoff| {
+                Term::Var(Var {
+                    index: if var.index >= cutoff {
+                        ((var.index as i32) + amount) as usize
+                    } else {
+                        var.index
+                    },
+                    context_length: ((var.context_length as i32) + amount) as usize,
+                    ..var
+                })
+            })
+        }
+
+        shift_with_cutoff(self, amount, 0)
+    }
+
+    pub fn subst(self, replacee: usize, replacer: &Term) -> Term {
+        self.map_vars(0, &|var, depth| {
+            if var.index == replacee + depth {
+                replacer.clone().shift(depth as i32)
+            } else {
+                Term::Var(var)
+            }
+        })
+    }
+
+    /// Replaces every variable, passing the number of abstractions it is under.
+    fn map_vars(self, depth: usize, f: &dyn Fn(Var, usize) -> Term) -> Term {
+        match self {
+            Term::Var(var) => f(var, depth),
+            Term::Abs(abs) => Term::Abs(Box::new(Abs {
+                body: abs.body.map_vars(depth + 1, f),
+                ..*abs
+            })),
+            Term::App(app) => Term::App(Box::new(App {
+                callee: app.callee.map_vars(depth, f),
+                arg: app.arg.map_vars(depth, f),
+                ..*app
+            })),
+            Term::If(if_) => Term::If(Box::new(If {
+                guard: if_.guard.map_vars(depth, f),
+                then: if_.then.map_vars(depth, f),
+                else_: if_.else_.map_vars(depth, f),
+                ..*if_
+            })),
+            Term::Nat(_) | Term::Bool(_) | Term::Prim(_) => self,
+        }
+    }
+
+    /// Abstractions, constants, and primitives applied to fewer values than their arity.
+    pub fn is_val(&self) -> bool {
+        match self {
+            Term::Abs(_) | Term::Nat(_) | Term::Bool(_) | Term::Prim(_) => true,
+            Term::App(_) => self.partial_prim().is_some_and(|(prim, args)| {
+                args.len() < prim.op.arity() && args.iter().all(|arg| arg.is_val())
+            }),
+            Term::Var(_) | Term::If(_) => false,
+        }
+    }
+
+    /// The primitive at the head of `self` and the arguments it is applied to.
+    pub fn partial_prim(&self) -> Option<(&Prim, Vec<&Term>)> {
+        let mut args = vec![];
+        let mut head = self;
+        while let Term::App(app) = head {
+            args.push(&app.arg);
+            head = &app.callee;
+        }
+        args.reverse();
+        match head {
+            Term::Prim(prim) => Some((prim, args)),
+            _ => None,
+        }
+    }
+
+    pub fn is_abs(&self) -> bool {
+        matches!(self, Term::Abs(_))
+    }
+
+    pub fn as_nat(&self) -> Option<u64> {
+        match self {
+            Term::Nat(nat) => Some(nat.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Term::Bool(b) => Some(b.value),
+            _ => None,
+        }
+    }
+}
+
+impl Abs {
+    pub fn apply(self, argument: &Term) -> Term {
+        self.body.subst(0, &argument.clone().shift(1)).shift(-1)
+    }
+}
+
+impl From<Var> for Term {
+    fn from(var: Var) -> Term {
+        Term::Var(var)
+    }
+}
+
+impl From<Abs> for Term {
+    fn from(abs: Abs) -> Term {
+        Term::Abs(Box::new(abs))
+    }
+}
+
+impl From<App> for Term {
+    fn from(app: App) -> Term {
+        Term::App(Box::new(app))
+    }
+}
+
+impl From<Nat> for Term {
+    fn from(nat: Nat) -> Term {
+        Term::Nat(nat)
+    }
+}
+
+impl From<Bool> for Term {
+    fn from(b: Bool) -> Term {
+        Term::Bool(b)
+    }
+}
+
+impl From<If> for Term {
+    fn from(if_: If) -> Term {
+        Term::If(Box::new(if_))
+    }
+}
+
+impl From<Prim> for Term {
+    fn from(prim: Prim) -> Term {
+        Term::Prim(prim)
+    }
+}
+
+impl Position for &Term {
+    fn position(self) -> FilePositionRange {
+        match self {
+            Term::Var(var) => var.position,
+            Term::Abs(abs) => abs.position,
+            Term::App(app) => app.position,
+            Term::Nat(nat) => nat.position,
+            Term::Bool(b) => b.position,
+            Term::If(if_) => if_.position,
+            Term::Prim(prim) => prim.position,
+        }
+    }
+}
+
+impl From<unnamed::Term> for Term {
+    fn from(term: unnamed::Term) -> Term {
+        match term {
+            unnamed::Term::Var(var) => Term::Var(var),
+            unnamed::Term::Abs(abs) => Abs {
+                position: abs.position,
+                param_name: abs.param_name,
+                body: abs.body.into(),
+            }
+            .into(),
+            unnamed::Term::App(app) => App {
+                position: app.position,
+                callee: app.callee.into(),
+                arg: app.arg.into(),
+            }
+            .into(),
+        }
+    }
+}
+
+impl Term {
+    /// Removes names like `unnamed::Term::from_named`, except that names
+    /// missing from `ctx` may refer to constants and primitives:
+    /// `true`, `false`, `zero`, `succ`, `pred`, `iszero` and `plus`.
+    /// `if` applied to three arguments is read as `if guard then then else else_`.
+    pub fn from_named(named: NamedTerm, ctx: &Context) -> Result<Term, CannotFindVarInCtxErr> {
+        if let Some((position, if_var, mut args)) = if_application(&named) {
+            if ctx.index(&if_var.name).is_none() {
+                if args.len() < 3 {
+                    return Err(CannotFindVarInCtxErr(if_var.clone()));
+                }
+                let rest = args.split_off(3);
+                let mut args = args.into_iter();
+                let mut term: Term = If {
+                    position,
+                    guard: Term::from_named(args.next().unwrap().clone(), ctx)?,
+                    then: Term::from_named(args.next().unwrap().clone(), ctx)?,
+                    else_: Term::from_named(args.next().unwrap().clone(), ctx)?,
+                }
+                .into();
+                for arg in rest {
+                    term = App {
+                        position: FilePositionRange {
+                            start: position.start,
+                            end: arg.position().end,
+                        },
+                        callee: term,
+                        arg: Term::from_named(arg.clone(), ctx)?,
+                    }
+                    .into();
+                }
+                return Ok(term);
+            }
+        }
+
+        match named {
+            NamedTerm::Var(var) => {
+                if ctx.index(&var.name).is_some() {
+                    return unnamed::Var::from_named(var, ctx).map(Term::from);
+                }
+                let position = var.position;
+                match var.name.as_str() {
+                    "true" => Ok(Bool {
+                        position,
+                        value: true,
+                    }
+                    .into()),
+                    "false" => Ok(Bool {
+                        position,
+                        value: false,
+                    }
+                    .into()),
+                    "zero" => Ok(Nat { position, value: 0 }.into()),
+                    name => match Op::from_name(name) {
+                        Some(op) => Ok(Prim { position, op }.into()),
+                        None => Err(CannotFindVarInCtxErr(var)),
+                    },
+                }
+            }
+            NamedTerm::Abs(abs) => {
+                let position = abs.position;
+                let param_name = abs.param.name;
+                let body_ctx = ctx.clone() + param_name.clone();
+                Ok(Abs {
+                    position,
+                    param_name,
+                    body: Term::from_named(abs.body, &body_ctx)?,
+                }
+                .into())
+            }
+            NamedTerm::App(app) => Ok(App {
+                position: app.position,
+                callee: Term::from_named(app.callee, ctx)?,
+                arg: Term::from_named(app.arg, ctx)?,
+            }
+            .into()),
+        }
+    }
+}
+
+/// Splits an application with `if` at its head into the position of the
+/// application, the `if` and its arguments.
+fn if_application(named: &NamedTerm) -> Option<(FilePositionRange, &named::Var, Vec<&NamedTerm>)> {
+    let mut args = vec![];
+    let mut head = named;
+    while let NamedTerm::App(app) = head {
+        args.push(&app.arg);
+        head = &app.callee;
+    }
+    args.reverse();
+    match head {
+        NamedTerm::Var(var) if var.name == "if" && !args.is_empty() => {
+            let end = args[args.len().min(3) - 1].position().end;
+            let position = FilePositionRange {
+                start: var.position.start,
+                end,
+            };
+            Some((position, var, args))
+        }
+        _ => None,
+    }
+}
+
+fn parse_closed(src: &str, position: FilePositionRange) -> Term {
+    let named = super::super::parse(src).expect("The source is a term.");
+    let term = Term::from_named(named, &Context::empty()).expect("The term is closed.");
+    term.reposition(position)
+}
+
+impl Term {
+    fn reposition(self, position: FilePositionRange) -> Term {
+        match self {
+            Term::Var(var) => Var { position, ..var }.into(),
+            Term::Abs(abs) => Abs {
+                position,
+                param_name: abs.param_name,
+                body: abs.body.reposition(position),
+            }
+            .into(),
+            Term::App(app) => App {
+                position,
+                callee: app.callee.reposition(position),
+                arg: app.arg.reposition(position),
+            }
+            .into(),
+            Term::Nat(nat) => Nat { position, ..nat }.into(),
+            Term::Bool(b) => Bool { position, ..b }.into(),
+            Term::If(if_) => If {
+                position,
+                guard: if_.guard.reposition(position),
+                then: if_.then.reposition(position),
+                else_: if_.else_.reposition(position),
+            }
+            .into(),
+            Term::Prim(prim) => Prim { position, ..prim }.into(),
+        }
+    }
+}
+
+/// The Church numeral `\s. \z. s (s ... z)` for `n`, in the empty context.
+pub fn church_nat(n: u64, position: FilePositionRange) -> unnamed::Term {
+    let var = |index: usize| -> unnamed::Term {
+        Var {
+            position,
+            index,
+            context_length: 2,
+        }
+        .into()
+    };
+    let mut body = var(0);
+    for _ in 0..n {
+        body = unnamed::App {
+            position,
+            callee: var(1),
+            arg: body,
+        }
+        .into();
+    }
+    ["s", "z"].iter().rev().fold(body, |body, param_name| {
+        unnamed::Abs {
+            position,
+            param_name: param_name.to_string(),
+            body,
+        }
+        .into()
+    })
+}
+
+/// The Church boolean `tru` or `fls`, in the empty context.
+pub fn church_bool(b: bool, position: FilePositionRange) -> unnamed::Term {
+    let index = if b { 1 } else { 0 };
+    ["t", "f"].iter().rev().fold(
+        Var {
+            position,
+            index,
+            context_length: 2,
+        }
+        .into(),
+        |body, param_name| {
+            unnamed::Abs {
+                position,
+                param_name: param_name.to_string(),
+                body,
+            }
+            .into()
+        },
+    )
+}
+
+/// `realbool = \b. b true false`, which converts a Church boolean to a native one.
+/// Every node takes the given position.
+pub fn realbool(position: FilePositionRange) -> Term {
+    parse_closed(r"\b. b true false", position)
+}
+
+/// `churchbool = \b. if b then tru else fls`.
+pub fn churchbool(position: FilePositionRange) -> Term {
+    parse_closed(r"\b. if b (\t. \f. t) (\t. \f. f)", position)
+}
+
+/// `realnat = \m. m (\x. succ x) 0`, which converts a Church numeral to a native one.
+pub fn realnat(position: FilePositionRange) -> Term {
+    parse_closed(r"\m. m (\x. succ x) zero", position)
+}
+
+/// Converts a native number to a Church numeral,
+/// using the call-by-value fixed-point combinator for the recursion.
+pub fn churchnat(position: FilePositionRange) -> Term {
+    parse_closed(
+        r"letrec cn = \n. if (iszero n)
+            (\s. \z. z)
+            (\s. \z. s (cn (pred n) s z))
+        in cn",
+        position,
+    )
+}
+
+impl fmt::Display for Term {
+    /// Variables are printed as indices, like in `unnamed::Term`,
+    /// and numbers with a leading `#`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(var) => write!(f, "{}", var.index),
+            Term::Abs(abs) => write!(f, "(\\. {})", abs.body),
+            Term::App(app) => write!(f, "({} {})", app.callee, app.arg),
+            Term::Nat(nat) => write!(f, "#{}", nat.value),
+            Term::Bool(b) => write!(f, "{}", b.value),
+            Term::If(if_) => write!(f, "(if {} then {} else {})", if_.guard, if_.then, if_.else_),
+            Term::Prim(prim) => write!(f, "{}", prim.op.name()),
+        }
+    }
+}
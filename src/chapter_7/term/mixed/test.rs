@@ -0,0 +1,89 @@
+use super::{church_bool, church_nat, Term};
+use crate::chapter_7::{
+    parse,
+    term::unnamed::{self, Context},
+    test_util::position,
+};
+use crate::file_position::Position;
+use std::convert::TryFrom;
+
+fn mixed(src: &str, ctx: &Context) -> Term {
+    Term::from_named(parse(src).expect("Cannot parse."), ctx).expect("Cannot remove names.")
+}
+
+#[test]
+fn free_names_may_be_primitives() {
+    let term = mixed(r"\x. plus (succ zero) x", &Context::empty());
+    assert_eq!(term.to_string(), r"(\. ((plus (succ #0)) 0))");
+    let term = mixed(r"if true zero (pred zero)", &Context::empty());
+    assert_eq!(term.to_string(), r"(if true then #0 else (pred #0))");
+}
+
+#[test]
+fn bound_names_shadow_primitives() {
+    let term = mixed(
+        r"\succ. \if. if succ zero zero",
+        &Context::from_strs(&["true"]),
+    );
+    assert_eq!(term.to_string(), r"(\. (\. (((0 1) #0) #0)))");
+    let term = mixed(r"true", &Context::from_strs(&["true"]));
+    assert_eq!(term.to_string(), "0");
+}
+
+#[test]
+fn if_needs_three_arguments() {
+    let err = Term::from_named(
+        parse("if true zero").expect("Cannot parse."),
+        &Context::empty(),
+    )
+    .expect_err("`if` has two arguments.");
+    assert_eq!(err.0.name, "if");
+    let term = mixed(r"if true succ pred zero", &Context::empty());
+    assert_eq!(term.to_string(), r"((if true then succ else pred) #0)");
+    assert_eq!(term.position().end.index, 22);
+}
+
+#[test]
+fn pure_terms_embed_unchanged() {
+    let src = r"(\x. \y. y x) (\z. z)";
+    let unnamed =
+        unnamed::Term::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.");
+    assert_eq!(Term::from(unnamed.clone()).to_string(), unnamed.to_string());
+    assert_eq!(Term::from(unnamed), mixed(src, &Context::empty()));
+}
+
+#[test]
+fn apply_shifts_around_constants() {
+    let ctx = Context::from_strs(&["w"]);
+    let abs = match mixed(r"\x. \y. if x (succ y) zero", &ctx) {
+        Term::Abs(abs) => *abs,
+        other => panic!("Expected an abstraction, but found {}.", other),
+    };
+    let applied = abs.apply(&mixed("w", &ctx));
+    assert_eq!(applied.to_string(), r"(\. (if 1 then (succ 0) else #0))");
+}
+
+#[test]
+fn church_encodings() {
+    let expected = |src: &str| {
+        unnamed::Term::try_from(parse(src).expect("Cannot parse."))
+            .expect("Cannot remove names.")
+            .to_string()
+    };
+    assert_eq!(
+        church_nat(0, position()).to_string(),
+        expected(r"\s. \z. z")
+    );
+    assert_eq!(
+        church_nat(3, position()).to_string(),
+        expected(r"\s. \z. s (s (s z))")
+    );
+    assert_eq!(
+        church_bool(true, position()).to_string(),
+        expected(r"\t. \f. t")
+    );
+    assert_eq!(
+        church_bool(false, position()).to_string(),
+        expected(r"\t. \f. f")
+    );
+}
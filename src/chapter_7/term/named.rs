@@ -31,28 +31,20 @@ pub struct App {
 }
 
 impl Term {
+    pub fn is_val(&self) -> bool {
+        self.is_abs()
+    }
+
     pub fn is_var(&self) -> bool {
-        if let Term::Var(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Term::Var(_))
     }
 
     pub fn is_abs(&self) -> bool {
-        if let Term::Abs(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Term::Abs(_))
     }
 
     pub fn is_app(&self) -> bool {
-        if let Term::App(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Term::App(_))
     }
 }
 
@@ -132,7 +124,7 @@ impl Abs {
 
         fn extend_ctx(ctx: &Context, ideal_name: String) -> (Context, String) {
             let mut used_name = ideal_name;
-            while let Some(_) = ctx.index(&used_name) {
+            while ctx.index(&used_name).is_some() {
                 used_name.push('\'');
             }
             (ctx + used_name.clone(), used_name)
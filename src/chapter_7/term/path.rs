@@ -0,0 +1,48 @@
+use super::unnamed::{Abs, App, Term};
+
+/// One step from a term to one of its immediate subterms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Callee,
+    Arg,
+    Body,
+}
+
+impl Term {
+    /// Returns the subterm reached by following `path` from `self`,
+    /// or `None` if the path does not exist in this term.
+    pub fn subterm(&self, path: &[Direction]) -> Option<&Term> {
+        match (self, path.split_first()) {
+            (_, None) => Some(self),
+            (Term::App(app), Some((Direction::Callee, rest))) => app.callee.subterm(rest),
+            (Term::App(app), Some((Direction::Arg, rest))) => app.arg.subterm(rest),
+            (Term::Abs(abs), Some((Direction::Body, rest))) => abs.body.subterm(rest),
+            _ => None,
+        }
+    }
+
+    /// Replaces the subterm reached by following `path` with `replacer`.
+    ///
+    /// Indices in `replacer` are not adjusted, so it must already be
+    /// expressed in the context of the subterm it replaces.
+    ///
+    /// Panics if the path does not exist in this term.
+    pub fn replace_subterm(self, path: &[Direction], replacer: Term) -> Term {
+        match (self, path.split_first()) {
+            (_, None) => replacer,
+            (Term::App(app), Some((Direction::Callee, rest))) => Term::App(Box::new(App {
+                callee: app.callee.replace_subterm(rest, replacer),
+                ..*app
+            })),
+            (Term::App(app), Some((Direction::Arg, rest))) => Term::App(Box::new(App {
+                arg: app.arg.replace_subterm(rest, replacer),
+                ..*app
+            })),
+            (Term::Abs(abs), Some((Direction::Body, rest))) => Term::Abs(Box::new(Abs {
+                body: abs.body.replace_subterm(rest, replacer),
+                ..*abs
+            })),
+            (_, Some((direction, _))) => panic!("Cannot follow {:?} in path.", direction),
+        }
+    }
+}
@@ -96,28 +96,96 @@ impl Term {
     }
 
     pub fn is_var(&self) -> bool {
-        if let Term::Var(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Term::Var(_))
     }
 
     pub fn is_abs(&self) -> bool {
-        if let Term::Abs(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Term::Abs(_))
     }
 
     pub fn is_app(&self) -> bool {
-        if let Term::App(_) = self {
-            true
-        } else {
-            false
+        matches!(self, Term::App(_))
+    }
+
+    /// Whether the variable with index `index` occurs free in `self`.
+    pub fn has_free_var(&self, index: usize) -> bool {
+        match self {
+            Term::Var(var) => var.index == index,
+            Term::Abs(abs) => abs.body.has_free_var(index + 1),
+            Term::App(app) => app.callee.has_free_var(index) || app.arg.has_free_var(index),
         }
     }
+
+    /// Whether `self` and `other` are the same term up to positions and parameter names,
+    /// i.e., whether they are alpha-equivalent.
+    pub fn is_alpha_equivalent(&self, other: &Term) -> bool {
+        match (self, other) {
+            (Term::Var(a), Term::Var(b)) => a.index == b.index,
+            (Term::Abs(a), Term::Abs(b)) => a.body.is_alpha_equivalent(&b.body),
+            (Term::App(a), Term::App(b)) => {
+                a.callee.is_alpha_equivalent(&b.callee) && a.arg.is_alpha_equivalent(&b.arg)
+            }
+            _ => false,
+        }
+    }
+
+    /// The length of the context `self` is in.
+    pub fn context_length(&self) -> usize {
+        match self {
+            Term::Var(var) => var.context_length,
+            Term::Abs(abs) => abs.body.context_length() - 1,
+            Term::App(app) => app.callee.context_length(),
+        }
+    }
+
+    /// Contracts every eta redex `\x. t x`, where `x` is not free in `t`, to `t`,
+    /// giving the eta normal form.
+    pub fn eta_reduce(self) -> Term {
+        match self {
+            Term::Var(var) => Term::Var(var),
+            Term::Abs(abs) => {
+                let body = abs.body.eta_reduce();
+                match body {
+                    Term::App(app) if is_var_zero(&app.arg) && !app.callee.has_free_var(0) => {
+                        app.callee.shift(-1)
+                    }
+                    body => Term::Abs(Box::new(Abs { body, ..*abs })),
+                }
+            }
+            Term::App(app) => Term::App(Box::new(App {
+                callee: app.callee.eta_reduce(),
+                arg: app.arg.eta_reduce(),
+                ..*app
+            })),
+        }
+    }
+
+    /// Wraps `self` in an abstraction `\x. t x`, the inverse of an eta step.
+    /// The new nodes take the position of `self`.
+    pub fn eta_expand(self, param_name: String) -> Term {
+        let position = (&self).position();
+        let context_length = self.context_length() + 1;
+        Abs {
+            position,
+            param_name,
+            body: App {
+                position,
+                callee: self.shift(1),
+                arg: Var {
+                    position,
+                    index: 0,
+                    context_length,
+                }
+                .into(),
+            }
+            .into(),
+        }
+        .into()
+    }
+}
+
+fn is_var_zero(term: &Term) -> bool {
+    matches!(term, Term::Var(Var { index: 0, .. }))
 }
 
 impl Abs {
@@ -233,6 +301,16 @@ impl Var {
 #[derive(Debug, Clone)]
 pub struct CannotFindVarInCtxErr(pub named::Var);
 
+impl fmt::Display for CannotFindVarInCtxErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: Cannot find `{}` in context.",
+            self.0.position.start, self.0.name
+        )
+    }
+}
+
 impl Context {
     pub fn empty() -> Context {
         Context { names: vec![] }
@@ -246,7 +324,7 @@ impl Context {
 
     pub fn from_strings(strings: &[String]) -> Context {
         Context {
-            names: strings.iter().cloned().collect(),
+            names: strings.to_vec(),
         }
     }
 
@@ -267,9 +345,19 @@ impl Context {
         }
     }
 
+    /// Returns the names in the order they were added,
+    /// so the name with index `0` is last.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
     pub fn len(&self) -> usize {
         self.names.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
 }
 
 impl Add<String> for Context {
@@ -0,0 +1,37 @@
+//! Helpers shared by the tests of this chapter.
+
+pub(crate) use super::church::position;
+
+use super::church::PRELUDE;
+use super::parse_statements;
+use super::statement::{Definitions, Statement};
+use super::term::unnamed::Term;
+
+/// Defines `church::PRELUDE` followed by `src`, returning the definitions
+/// and the resolved term that `src` ends with, if any.
+/// Both are parsed as one source, so that no two definitions share a position.
+pub fn load(src: &str) -> (Definitions, Option<Term>) {
+    let mut defs = Definitions::empty();
+    let mut term = None;
+    let src = format!("{}{}", PRELUDE, src);
+    for statement in parse_statements(&src).expect("Cannot parse.") {
+        match statement {
+            Statement::Def(def) => defs.define(def).expect("Cannot define."),
+            Statement::Eval(named) => term = Some(defs.resolve(named).expect("Cannot resolve.")),
+        }
+    }
+    (defs, term)
+}
+
+/// A small linear congruential generator, so the generated terms are reproducible.
+pub struct Random(pub u64);
+
+impl Random {
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) % n
+    }
+}
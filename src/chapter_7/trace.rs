@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod test;
+
+use super::evaluator::{contract, Strategy};
+use super::term::{named::Term as NamedTerm, path::Direction, unnamed::Term};
+use crate::file_position::{FilePositionRange, Position};
+
+/// A single reduction step, as performed by `evaluator::eval1`
+/// (or `evaluator::eval1_with`, for other strategies).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub before: Term,
+    /// The path from `before` to the contracted redex.
+    pub path: Vec<Direction>,
+    pub redex_position: FilePositionRange,
+    pub redex: Term,
+    pub contractum: Term,
+    pub after: Term,
+}
+
+/// An iterator over the steps taken to evaluate a term.
+///
+/// The iterator is lazy, so it can be used on divergent terms
+/// as long as the caller stops after some number of steps.
+#[derive(Clone, Debug)]
+pub struct Steps {
+    term: Option<Term>,
+    strategy: Strategy,
+}
+
+impl Iterator for Steps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let strategy = self.strategy;
+        let step = self
+            .term
+            .as_ref()
+            .and_then(|term| step_with(term, strategy));
+        self.term = step.as_ref().map(|step| step.after.clone());
+        step
+    }
+}
+
+pub fn steps(term: Term) -> Steps {
+    steps_with(term, Strategy::CallByValue)
+}
+
+pub fn steps_with(term: Term, strategy: Strategy) -> Steps {
+    Steps {
+        term: Some(term),
+        strategy,
+    }
+}
+
+pub fn step(term: &Term) -> Option<Step> {
+    step_with(term, Strategy::CallByValue)
+}
+
+pub fn step_with(term: &Term, strategy: Strategy) -> Option<Step> {
+    let path = strategy.redex_path(term)?;
+    let redex = term.subterm(&path)?.clone();
+    let redex_position = (&redex).position();
+    let contractum = contract(&redex)?;
+    let after = term.clone().replace_subterm(&path, contractum.clone());
+    Some(Step {
+        before: term.clone(),
+        path,
+        redex_position,
+        redex,
+        contractum,
+        after,
+    })
+}
+
+/// Prints the term before the step with the redex underlined, e.g.,
+///
+/// ```text
+/// ((\x. x) (\y. y))
+/// ^^^^^^^^^^^^^^^^^
+/// ```
+pub fn render(step: &Step) -> String {
+    let named = step.before.clone().into_unpositioned_named();
+    let mut printed = String::new();
+    let (start, end) = print_with_span(&named, &step.path, &mut printed);
+    let underline: String = (0..end)
+        .map(|column| if column < start { ' ' } else { '^' })
+        .collect();
+    format!("{}\n{}", printed, underline)
+}
+
+/// Renders every step of a trace, followed by the final term.
+pub fn render_all(steps: &[Step]) -> String {
+    let mut out: Vec<String> = steps.iter().map(render).collect();
+    if let Some(last) = steps.last() {
+        out.push(last.after.clone().into_unpositioned_named().to_string());
+    }
+    out.join("\n")
+}
+
+/// Prints `term` the same way as its `Display` implementation does,
+/// returning the start and end columns of the subterm at `path`.
+fn print_with_span(term: &NamedTerm, path: &[Direction], out: &mut String) -> (usize, usize) {
+    let start = out.chars().count();
+    let mut span = None;
+    match term {
+        NamedTerm::Var(var) => out.push_str(&var.name),
+        NamedTerm::Abs(abs) => {
+            out.push_str(&format!("(\\{}. ", abs.param.name));
+            let body_span = print_with_span(&abs.body, tail(path), out);
+            if path.first() == Some(&Direction::Body) {
+                span = Some(body_span);
+            }
+            out.push(')');
+        }
+        NamedTerm::App(app) => {
+            out.push('(');
+            let callee_span = print_with_span(&app.callee, tail(path), out);
+            out.push(' ');
+            let arg_span = print_with_span(&app.arg, tail(path), out);
+            out.push(')');
+            match path.first() {
+                Some(Direction::Callee) => span = Some(callee_span),
+                Some(Direction::Arg) => span = Some(arg_span),
+                _ => {}
+            }
+        }
+    }
+    span.unwrap_or((start, out.chars().count()))
+}
+
+fn tail(path: &[Direction]) -> &[Direction] {
+    if path.is_empty() {
+        path
+    } else {
+        &path[1..]
+    }
+}
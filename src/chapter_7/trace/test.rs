@@ -0,0 +1,79 @@
+use super::{render, render_all, step, steps};
+use crate::chapter_7::{eval_count, parse, term::path::Direction, term::unnamed::Term};
+use crate::file_position::Position;
+use std::convert::TryFrom;
+
+fn unnamed(src: &str) -> Term {
+    Term::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.")
+}
+
+#[test]
+fn value_has_no_step() {
+    assert_eq!(step(&unnamed(r"\x. x")), None);
+}
+
+#[test]
+fn top_level_redex() {
+    let term = unnamed(r"(\x. x) \y. y");
+    let step = step(&term).expect("Cannot step.");
+    assert_eq!(step.path, vec![]);
+    assert_eq!(step.redex, term);
+    assert_eq!(step.redex_position, (&term).position());
+    assert_eq!(step.after.into_unpositioned_named().to_string(), r"(\y. y)");
+}
+
+#[test]
+fn callee_is_reduced_first() {
+    let term = unnamed(r"((\x. x) (\y. y)) ((\z. z) (\w. w))");
+    let step = step(&term).expect("Cannot step.");
+    assert_eq!(step.path, vec![Direction::Callee]);
+    assert_eq!(
+        step.contractum.into_unpositioned_named().to_string(),
+        r"(\y. y)"
+    );
+}
+
+#[test]
+fn arg_is_reduced_once_callee_is_value() {
+    let term = unnamed(r"(\x. x) ((\z. z) (\w. w))");
+    let step = step(&term).expect("Cannot step.");
+    assert_eq!(step.path, vec![Direction::Arg]);
+}
+
+#[test]
+fn step_count_matches_eval_count() {
+    let term = unnamed(r"(\a. a \b. a) \b.b");
+    let trace: Vec<_> = steps(term.clone()).collect();
+    let (normal_form, count) = eval_count(term);
+    assert_eq!(trace.len(), count);
+    assert_eq!(
+        trace.last().map(|step| step.after.clone()),
+        Some(normal_form)
+    );
+}
+
+#[test]
+fn steps_are_lazy() {
+    let omega = unnamed(r"(\x. x x) \x. x x");
+    assert_eq!(steps(omega).take(3).count(), 3);
+}
+
+#[test]
+fn render_underlines_redex() {
+    let term = unnamed(r"(\x. x) ((\z. z) (\w. w))");
+    let step = step(&term).expect("Cannot step.");
+    assert_eq!(
+        render(&step),
+        "((\\x. x) ((\\z. z) (\\w. w)))\n         ^^^^^^^^^^^^^^^^^"
+    );
+}
+
+#[test]
+fn render_all_ends_with_normal_form() {
+    let term = unnamed(r"(\x. x) ((\z. z) (\w. w))");
+    let trace: Vec<_> = steps(term).collect();
+    assert_eq!(
+        render_all(&trace),
+        "((\\x. x) ((\\z. z) (\\w. w)))\n         ^^^^^^^^^^^^^^^^^\n((\\x. x) (\\w. w))\n^^^^^^^^^^^^^^^^^\n(\\w. w)"
+    );
+}
@@ -0,0 +1,638 @@
+#[cfg(test)]
+mod test;
+
+use crate::chapter_11::{
+    self,
+    store::Store,
+    type_checker::{TypeContext, DEFAULT_EXN_TYPE},
+    Outcome,
+};
+use crate::chapter_15;
+use crate::chapter_4;
+use crate::chapter_7::{
+    big_step_evaluator,
+    evaluator::{eval_count_with_fuel, OutOfFuelErr, Strategy},
+    json::Document,
+    parse_statements,
+    parser::err::ParseErr,
+    statement::{Definitions, Statement},
+    term::unnamed::{CannotFindVarInCtxErr, Context, Term},
+};
+use crate::file_position::{FilePositionRange, Position};
+use crate::json::{FromJson, Json, ToJson};
+use crate::repl::{describe_uncaught, steps, Language, DEFAULT_FUEL, DEFAULT_GC_THRESHOLD};
+use std::fs;
+use std::io::{self, Write};
+
+pub const USAGE: &str = r"Usage: tapl-rust [OPTIONS] [FILE...]
+
+With no files, starts an interactive session.
+Otherwise, runs every `;`-separated statement in each file,
+printing results to stdout and diagnostics to stderr.
+Files ending in `.json` are read as JSON documents (chapter_7 only).
+A chapter_11 file is a single term, which is type checked before it is evaluated.
+
+Options:
+  --lang chapter_4|chapter_7|chapter_11
+                                  The language of the files (default: chapter_7).
+  --mode small-step|big-step|count|parse
+                                  How to evaluate terms (default: small-step).
+                                  `count` also prints the number of steps taken.
+                                  `parse` prints the statements without evaluating them.
+  --format named|nameless|json    How to print terms (default: named).
+                                  `json` prints one JSON document per line to stdout,
+                                  including diagnostics (chapter_7 only).
+  --strategy cbv|cbn|normal       The small-step evaluation strategy (default: cbv).
+  --fuel <steps>                  The maximum number of steps per term (default: 10000).
+  --gc-threshold <locations>|off  The number of live locations at which the store is collected
+                                  (chapter_11 only, default: 1000).
+                                  `count` also prints what the collections freed.
+  --exn-type <type>               The type of the values that `raise` takes
+                                  (chapter_11 only, default: Nat).
+  -h, --help                      Show this message.
+
+Exits with status 1 if any file has a parse, naming or evaluation error.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    SmallStep,
+    BigStep,
+    Count,
+    Parse,
+}
+
+impl Mode {
+    pub fn from_name(name: &str) -> Option<Mode> {
+        match name {
+            "small-step" => Some(Mode::SmallStep),
+            "big-step" => Some(Mode::BigStep),
+            "count" => Some(Mode::Count),
+            "parse" => Some(Mode::Parse),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Terms are printed with names, e.g., `(\x. x)`.
+    Named,
+    /// Terms are printed with de Bruijn indices, e.g., `(\. 0)`.
+    Nameless,
+    /// Results and diagnostics are printed as `chapter_7::json::Document`s,
+    /// one per line.
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "named" => Some(Format::Named),
+            "nameless" => Some(Format::Nameless),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub files: Vec<String>,
+    pub language: Language,
+    pub mode: Mode,
+    pub format: Format,
+    pub strategy: Strategy,
+    pub fuel: usize,
+    /// `None` if the store of a chapter_11 term is never collected.
+    pub gc_threshold: Option<usize>,
+    /// The type of the values that `raise` takes in a chapter_11 term.
+    pub exn_type: chapter_11::Type,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            files: vec![],
+            language: Language::Lambda,
+            mode: Mode::SmallStep,
+            format: Format::Named,
+            strategy: Strategy::CallByValue,
+            fuel: DEFAULT_FUEL,
+            gc_threshold: Some(DEFAULT_GC_THRESHOLD),
+            exn_type: DEFAULT_EXN_TYPE,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Repl,
+    Help,
+    Run(Options),
+}
+
+/// Parses the command-line arguments (excluding the program name).
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .map(String::as_str)
+                .ok_or_else(|| format!("Missing value for `{}`.", flag))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--lang" => {
+                let name = value(arg)?;
+                options.language = Language::from_name(name)
+                    .ok_or_else(|| format!("Unknown language `{}`.", name))?;
+            }
+            "--mode" => {
+                let name = value(arg)?;
+                options.mode =
+                    Mode::from_name(name).ok_or_else(|| format!("Unknown mode `{}`.", name))?;
+            }
+            "--format" => {
+                let name = value(arg)?;
+                options.format =
+                    Format::from_name(name).ok_or_else(|| format!("Unknown format `{}`.", name))?;
+            }
+            "--strategy" => {
+                let name = value(arg)?;
+                options.strategy = Strategy::from_name(name)
+                    .ok_or_else(|| format!("Unknown strategy `{}`.", name))?;
+            }
+            "--fuel" => {
+                let steps = value(arg)?;
+                options.fuel = steps
+                    .parse()
+                    .map_err(|_| format!("Invalid fuel `{}`.", steps))?;
+            }
+            "--gc-threshold" => {
+                let locations = value(arg)?;
+                options.gc_threshold = match locations {
+                    "off" => None,
+                    _ => Some(
+                        locations
+                            .parse()
+                            .map_err(|_| format!("Invalid GC threshold `{}`.", locations))?,
+                    ),
+                };
+            }
+            "--exn-type" => {
+                let ty = value(arg)?;
+                options.exn_type = chapter_11::parser::parse_type(ty)
+                    .map_err(|e| format!("Invalid exception type `{}`: {}", ty, e))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option `{}`.", arg)),
+            _ => options.files.push(arg.clone()),
+        }
+    }
+
+    if options.format == Format::Json && options.language != Language::Lambda {
+        return Err("`--format json` is only supported for chapter_7.".to_string());
+    }
+    if options.mode == Mode::BigStep && options.language == Language::Typed {
+        return Err("`--mode big-step` is not supported for chapter_11.".to_string());
+    }
+    if options.mode == Mode::Parse && options.format == Format::Nameless {
+        return Err(
+            "`--mode parse` prints terms as written; use `--format named` or `--format json`."
+                .to_string(),
+        );
+    }
+
+    if options.files.is_empty() {
+        Ok(Command::Repl)
+    } else {
+        Ok(Command::Run(options))
+    }
+}
+
+/// Runs every file, returning `true` if there were no errors.
+pub fn run(options: &Options, out: &mut dyn Write, err: &mut dyn Write) -> io::Result<bool> {
+    let mut ok = true;
+    for path in &options.files {
+        ok &= match fs::read_to_string(path) {
+            Ok(src) => run_source(options, path, &src, out, err)?,
+            Err(e) => {
+                writeln!(err, "{}: Cannot read file: {}", path, e)?;
+                false
+            }
+        };
+    }
+    Ok(ok)
+}
+
+/// Runs every statement in `src`, returning `true` if there were no errors.
+/// `name` is used to identify the source in diagnostics.
+pub fn run_source(
+    options: &Options,
+    name: &str,
+    src: &str,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<bool> {
+    match options.language {
+        Language::Arith => run_arith(options, name, src, out, err),
+        Language::Lambda => run_lambda(options, name, src, out, err),
+        Language::Typed => run_typed(options, name, src, out, err),
+    }
+}
+
+fn run_arith(
+    options: &Options,
+    name: &str,
+    src: &str,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<bool> {
+    let mut ok = true;
+    let statements = src
+        .split(';')
+        .map(str::trim)
+        .filter(|statement| !statement.is_empty());
+
+    for (i, statement) in statements.enumerate() {
+        let term = match chapter_4::parse(statement) {
+            Some(term) => term,
+            None => {
+                writeln!(err, "{}: statement {}: Cannot parse.", name, i + 1)?;
+                ok = false;
+                continue;
+            }
+        };
+        match options.mode {
+            Mode::Parse => writeln!(out, "{}", term)?,
+            Mode::BigStep => match chapter_4::big_step_evaluator::eval(term) {
+                Some(value) => writeln!(out, "{}", value)?,
+                None => {
+                    writeln!(err, "{}: statement {}: Evaluation got stuck.", name, i + 1)?;
+                    ok = false;
+                }
+            },
+            Mode::SmallStep | Mode::Count => {
+                let (result, count) = chapter_4::eval_count(term);
+                if !chapter_4::evaluator::is_val(&result) {
+                    writeln!(
+                        err,
+                        "{}: statement {}: Evaluation got stuck at `{}`.",
+                        name,
+                        i + 1,
+                        result
+                    )?;
+                    ok = false;
+                } else if options.mode == Mode::Count {
+                    writeln!(out, "{} [{}]", result, steps(count))?;
+                } else {
+                    writeln!(out, "{}", result)?;
+                }
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+/// Type checks and evaluates the term in `src`, reporting warnings on stderr
+/// without failing.
+fn run_typed(
+    options: &Options,
+    name: &str,
+    src: &str,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<bool> {
+    let named = match chapter_11::parse(src) {
+        Ok(named) => named,
+        Err(e) => {
+            writeln!(err, "{}: {}", name, e)?;
+            return Ok(false);
+        }
+    };
+    if options.mode == Mode::Parse {
+        writeln!(out, "{}", named)?;
+        return Ok(true);
+    }
+    let position = (&named).position();
+    let term = match chapter_11::term::unnamed::Term::from_named(named, &Context::empty()) {
+        Ok(term) => term,
+        Err(e) => {
+            writeln!(err, "{}: {}", name, e)?;
+            return Ok(false);
+        }
+    };
+    let ty = match chapter_15::type_checker::type_of_with_warnings(
+        &term,
+        &TypeContext::empty().with_exn_type(options.exn_type.clone()),
+    ) {
+        Ok((ty, warnings)) => {
+            for warning in warnings {
+                writeln!(err, "{}: {}", name, warning)?;
+            }
+            ty
+        }
+        Err(e) => {
+            writeln!(err, "{}: {}", name, e)?;
+            return Ok(false);
+        }
+    };
+    let mut store = match options.gc_threshold {
+        Some(threshold) => Store::with_gc_threshold(threshold),
+        None => Store::new(),
+    };
+    match chapter_11::evaluator::eval_count_with_fuel(term, &mut store, options.fuel) {
+        Ok((normal_form, count)) => {
+            let (value, is_val) = match Outcome::of(normal_form) {
+                Outcome::Value(value) => (value, true),
+                Outcome::Stuck(stuck) => (stuck, false),
+                Outcome::Uncaught { position, value } => {
+                    writeln!(
+                        err,
+                        "{}: {}: {}.",
+                        name,
+                        position.start,
+                        describe_uncaught(value)
+                    )?;
+                    return Ok(false);
+                }
+            };
+            let printed = match options.format {
+                Format::Named | Format::Json => value.into_unpositioned_named().to_string(),
+                Format::Nameless => value.to_string(),
+            };
+            if !is_val {
+                writeln!(
+                    err,
+                    "{}: {}: Evaluation got stuck at `{}`.",
+                    name, position.start, printed
+                )?;
+                return Ok(false);
+            }
+            if options.mode == Mode::Count && store.gc_stats().collections > 0 {
+                writeln!(
+                    out,
+                    "{} : {} [{}, {}]",
+                    printed,
+                    ty,
+                    steps(count),
+                    store.gc_stats()
+                )?;
+            } else if options.mode == Mode::Count {
+                writeln!(out, "{} : {} [{}]", printed, ty, steps(count))?;
+            } else {
+                writeln!(out, "{} : {}", printed, ty)?;
+            }
+            Ok(true)
+        }
+        Err(chapter_11::evaluator::OutOfFuelErr { steps: count, .. }) => {
+            writeln!(
+                err,
+                "{}: {}: Out of fuel after {}.",
+                name,
+                position.start,
+                steps(count)
+            )?;
+            Ok(false)
+        }
+    }
+}
+
+/// A problem with a chapter_7 source, reported on stderr or,
+/// with `Format::Json`, as a document on stdout.
+enum Diagnostic {
+    Parse(ParseErr),
+    Naming(CannotFindVarInCtxErr),
+    Evaluation {
+        message: String,
+        position: FilePositionRange,
+    },
+    /// A `.json` file that does not contain a term or statements.
+    Import(String),
+}
+
+/// The contents of a chapter_7 source.
+enum Input {
+    Statements(Vec<Statement>),
+    /// A closed nameless term, imported from JSON.
+    Term(Term),
+}
+
+fn run_lambda(
+    options: &Options,
+    name: &str,
+    src: &str,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<bool> {
+    let input = if name.ends_with(".json") {
+        import(src)
+    } else {
+        parse_statements(src)
+            .map(Input::Statements)
+            .map_err(Diagnostic::Parse)
+    };
+    let statements = match input {
+        Ok(Input::Statements(statements)) => statements,
+        Ok(Input::Term(term)) => {
+            let position = (&term).position();
+            return run_term(options, name, term, position, out, err);
+        }
+        Err(diagnostic) => {
+            report(options, name, diagnostic, out, err)?;
+            return Ok(false);
+        }
+    };
+
+    if options.mode == Mode::Parse {
+        print_statements(options, statements, out)?;
+        return Ok(true);
+    }
+
+    let mut ok = true;
+    let mut defs = Definitions::empty();
+
+    for statement in statements {
+        match statement {
+            Statement::Def(def) => {
+                if let Err(e) = defs.define(def) {
+                    report(options, name, Diagnostic::Naming(e), out, err)?;
+                    ok = false;
+                }
+            }
+            Statement::Eval(named) => {
+                let position = (&named).position();
+                match defs.resolve(named) {
+                    Ok(term) => ok &= run_term(options, name, term, position, out, err)?,
+                    Err(e) => {
+                        report(options, name, Diagnostic::Naming(e), out, err)?;
+                        ok = false;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+fn import(src: &str) -> Result<Input, Diagnostic> {
+    let json = Json::parse(src).map_err(|e| Diagnostic::Import(e.to_string()))?;
+    let document = Document::from_json(&json).map_err(|e| Diagnostic::Import(e.to_string()))?;
+    match document {
+        Document::NamedTerm(term) => Ok(Input::Statements(vec![Statement::Eval(term)])),
+        Document::Statements(statements) => Ok(Input::Statements(statements)),
+        Document::UnnamedTerm { context, term } => {
+            if context.is_empty() {
+                Ok(Input::Term(term))
+            } else {
+                Err(Diagnostic::Import(format!(
+                    "Cannot evaluate a term with free variables: {}.",
+                    context.names().join(", ")
+                )))
+            }
+        }
+        other => Err(Diagnostic::Import(format!(
+            "Expected a `named_term`, `unnamed_term` or `statements` document, but found `{}`.",
+            other.kind()
+        ))),
+    }
+}
+
+fn print_statements(
+    options: &Options,
+    statements: Vec<Statement>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    if options.format == Format::Json {
+        return writeln!(out, "{}", Document::Statements(statements).to_json());
+    }
+    for statement in statements {
+        match statement {
+            Statement::Def(def) => writeln!(out, "{} = {}", def.name.name, def.value)?,
+            Statement::Eval(term) => writeln!(out, "{}", term)?,
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates (or, with `Mode::Parse`, prints) a closed term,
+/// returning `true` if there were no errors.
+fn run_term(
+    options: &Options,
+    name: &str,
+    term: Term,
+    position: FilePositionRange,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<bool> {
+    if options.mode == Mode::Parse {
+        match options.format {
+            Format::Json => {
+                let document = Document::UnnamedTerm {
+                    context: Context::empty(),
+                    term,
+                };
+                writeln!(out, "{}", document.to_json())?
+            }
+            format => writeln!(out, "{}", format_lambda(format, term))?,
+        }
+        return Ok(true);
+    }
+
+    match eval_lambda(options, term) {
+        Ok((result, count)) => {
+            match options.format {
+                Format::Json => {
+                    let document = Document::Evaluation {
+                        term: result,
+                        steps: count,
+                    };
+                    writeln!(out, "{}", document.to_json())?
+                }
+                format => {
+                    let printed = format_lambda(format, result);
+                    match count {
+                        Some(count) if options.mode == Mode::Count => {
+                            writeln!(out, "{} [{}]", printed, steps(count))?
+                        }
+                        _ => writeln!(out, "{}", printed)?,
+                    }
+                }
+            }
+            Ok(true)
+        }
+        Err(message) => {
+            report(
+                options,
+                name,
+                Diagnostic::Evaluation { message, position },
+                out,
+                err,
+            )?;
+            Ok(false)
+        }
+    }
+}
+
+/// Returns the result and, unless evaluating big-step, the number of steps taken.
+fn eval_lambda(options: &Options, term: Term) -> Result<(Term, Option<usize>), String> {
+    match options.mode {
+        Mode::BigStep => match big_step_evaluator::eval_with_fuel(term, options.fuel) {
+            Ok(value) => Ok((value, None)),
+            Err(big_step_evaluator::EvalErr::Stuck(stuck)) => Err(format!(
+                "Evaluation got stuck at `{}`.",
+                format_lambda(options.format, stuck)
+            )),
+            Err(big_step_evaluator::EvalErr::OutOfFuel) => {
+                Err(format!("Out of fuel after {}.", steps(options.fuel)))
+            }
+        },
+        Mode::SmallStep | Mode::Count | Mode::Parse => {
+            match eval_count_with_fuel(term, options.strategy, options.fuel) {
+                Ok((normal_form, count)) => Ok((normal_form, Some(count))),
+                Err(OutOfFuelErr { steps: count, .. }) => {
+                    Err(format!("Out of fuel after {}.", steps(count)))
+                }
+            }
+        }
+    }
+}
+
+fn report(
+    options: &Options,
+    name: &str,
+    diagnostic: Diagnostic,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<()> {
+    if options.format == Format::Json {
+        let document = match diagnostic {
+            Diagnostic::Parse(e) => Document::ParseErr(e),
+            Diagnostic::Naming(e) => Document::NamingErr(e),
+            Diagnostic::Evaluation { message, position } => {
+                Document::EvaluationErr { message, position }
+            }
+            Diagnostic::Import(message) => Document::ImportErr { message },
+        };
+        return writeln!(out, "{}", document.to_json());
+    }
+    match diagnostic {
+        Diagnostic::Parse(e) => writeln!(err, "{}: {}", name, e),
+        Diagnostic::Naming(e) => writeln!(err, "{}: {}", name, e),
+        Diagnostic::Evaluation { message, position } => {
+            writeln!(err, "{}: {}: {}", name, position.start, message)
+        }
+        Diagnostic::Import(message) => writeln!(err, "{}: Cannot import: {}", name, message),
+    }
+}
+
+fn format_lambda(format: Format, term: Term) -> String {
+    match format {
+        Format::Named | Format::Json => term.into_unpositioned_named().to_string(),
+        Format::Nameless => term.to_string(),
+    }
+}
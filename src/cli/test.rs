@@ -0,0 +1,361 @@
+use super::{parse_args, run_source, Command, Format, Mode, Options};
+use crate::chapter_11::Type;
+use crate::chapter_7::{evaluator::Strategy, json::Document, parse};
+use crate::json::{FromJson, Json, ToJson};
+use crate::repl::Language;
+
+fn args(strs: &[&str]) -> Vec<String> {
+    strs.iter().map(ToString::to_string).collect()
+}
+
+/// Returns stdout, stderr and whether there were no errors.
+fn run(options: &Options, src: &str) -> (String, String, bool) {
+    run_file(options, "test.tapl", src)
+}
+
+fn run_file(options: &Options, name: &str, src: &str) -> (String, String, bool) {
+    let mut out = vec![];
+    let mut err = vec![];
+    let ok = run_source(options, name, src, &mut out, &mut err).expect("Cannot write.");
+    (
+        String::from_utf8(out).expect("Invalid UTF-8."),
+        String::from_utf8(err).expect("Invalid UTF-8."),
+        ok,
+    )
+}
+
+#[test]
+fn no_args_starts_repl() {
+    assert_eq!(parse_args(&[]), Ok(Command::Repl));
+}
+
+#[test]
+fn options_are_parsed() {
+    let actual = parse_args(&args(&[
+        "--lang",
+        "chapter_4",
+        "a.tapl",
+        "--mode",
+        "big-step",
+        "--format",
+        "nameless",
+        "--strategy",
+        "cbn",
+        "--fuel",
+        "5",
+        "--gc-threshold",
+        "off",
+        "--exn-type",
+        "Bool",
+        "b.tapl",
+    ]));
+    let expected = Ok(Command::Run(Options {
+        files: args(&["a.tapl", "b.tapl"]),
+        language: Language::Arith,
+        mode: Mode::BigStep,
+        format: Format::Nameless,
+        strategy: Strategy::CallByName,
+        fuel: 5,
+        gc_threshold: None,
+        exn_type: Type::Bool,
+    }));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn bad_options_are_rejected() {
+    assert!(parse_args(&args(&["--mode", "medium-step", "a.tapl"])).is_err());
+    assert!(parse_args(&args(&["--fuel"])).is_err());
+    assert!(parse_args(&args(&["--gc-threshold", "never", "a.tapl"])).is_err());
+    assert!(parse_args(&args(&["--exn-type", "->", "a.tapl"])).is_err());
+    assert!(parse_args(&args(&["--verbose", "a.tapl"])).is_err());
+    assert!(parse_args(&args(&[
+        "--lang",
+        "chapter_4",
+        "--format",
+        "json",
+        "a.tapl"
+    ]))
+    .is_err());
+    assert!(parse_args(&args(&[
+        "--mode", "parse", "--format", "nameless", "a.tapl"
+    ]))
+    .is_err());
+    assert_eq!(parse_args(&args(&["--help", "a.tapl"])), Ok(Command::Help));
+}
+
+#[test]
+fn lambda_file() {
+    let src = r"
+        id = \x. x;
+        k = \x. \y. x;
+        k id id;
+        id;
+    ";
+    let actual = run(&Options::default(), src);
+    let expected = ("(\\x. x)\n(\\x. x)\n".to_string(), "".to_string(), true);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn lambda_modes_and_formats() {
+    let src = r"(\a. a \b. a) \b.b";
+    let count = Options {
+        mode: Mode::Count,
+        format: Format::Nameless,
+        ..Options::default()
+    };
+    assert_eq!(run(&count, src).0, "(\\. (\\. 0)) [2 steps]\n");
+    let big_step = Options {
+        mode: Mode::BigStep,
+        ..Options::default()
+    };
+    assert_eq!(run(&big_step, src).0, "(\\b. (\\b'. b'))\n");
+}
+
+#[test]
+fn lambda_errors() {
+    let options = Options {
+        fuel: 10,
+        ..Options::default()
+    };
+    let src = "x;\n(\\x. x x) \\x. x x;\n\\y. y";
+    let actual = run(&options, src);
+    let expected = (
+        "(\\y. y)\n".to_string(),
+        "test.tapl: 1:1: Cannot find `x` in context.\ntest.tapl: 2:2: Out of fuel after 10 steps.\n"
+            .to_string(),
+        false,
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn lambda_parse_error() {
+    let (out, err, ok) = run(&Options::default(), r"\x. x )");
+    assert_eq!(out, "");
+    assert_eq!(
+        err,
+        "test.tapl: 1:7: Expected `;` or end of input, but found `)`.\n"
+    );
+    assert!(!ok);
+}
+
+#[test]
+fn arith_file() {
+    let options = Options {
+        language: Language::Arith,
+        mode: Mode::Count,
+        ..Options::default()
+    };
+    let actual = run(&options, "succ 0; pred succ 0;\niszero true; succ");
+    let expected = (
+        "succ 0 [0 steps]\n0 [1 step]\n".to_string(),
+        "test.tapl: statement 3: Evaluation got stuck at `iszero true`.\ntest.tapl: statement 4: Cannot parse.\n"
+            .to_string(),
+        false,
+    );
+    assert_eq!(actual, expected);
+}
+
+fn documents(out: &str) -> Vec<Document> {
+    out.lines()
+        .map(|line| {
+            let json = Json::parse(line).expect("Invalid JSON.");
+            Document::from_json(&json).expect("Invalid document.")
+        })
+        .collect()
+}
+
+#[test]
+fn lambda_json_output() {
+    let options = Options {
+        format: Format::Json,
+        ..Options::default()
+    };
+    let (out, err, ok) = run(&options, r"id = \x. x; id id; y; (\x. x");
+    assert_eq!(err, "");
+    assert!(!ok);
+    let results = documents(&out);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].kind(), "parse_error");
+
+    let (out, err, ok) = run(&options, r"id = \x. x; id id; y");
+    assert_eq!(err, "");
+    assert!(!ok);
+    let results = documents(&out);
+    assert_eq!(results.len(), 2);
+    match &results[0] {
+        Document::Evaluation { term, steps } => {
+            assert_eq!(
+                term.clone().into_unpositioned_named().to_string(),
+                r"(\x. x)"
+            );
+            assert_eq!(*steps, Some(1));
+        }
+        other => panic!("Expected an evaluation, but found {:?}.", other),
+    }
+    assert_eq!(results[1].kind(), "naming_error");
+}
+
+#[test]
+fn lambda_parse_mode() {
+    let parse_mode = Options {
+        mode: Mode::Parse,
+        ..Options::default()
+    };
+    let src = r"id = \x. x; id (\y. y)";
+    let (out, err, ok) = run(&parse_mode, src);
+    assert_eq!(
+        (out.as_str(), err.as_str(), ok),
+        ("id = (\\x. x)\n(id (\\y. y))\n", "", true)
+    );
+
+    let json = Options {
+        format: Format::Json,
+        ..parse_mode
+    };
+    let (out, _, _) = run(&json, src);
+    match &documents(&out)[..] {
+        [Document::Statements(statements)] => assert_eq!(statements.len(), 2),
+        other => panic!("Expected statements, but found {:?}.", other),
+    }
+
+    // The exported statements can be run like the source they came from.
+    let actual = run_file(&Options::default(), "test.json", &out);
+    assert_eq!(actual, run(&Options::default(), src));
+}
+
+#[test]
+fn json_input() {
+    let term = parse(r"(\x. x) \y. y").expect("Cannot parse.");
+    let src = Document::NamedTerm(term).to_json().to_string();
+    let actual = run_file(&Options::default(), "test.json", &src);
+    assert_eq!(actual, ("(\\y. y)\n".to_string(), "".to_string(), true));
+
+    let src = Document::Statements(vec![])
+        .to_json()
+        .to_string()
+        .replace("1", "2");
+    let (out, err, ok) = run_file(&Options::default(), "test.json", &src);
+    assert_eq!(out, "");
+    assert_eq!(
+        err,
+        "test.json: Cannot import: /version: Unsupported version 2. Expected version 1.\n"
+    );
+    assert!(!ok);
+
+    let json = Options {
+        format: Format::Json,
+        ..Options::default()
+    };
+    let (out, err, ok) = run_file(&json, "test.json", &src);
+    match &documents(&out)[..] {
+        [Document::ImportErr { message }] => assert_eq!(
+            message,
+            "/version: Unsupported version 2. Expected version 1."
+        ),
+        other => panic!("Expected an import error, but found {:?}.", other),
+    }
+    assert_eq!(err, "");
+    assert!(!ok);
+}
+
+#[test]
+fn typed_file() {
+    let options = Options {
+        language: Language::Typed,
+        mode: Mode::Count,
+        fuel: 100,
+        ..Options::default()
+    };
+    let src = r"letrec double:Nat -> Nat = \n:Nat. if iszero n then 0 else succ (succ (double (pred n))) in
+        double 2";
+    let (out, err, ok) = run(&options, src);
+    assert_eq!(out, "4 : Nat [15 steps]\n");
+    assert_eq!(err, "");
+    assert!(ok);
+
+    let actual = run(&options, r"fix (\x:Nat. x)");
+    let expected = (
+        String::new(),
+        "test.tapl: 1:1: Out of fuel after 100 steps.\n".to_string(),
+        false,
+    );
+    assert_eq!(actual, expected);
+
+    let (_, err, ok) = run(&options, "iszero unit");
+    assert_eq!(
+        err,
+        "test.tapl: 1:8: Expected a term of type `Nat`, but found a term of type `Unit`.\n"
+    );
+    assert!(!ok);
+
+    let (out, err, ok) = run(&options, "succ (raise 4)");
+    assert_eq!(out, "");
+    assert_eq!(err, "test.tapl: 1:7: Uncaught exception `4`.\n");
+    assert!(!ok);
+
+    let (_, err, ok) = run(&options, "head[Unit] nil[Unit]");
+    assert_eq!(
+        err,
+        "test.tapl: 1:1: Evaluation got stuck at `(head[Unit] nil[Unit])`.\n"
+    );
+    assert!(!ok);
+    assert!(parse_args(&args(&[
+        "--lang",
+        "chapter_11",
+        "--mode",
+        "big-step",
+        "a.tapl"
+    ]))
+    .is_err());
+}
+
+#[test]
+fn typed_file_with_garbage_collection() {
+    let options = Options {
+        language: Language::Typed,
+        mode: Mode::Count,
+        gc_threshold: Some(2),
+        ..Options::default()
+    };
+    let src = r"letrec loop:Nat -> Nat = \n:Nat.
+            if iszero n then 0 else let _ = ref n in loop (pred n)
+        in loop 3";
+    let (out, err, ok) = run(&options, src);
+    assert_eq!(out, "0 : Nat [26 steps, 2 collections freed 2 locations]\n");
+    assert_eq!(err, "");
+    assert!(ok);
+
+    let options = Options {
+        gc_threshold: None,
+        ..options
+    };
+    let (out, _, _) = run(&options, src);
+    assert_eq!(out, "0 : Nat [26 steps]\n");
+}
+
+#[test]
+fn typed_file_with_exception_type() {
+    let options = Options {
+        language: Language::Typed,
+        ..Options::default()
+    };
+    let src = "try raise true with e => if e then 1 else 0";
+    let (_, err, ok) = run(&options, src);
+    assert_eq!(
+        err,
+        "test.tapl: 1:11: Expected a term of type `Nat`, but found a term of type `Bool`.\n"
+    );
+    assert!(!ok);
+
+    let options = Options {
+        exn_type: Type::Bool,
+        ..options
+    };
+    let (out, err, ok) = run(&options, src);
+    assert_eq!(out, "1 : Nat\n");
+    assert_eq!(err, "");
+    assert!(ok);
+}
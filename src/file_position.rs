@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct FilePositionRange {
     pub start: FilePosition,
@@ -18,3 +20,10 @@ pub struct FilePosition {
 pub trait Position {
     fn position(self) -> FilePositionRange;
 }
+
+impl fmt::Display for FilePosition {
+    /// Columns are displayed one-based, as most editors do.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column + 1)
+    }
+}
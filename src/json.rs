@@ -0,0 +1,508 @@
+#[cfg(test)]
+mod test;
+
+use crate::file_position::{FilePosition, FilePositionRange};
+use std::fmt;
+
+/// A JSON value. This is just enough JSON to import and
+/// export terms (see `chapter_7::json`) without any dependencies.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Fields are kept in insertion order, so printed output is deterministic.
+    Object(Vec<(String, Json)>),
+}
+
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+pub trait FromJson: Sized {
+    fn from_json(json: &Json) -> Result<Self, FromJsonErr>;
+}
+
+/// An error describing why a JSON value does not encode the expected type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FromJsonErr {
+    /// A JSON Pointer (RFC 6901) to the offending value, e.g., `/term/body`.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl FromJsonErr {
+    pub fn new(message: String) -> FromJsonErr {
+        FromJsonErr {
+            pointer: String::new(),
+            message,
+        }
+    }
+
+    /// Prepends `key` to the pointer, for errors that occurred inside a field or element.
+    pub fn within(mut self, key: &str) -> FromJsonErr {
+        let escaped = key.replace('~', "~0").replace('/', "~1");
+        self.pointer = format!("/{}{}", escaped, self.pointer);
+        self
+    }
+}
+
+impl fmt::Display for FromJsonErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.pointer, self.message)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonParseErr {
+    /// The byte index at which parsing failed.
+    pub index: usize,
+    pub message: String,
+}
+
+impl fmt::Display for JsonParseErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid JSON at byte {}: {}", self.index, self.message)
+    }
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    pub fn parse(src: &str) -> Result<Json, JsonParseErr> {
+        let mut parser = Parser { src, index: 0 };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.index == src.len() {
+            Ok(value)
+        } else {
+            Err(parser.err("Expected end of input."))
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        if let Json::Object(fields) = self {
+            fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the field `key`, or an error if `self` is not
+    /// an object or has no such field.
+    pub fn field(&self, key: &str) -> Result<&Json, FromJsonErr> {
+        match self {
+            Json::Object(_) => self
+                .get(key)
+                .ok_or_else(|| FromJsonErr::new(format!("Missing field `{}`.", key))),
+            _ => Err(FromJsonErr::new("Expected an object.".to_string())),
+        }
+    }
+
+    /// Decodes the field `key`, adding `key` to the pointer of any error.
+    pub fn decode_field<T: FromJson>(&self, key: &str) -> Result<T, FromJsonErr> {
+        self.field(key)
+            .and_then(T::from_json)
+            .map_err(|err| err.within(key))
+    }
+
+    pub fn as_str(&self) -> Result<&str, FromJsonErr> {
+        match self {
+            Json::String(s) => Ok(s),
+            _ => Err(FromJsonErr::new("Expected a string.".to_string())),
+        }
+    }
+
+    pub fn as_usize(&self) -> Result<usize, FromJsonErr> {
+        match self {
+            Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 && *n <= MAX_SAFE_INTEGER => {
+                Ok(*n as usize)
+            }
+            _ => Err(FromJsonErr::new(
+                "Expected a non-negative integer.".to_string(),
+            )),
+        }
+    }
+
+    pub fn as_array(&self) -> Result<&[Json], FromJsonErr> {
+        match self {
+            Json::Array(elements) => Ok(elements),
+            _ => Err(FromJsonErr::new("Expected an array.".to_string())),
+        }
+    }
+}
+
+/// The largest integer that every JSON implementation can represent exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+impl ToJson for usize {
+    fn to_json(&self) -> Json {
+        Json::Number(*self as f64)
+    }
+}
+
+impl FromJson for usize {
+    fn from_json(json: &Json) -> Result<usize, FromJsonErr> {
+        json.as_usize()
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> Json {
+        Json::String(self.clone())
+    }
+}
+
+impl FromJson for String {
+    fn from_json(json: &Json) -> Result<String, FromJsonErr> {
+        json.as_str().map(ToString::to_string)
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Json {
+        Json::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(json: &Json) -> Result<Vec<T>, FromJsonErr> {
+        json.as_array()?
+            .iter()
+            .enumerate()
+            .map(|(i, element)| T::from_json(element).map_err(|err| err.within(&i.to_string())))
+            .collect()
+    }
+}
+
+impl ToJson for FilePosition {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("line", self.line.to_json()),
+            ("column", self.column.to_json()),
+            ("index", self.index.to_json()),
+        ])
+    }
+}
+
+impl FromJson for FilePosition {
+    fn from_json(json: &Json) -> Result<FilePosition, FromJsonErr> {
+        Ok(FilePosition {
+            line: json.decode_field("line")?,
+            column: json.decode_field("column")?,
+            index: json.decode_field("index")?,
+        })
+    }
+}
+
+impl ToJson for FilePositionRange {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("start", self.start.to_json()),
+            ("end", self.end.to_json()),
+        ])
+    }
+}
+
+impl FromJson for FilePositionRange {
+    fn from_json(json: &Json) -> Result<FilePositionRange, FromJsonErr> {
+        Ok(FilePositionRange {
+            start: json.decode_field("start")?,
+            end: json.decode_field("end")?,
+        })
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) => {
+                if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER {
+                    write!(f, "{}", *n as i64)
+                } else {
+                    write!(f, "{}", n)
+                }
+            }
+            Json::String(s) => write_string(f, s),
+            Json::Array(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn err(&self, message: &str) -> JsonParseErr {
+        JsonParseErr {
+            index: self.index,
+            message: message.to_string(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.index..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+        self.index += rest.len() - trimmed.len();
+    }
+
+    fn consume_literal(&mut self, literal: &str) -> bool {
+        if self.rest().starts_with(literal) {
+            self.index += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), JsonParseErr> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.index += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.err(&format!("Expected `{}`.", c)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, JsonParseErr> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('n') if self.consume_literal("null") => Ok(Json::Null),
+            Some('t') if self.consume_literal("true") => Ok(Json::Bool(true)),
+            Some('f') if self.consume_literal("false") => Ok(Json::Bool(false)),
+            Some('"') => self.parse_string().map(Json::String),
+            Some('[') => self.parse_array(),
+            Some('{') => self.parse_object(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            _ => Err(self.err("Expected a value.")),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, JsonParseErr> {
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or_else(|| self.rest().len());
+        let src = &self.rest()[..len];
+        if !is_number(src) {
+            return Err(self.err("Invalid number."));
+        }
+        match src.parse() {
+            Ok(n) => {
+                self.index += len;
+                Ok(Json::Number(n))
+            }
+            Err(_) => Err(self.err("Invalid number.")),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonParseErr> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.err("Unterminated string."))?;
+            self.index += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.err("Unterminated string."))?;
+                    self.index += escaped.len_utf8();
+                    match escaped {
+                        '"' => out.push('"'),
+                        '\\' => out.push('\\'),
+                        '/' => out.push('/'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'u' => out.push(self.parse_unicode_escape()?),
+                        _ => return Err(self.err("Invalid escape sequence.")),
+                    }
+                }
+                c if (c as u32) < 0x20 => return Err(self.err("Unescaped control character.")),
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonParseErr> {
+        let high = self.parse_hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !self.consume_literal("\\u") {
+                return Err(self.err("Expected a low surrogate."));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.err("Invalid low surrogate."));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        std::char::from_u32(code).ok_or_else(|| self.err("Invalid code point."))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonParseErr> {
+        let digits = self
+            .rest()
+            .get(..4)
+            .ok_or_else(|| self.err("Expected 4 hex digits."))?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(self.err("Expected 4 hex digits."));
+        }
+        let code =
+            u32::from_str_radix(digits, 16).map_err(|_| self.err("Expected 4 hex digits."))?;
+        self.index += 4;
+        Ok(code)
+    }
+
+    fn parse_array(&mut self) -> Result<Json, JsonParseErr> {
+        self.expect('[')?;
+        let mut elements = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.index += 1;
+            return Ok(Json::Array(elements));
+        }
+        loop {
+            elements.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.index += 1,
+                Some(']') => {
+                    self.index += 1;
+                    return Ok(Json::Array(elements));
+                }
+                _ => return Err(self.err("Expected `,` or `]`.")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, JsonParseErr> {
+        self.expect('{')?;
+        let mut fields = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.index += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            fields.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.index += 1,
+                Some('}') => {
+                    self.index += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.err("Expected `,` or `}`.")),
+            }
+        }
+    }
+}
+
+/// Whether `src` is a number in the grammar of RFC 8259:
+/// an optional `-`, an integer part without leading zeros,
+/// then an optional fraction and an optional exponent, each with at least one digit.
+fn is_number(src: &str) -> bool {
+    fn digits(src: &str) -> (&str, &str) {
+        let len = src.find(|c: char| !c.is_ascii_digit()).unwrap_or(src.len());
+        src.split_at(len)
+    }
+
+    let src = src.strip_prefix('-').unwrap_or(src);
+    let (int, rest) = digits(src);
+    if int.is_empty() || (int.len() > 1 && int.starts_with('0')) {
+        return false;
+    }
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => match digits(fraction) {
+            ("", _) => return false,
+            (_, rest) => rest,
+        },
+        None => rest,
+    };
+    match rest.strip_prefix(|c| c == 'e' || c == 'E') {
+        Some(exponent) => {
+            let exponent = exponent
+                .strip_prefix(|c| c == '+' || c == '-')
+                .unwrap_or(exponent);
+            matches!(digits(exponent), (digits, "") if !digits.is_empty())
+        }
+        None => rest.is_empty(),
+    }
+}
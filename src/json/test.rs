@@ -0,0 +1,108 @@
+use super::{FromJson, Json};
+
+#[test]
+fn parse_and_print() {
+    let src = r#" { "a" : [1, -2.5, true, false, null], "b": {"c": "d"}, "e": [] } "#;
+    let json = Json::parse(src).expect("Cannot parse.");
+    assert_eq!(
+        json,
+        Json::object(vec![
+            (
+                "a",
+                Json::Array(vec![
+                    Json::Number(1.0),
+                    Json::Number(-2.5),
+                    Json::Bool(true),
+                    Json::Bool(false),
+                    Json::Null,
+                ])
+            ),
+            (
+                "b",
+                Json::object(vec![("c", Json::String("d".to_string()))])
+            ),
+            ("e", Json::Array(vec![])),
+        ])
+    );
+    assert_eq!(
+        json.to_string(),
+        r#"{"a":[1,-2.5,true,false,null],"b":{"c":"d"},"e":[]}"#
+    );
+}
+
+#[test]
+fn string_escapes_round_trip() {
+    let s = "\\x. \"quoted\"\n\ttab \u{1} \u{1F600}";
+    let printed = Json::String(s.to_string()).to_string();
+    assert_eq!(
+        printed,
+        "\"\\\\x. \\\"quoted\\\"\\n\\ttab \\u0001 \u{1F600}\""
+    );
+    assert_eq!(Json::parse(&printed), Ok(Json::String(s.to_string())));
+    assert_eq!(
+        Json::parse(r#""\ud83d\ude00 \u00e9 \/""#),
+        Ok(Json::String("\u{1F600} \u{e9} /".to_string()))
+    );
+}
+
+#[test]
+fn invalid_json() {
+    const SRCS: [&str; 17] = [
+        "",
+        "[1,]",
+        "{\"a\" 1}",
+        "\"abc",
+        "tru",
+        "[1] 2",
+        "{1: 2}",
+        "\"\\q\"",
+        "\"\\u+041\"",
+        "\"\\u-041\"",
+        "01",
+        "-01",
+        "1.",
+        ".5",
+        "1e",
+        "1e+",
+        "+1",
+    ];
+    for src in &SRCS {
+        assert!(Json::parse(src).is_err(), "Parsed {:?}.", src);
+    }
+    assert_eq!(Json::parse("[1, 2").map_err(|err| err.index), Err(5));
+}
+
+#[test]
+fn numbers_follow_rfc_8259() {
+    for (src, expected) in &[
+        ("0", 0.0),
+        ("-0", 0.0),
+        ("10", 10.0),
+        ("1.5", 1.5),
+        ("-2.25e1", -22.5),
+        ("1E+2", 100.0),
+        ("5e-1", 0.5),
+    ] {
+        assert_eq!(Json::parse(src), Ok(Json::Number(*expected)), "{}", src);
+    }
+    assert_eq!(
+        Json::parse("\"\\u0041\""),
+        Ok(Json::String("A".to_string()))
+    );
+}
+
+#[test]
+fn decode_errors_have_pointers() {
+    let json = Json::parse(r#"{"a": {"b/c": [0, 1, -1]}}"#).expect("Cannot parse.");
+    let err = json
+        .field("a")
+        .and_then(|a| a.decode_field::<Vec<usize>>("b/c"))
+        .expect_err("Decoded a negative number.");
+    assert_eq!(err.pointer, "/b~1c/2");
+    assert_eq!(err.to_string(), "/b~1c/2: Expected a non-negative integer.");
+    assert_eq!(
+        json.field("z").map(|_| ()).map_err(|err| err.message),
+        Err("Missing field `z`.".to_string())
+    );
+    assert!(usize::from_json(&Json::Number(1.5)).is_err());
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod test;
+
+use std::fmt;
+
+/// Escapes the characters that are special in LaTeX text mode.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '{' | '}' | '$' | '&' | '#' | '_' | '%' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Typesets an identifier in math mode.
+///
+/// Single letters are left as they are, a trailing number becomes a subscript
+/// (`x1` becomes `x_{1}`), trailing primes are kept, and anything else is set
+/// in `\mathit` so that multi-letter names are not spaced as products.
+pub fn ident(name: &str) -> String {
+    let base = name.trim_end_matches('\'');
+    let primes = &name[base.len()..];
+    let stem = base.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = &base[stem.len()..];
+
+    let mut typeset = if stem.chars().count() == 1 && stem.chars().all(|c| c.is_ascii_alphabetic())
+    {
+        stem.to_string()
+    } else {
+        format!(r"\mathit{{{}}}", escape(stem))
+    };
+    if !number.is_empty() {
+        typeset.push_str(&format!("_{{{}}}", number));
+    }
+    typeset.push_str(primes);
+    typeset
+}
+
+/// The LaTeX package used to typeset derivation trees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// `\inferrule*` from `mathpartir`, to be placed in a `mathpar` environment.
+    Mathpartir,
+    /// A `prooftree` environment from `bussproofs`.
+    Bussproofs,
+}
+
+/// A derivation tree, whose conclusion and premises are in math mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Derivation {
+    /// The name of the last rule applied, e.g., `E-App1`. It is escaped when rendered.
+    pub rule: String,
+    pub premises: Vec<Derivation>,
+    pub conclusion: String,
+}
+
+impl Derivation {
+    pub fn axiom(rule: &str, conclusion: String) -> Derivation {
+        Derivation {
+            rule: rule.to_string(),
+            premises: vec![],
+            conclusion,
+        }
+    }
+
+    /// Fails only for `Style::Bussproofs`, which cannot typeset a rule
+    /// with more than five premises.
+    pub fn to_latex(&self, style: Style) -> Result<String, TooManyPremisesErr> {
+        match style {
+            Style::Mathpartir => Ok(self.to_mathpartir(0)),
+            Style::Bussproofs => {
+                let mut lines = vec![r"\begin{prooftree}".to_string()];
+                self.push_bussproofs(&mut lines)?;
+                lines.push(r"\end{prooftree}".to_string());
+                Ok(lines.join("\n"))
+            }
+        }
+    }
+
+    fn to_mathpartir(&self, depth: usize) -> String {
+        let indent = "  ".repeat(depth + 1);
+        let premises = self
+            .premises
+            .iter()
+            .map(|premise| format!("{}{}", indent, premise.to_mathpartir(depth + 1)))
+            .collect::<Vec<_>>()
+            .join(" \\\\\n");
+        let premises = if premises.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}\n{}", premises, "  ".repeat(depth))
+        };
+        format!(
+            r"\inferrule*[right=\textsc{{{}}}]{{{}}}{{{}}}",
+            escape(&self.rule),
+            premises,
+            self.conclusion
+        )
+    }
+
+    fn push_bussproofs(&self, lines: &mut Vec<String>) -> Result<(), TooManyPremisesErr> {
+        if self.premises.is_empty() {
+            lines.push(r"\AxiomC{}".to_string());
+        }
+        for premise in &self.premises {
+            premise.push_bussproofs(lines)?;
+        }
+        let inference = match self.premises.len() {
+            0 | 1 => "Unary",
+            2 => "Binary",
+            3 => "Trinary",
+            4 => "Quaternary",
+            5 => "Quinary",
+            premises => {
+                return Err(TooManyPremisesErr {
+                    rule: self.rule.clone(),
+                    premises,
+                })
+            }
+        };
+        lines.push(format!(
+            r"\RightLabel{{\scriptsize\textsc{{{}}}}}",
+            escape(&self.rule)
+        ));
+        lines.push(format!(r"\{}InfC{{${}$}}", inference, self.conclusion));
+        Ok(())
+    }
+}
+
+/// A rule with more premises than `bussproofs` can typeset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TooManyPremisesErr {
+    pub rule: String,
+    pub premises: usize,
+}
+
+impl fmt::Display for TooManyPremisesErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` has {} premises, but bussproofs supports at most five.",
+            self.rule, self.premises
+        )
+    }
+}
+
+/// Typesets a sequence of evaluation steps in an `align*` environment,
+/// one step per line.
+///
+/// Each term is given as a list of chunks, separated by spaces,
+/// that may be placed on separate lines. Chunks are packed onto lines of
+/// at most `width` characters of plain text, and continuation lines are indented.
+pub fn align_steps(terms: &[Vec<Chunk>], width: usize) -> String {
+    let mut body = vec![];
+    for (i, chunks) in terms.iter().enumerate() {
+        let prefix = if i == 0 { "&" } else { r"\longrightarrow{} &" };
+        let mut packed = pack(chunks, width).into_iter();
+        if let Some(first) = packed.next() {
+            body.push(format!("{}{}", prefix, first));
+        }
+        for rest in packed {
+            body.push(format!(r"&\quad {}", rest));
+        }
+    }
+    let mut lines = vec![r"\begin{align*}".to_string()];
+    if !body.is_empty() {
+        lines.push(body.join(" \\\\\n"));
+    }
+    lines.push(r"\end{align*}".to_string());
+    lines.join("\n")
+}
+
+/// A piece of a typeset term, with the plain text it stands for,
+/// which is used to measure its width.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chunk {
+    pub latex: String,
+    pub plain: String,
+}
+
+fn pack(chunks: &[Chunk], width: usize) -> Vec<String> {
+    let mut lines: Vec<(String, usize)> = vec![];
+    for chunk in chunks {
+        let len = chunk.plain.chars().count();
+        match lines.last_mut() {
+            Some((latex, line_len)) if *line_len + 1 + len <= width => {
+                latex.push_str(r"\;");
+                latex.push_str(&chunk.latex);
+                *line_len += 1 + len;
+            }
+            _ => lines.push((chunk.latex.clone(), len)),
+        }
+    }
+    lines.into_iter().map(|(latex, _)| latex).collect()
+}
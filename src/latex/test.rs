@@ -0,0 +1,98 @@
+use super::{align_steps, escape, ident, Chunk, Derivation, Style};
+
+fn chunk(s: &str) -> Chunk {
+    Chunk {
+        latex: s.to_string(),
+        plain: s.to_string(),
+    }
+}
+
+#[test]
+fn special_characters_are_escaped() {
+    assert_eq!(
+        escape(r"a_b {50%} $1 & #2 \ ~^"),
+        r"a\_b \{50\%\} \$1 \& \#2 \textbackslash{} \textasciitilde{}\textasciicircum{}"
+    );
+}
+
+#[test]
+fn identifiers() {
+    assert_eq!(ident("x"), "x");
+    assert_eq!(ident("x1'"), "x_{1}'");
+    assert_eq!(ident("succ"), r"\mathit{succ}");
+    assert_eq!(ident("is_zero2"), r"\mathit{is\_zero}_{2}");
+}
+
+fn example() -> Derivation {
+    Derivation {
+        rule: "E-App1".to_string(),
+        premises: vec![Derivation::axiom("E-AppAbs", "a".to_string())],
+        conclusion: "b".to_string(),
+    }
+}
+
+#[test]
+fn mathpartir() {
+    let expected = r"\inferrule*[right=\textsc{E-App1}]{
+  \inferrule*[right=\textsc{E-AppAbs}]{}{a}
+}{b}";
+    assert_eq!(
+        example()
+            .to_latex(Style::Mathpartir)
+            .expect("Cannot typeset."),
+        expected
+    );
+}
+
+#[test]
+fn bussproofs() {
+    let expected = r"\begin{prooftree}
+\AxiomC{}
+\RightLabel{\scriptsize\textsc{E-AppAbs}}
+\UnaryInfC{$a$}
+\RightLabel{\scriptsize\textsc{E-App1}}
+\UnaryInfC{$b$}
+\end{prooftree}";
+    assert_eq!(
+        example()
+            .to_latex(Style::Bussproofs)
+            .expect("Cannot typeset."),
+        expected
+    );
+}
+
+#[test]
+fn bussproofs_rejects_more_than_five_premises() {
+    let derivation = Derivation {
+        rule: "T-Record".to_string(),
+        premises: vec![Derivation::axiom("T-Zero", "a".to_string()); 6],
+        conclusion: "b".to_string(),
+    };
+    let err = derivation
+        .to_latex(Style::Bussproofs)
+        .expect_err("bussproofs has no rule with six premises.");
+    assert_eq!(
+        err.to_string(),
+        "`T-Record` has 6 premises, but bussproofs supports at most five."
+    );
+    assert!(derivation.to_latex(Style::Mathpartir).is_ok());
+}
+
+#[test]
+fn no_steps_make_an_empty_alignment() {
+    assert_eq!(align_steps(&[], 60), "\\begin{align*}\n\\end{align*}");
+}
+
+#[test]
+fn long_terms_are_broken() {
+    let terms = vec![
+        vec![chunk("aaaa"), chunk("bb"), chunk("cccc")],
+        vec![chunk("d")],
+    ];
+    let expected = r"\begin{align*}
+&aaaa\;bb \\
+&\quad cccc \\
+\longrightarrow{} &d
+\end{align*}";
+    assert_eq!(align_steps(&terms, 8), expected);
+}
@@ -1,3 +1,10 @@
+pub mod chapter_10;
+pub mod chapter_11;
+pub mod chapter_15;
 pub mod chapter_4;
 pub mod chapter_7;
+pub mod cli;
 pub mod file_position;
+pub mod json;
+pub mod latex;
+pub mod repl;
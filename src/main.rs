@@ -1,16 +1,49 @@
-use std::convert::TryFrom;
-use tapl_rust::chapter_7::*;
-use term::unnamed::Term as UnnamedTerm;
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process;
+use tapl_rust::cli::{self, Command};
+use tapl_rust::repl::Session;
 
 fn main() {
-    let src = r#"(\a. a \b. a) \b.b"#;
-    let unnamed =
-        UnnamedTerm::try_from(parse(src).expect("Cannot parse.")).expect("Cannot remove names.");
-    println!("Unnamed: {}", unnamed.clone().into_unpositioned_named());
-    let res1 = eval1(unnamed.clone()).expect("Cannot eval 1");
-    println!("Eval1: {}", res1.clone().into_unpositioned_named());
-    let res2 = eval1(res1).expect("Cannot eval 2");
-    println!("Eval2: {}", res2.clone().into_unpositioned_named());
-    let (normal_form, count) = eval_count(unnamed);
-    println!("Eval ({}): {}", count, normal_form);
+    let args: Vec<String> = env::args().skip(1).collect();
+    match cli::parse_args(&args) {
+        Ok(Command::Repl) => repl(),
+        Ok(Command::Help) => println!("{}", cli::USAGE),
+        Ok(Command::Run(options)) => {
+            let stdout = io::stdout();
+            let stderr = io::stderr();
+            let ok = cli::run(&options, &mut stdout.lock(), &mut stderr.lock())
+                .expect("Cannot write output.");
+            if !ok {
+                process::exit(1);
+            }
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+fn repl() {
+    let mut session = Session::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("{}", session.prompt());
+        io::stdout().flush().expect("Cannot flush stdout.");
+
+        let line = match lines.next() {
+            Some(line) => line.expect("Cannot read stdin."),
+            None => break,
+        };
+        let output = session.handle_line(&line);
+        if !output.is_empty() {
+            println!("{}", output);
+        }
+        if session.has_quit() {
+            break;
+        }
+    }
 }
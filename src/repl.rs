@@ -0,0 +1,838 @@
+#[cfg(test)]
+mod test;
+
+use crate::chapter_11::{
+    self,
+    store::Store,
+    type_checker::{TypeContext, DEFAULT_EXN_TYPE},
+    Outcome,
+};
+use crate::chapter_15::{self, subtyping};
+use crate::chapter_4;
+use crate::chapter_7::term::unnamed::Context;
+use crate::chapter_7::{
+    debugger::{Breakpoint, Debugger, Stop},
+    dot::{self, ReductionGraph},
+    evaluator::{eval_count_with_fuel, OutOfFuelErr, Strategy},
+    latex, parse,
+    parser::{parse_at, parse_statements_at},
+    statement::{Definitions, Statement},
+    term::unnamed::Term,
+    trace,
+};
+use crate::file_position::{FilePosition, FilePositionRange};
+use crate::latex::Style;
+use std::cell::Cell;
+use std::fs;
+
+pub const DEFAULT_FUEL: usize = 10_000;
+
+/// The number of live locations at which the store of a typed term is collected,
+/// unless changed with `:gc`.
+pub const DEFAULT_GC_THRESHOLD: usize = 1_000;
+
+/// The maximum number of terms shown by `:graph`.
+const GRAPH_LIMIT: usize = 50;
+
+/// The width, in characters, at which `:latex` breaks long terms.
+const LATEX_WIDTH: usize = 60;
+
+const HELP: &str = r"Enter a term to evaluate it, or `name = term` to define a name.
+Separate multiple statements with `;`.
+Write `letrec f = term in term` for a recursive definition of `f`.
+
+Commands:
+  :step <term>                    Show the first reduction step of a term.
+  :trace <term>                   Show every reduction step of a term.
+  :strategy [cbv|cbn|normal]      Show or set the evaluation strategy.
+  :lang [arith|lambda|typed]      Show or set the language.
+  :fuel [<steps>]                 Show or set the maximum number of steps.
+  :gc [<locations>|off]           Show or set when the store of a typed term is collected.
+  :exn [<type>]                   Show or set the type of the values that `raise` takes.
+  :subtype <type> <: <type>       Show the derivation of a subtyping, or why it fails.
+  :load <file>                    Run every statement in a file.
+  :defs                           List the definitions made so far.
+  :debug <term>                   Step through the call-by-value evaluation of a term.
+  :tree <term>                    Print the syntax tree of a term as Graphviz DOT.
+  :graph <term>                   Print the reduction graph of a term as Graphviz DOT.
+  :latex <term>                   Print every reduction step of a term as LaTeX.
+  :derive <term>                  Print the derivation of the first step of a term as LaTeX.
+  :help                           Show this message.
+  :quit                           Exit.";
+
+const DEBUG_HELP: &str = r"Debugger commands:
+  :step, :s                       Take a single step.
+  :next, :n                       Reduce the active redex to a value in one go.
+  :continue, :c                   Take steps until a breakpoint or the normal form is reached.
+  :break <name>                   Stop whenever the definition of <name> is applied.
+  :break <line>:<col>-<line>:<col>
+                                  Stop whenever the active redex lies within a source range.
+  :delete <n>                     Remove breakpoint <n>.
+  :breakpoints                    List breakpoints.
+  :where                          Show the active redex.
+  :abort                          Stop debugging.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    /// The arithmetic expressions of Chapter 4.
+    Arith,
+    /// The untyped lambda calculus of Chapter 7.
+    Lambda,
+    /// The typed language of `chapter_11`, with the subtyping of `chapter_15`.
+    Typed,
+}
+
+impl Language {
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::Arith => "arith",
+            Language::Lambda => "lambda",
+            Language::Typed => "typed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Language> {
+        match name {
+            "arith" | "chapter_4" => Some(Language::Arith),
+            "lambda" | "chapter_7" => Some(Language::Lambda),
+            "typed" | "chapter_11" => Some(Language::Typed),
+            _ => None,
+        }
+    }
+}
+
+/// The state of an interactive session.
+///
+/// Input is fed to the session one line at a time.
+/// Lines with unbalanced parentheses are buffered until
+/// the parentheses are balanced.
+#[derive(Clone, Debug)]
+pub struct Session {
+    language: Language,
+    strategy: Strategy,
+    fuel: usize,
+    gc_threshold: Option<usize>,
+    exn_type: chapter_11::Type,
+    defs: Definitions,
+    pending: String,
+    debugger: Option<Debugger>,
+    /// The index the characters of the next lambda source are numbered from.
+    next_index: Cell<usize>,
+    has_quit: bool,
+}
+
+impl Default for Session {
+    fn default() -> Session {
+        Session::new()
+    }
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session {
+            language: Language::Lambda,
+            strategy: Strategy::CallByValue,
+            fuel: DEFAULT_FUEL,
+            gc_threshold: Some(DEFAULT_GC_THRESHOLD),
+            exn_type: DEFAULT_EXN_TYPE,
+            defs: Definitions::empty(),
+            pending: String::new(),
+            debugger: None,
+            next_index: Cell::new(0),
+            has_quit: false,
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    pub fn definitions(&self) -> &Definitions {
+        &self.defs
+    }
+
+    pub fn has_quit(&self) -> bool {
+        self.has_quit
+    }
+
+    pub fn is_debugging(&self) -> bool {
+        self.debugger.is_some()
+    }
+
+    pub fn prompt(&self) -> String {
+        if self.pending.is_empty() && self.is_debugging() {
+            "debug> ".to_string()
+        } else if self.pending.is_empty() {
+            format!("{}> ", self.language.name())
+        } else {
+            format!("{}| ", " ".repeat(self.language.name().len()))
+        }
+    }
+
+    /// Handles a line of input, returning the text to print.
+    /// The returned text is empty if the line was buffered.
+    pub fn handle_line(&mut self, line: &str) -> String {
+        self.pending.push_str(line);
+        self.pending.push('\n');
+        if paren_depth(&self.pending) > 0 {
+            return String::new();
+        }
+        let input = std::mem::take(&mut self.pending);
+        self.handle_input(input.trim())
+    }
+
+    fn handle_input(&mut self, input: &str) -> String {
+        if input.is_empty() {
+            String::new()
+        } else if let Some(command) = input.strip_prefix(':') {
+            let (name, arg) = split_command(command);
+            if self.is_debugging() {
+                if let Some(output) = self.handle_debug_command(name, arg) {
+                    return output;
+                }
+            }
+            self.handle_command(name, arg)
+        } else if self.is_debugging() {
+            "Cannot evaluate terms while debugging. Type `:abort` to stop debugging.".to_string()
+        } else {
+            self.run(input)
+        }
+    }
+
+    /// Returns `None` if `name` is not a debugger command.
+    fn handle_debug_command(&mut self, name: &str, arg: &str) -> Option<String> {
+        let debugger = self.debugger.as_mut()?;
+        let output = match name {
+            "step" | "s" if arg.is_empty() => match debugger.step() {
+                Some(_) => describe_debugger(debugger),
+                None => "The term is already in normal form.".to_string(),
+            },
+            "next" | "n" => {
+                let stop = debugger.step_over();
+                format!("{}\n{}", describe_stop(&stop), describe_debugger(debugger))
+            }
+            "continue" | "c" => {
+                let stop = debugger.resume();
+                format!("{}\n{}", describe_stop(&stop), describe_debugger(debugger))
+            }
+            "break" => {
+                let breakpoint = match parse_range(arg) {
+                    Some(range) => Some(Breakpoint::Range(range)),
+                    None => Breakpoint::def(&self.defs, arg),
+                };
+                match breakpoint {
+                    Some(breakpoint) => {
+                        let description = describe_breakpoint(&breakpoint);
+                        let index = debugger.add_breakpoint(breakpoint);
+                        format!("Breakpoint {}: {}", index, description)
+                    }
+                    None => format!(
+                        "Expected a defined name or a range like `1:1-1:10`, but found `{}`.",
+                        arg
+                    ),
+                }
+            }
+            "delete" => match arg.parse().ok().and_then(|i| debugger.remove_breakpoint(i)) {
+                Some(breakpoint) => format!("Deleted {}.", describe_breakpoint(&breakpoint)),
+                None => format!("No breakpoint `{}`.", arg),
+            },
+            "breakpoints" => debugger
+                .breakpoints()
+                .map(|(i, breakpoint)| format!("{}: {}", i, describe_breakpoint(breakpoint)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "where" => describe_debugger(debugger),
+            "abort" => {
+                self.debugger = None;
+                "Stopped debugging.".to_string()
+            }
+            "help" => DEBUG_HELP.to_string(),
+            _ => return None,
+        };
+        Some(output)
+    }
+
+    fn debug(&mut self, src: &str) -> String {
+        if self.language != Language::Lambda {
+            return "The debugger only supports the lambda language.".to_string();
+        }
+        match self.resolve_lambda(src) {
+            Ok(term) => {
+                let debugger = Debugger::new(term, self.fuel);
+                let output = describe_debugger(&debugger);
+                self.debugger = Some(debugger);
+                output
+            }
+            Err(message) => message,
+        }
+    }
+
+    fn handle_command(&mut self, name: &str, arg: &str) -> String {
+        match name {
+            "step" => self.step(arg),
+            "trace" => self.trace(arg),
+            "strategy" => self.set_strategy(arg),
+            "lang" => self.set_language(arg),
+            "fuel" => self.set_fuel(arg),
+            "gc" => self.set_gc_threshold(arg),
+            "exn" => self.set_exn_type(arg),
+            "subtype" => subtype(arg),
+            "load" => self.load(arg),
+            "defs" => self.list_defs(),
+            "debug" => self.debug(arg),
+            "tree" => self.tree(arg),
+            "graph" => self.graph(arg),
+            "latex" => self.latex(arg),
+            "derive" => self.derive(arg),
+            "help" => HELP.to_string(),
+            "quit" | "q" => {
+                self.has_quit = true;
+                String::new()
+            }
+            _ => format!(
+                "Unknown command `:{}`. Type `:help` for a list of commands.",
+                name
+            ),
+        }
+    }
+
+    /// Runs every statement in `src`, returning the output of each.
+    pub fn run(&mut self, src: &str) -> String {
+        match self.language {
+            Language::Arith => src
+                .split(';')
+                .map(str::trim)
+                .filter(|statement| !statement.is_empty())
+                .map(|statement| self.run_arith(statement))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Language::Lambda => match parse_statements_at(src, self.claim_index(src)) {
+                Ok(statements) => statements
+                    .into_iter()
+                    .filter_map(|statement| self.run_lambda(statement))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Err(err) => format!("Parse error: {}", err),
+            },
+            Language::Typed => self.run_typed(src),
+        }
+    }
+
+    fn run_arith(&self, src: &str) -> String {
+        match chapter_4::parse(src) {
+            Some(term) => {
+                let (result, count) = chapter_4::eval_count(term);
+                if chapter_4::evaluator::is_val(&result) {
+                    format!("{} [{}]", result, steps(count))
+                } else {
+                    format!("Stuck: {} [{}]", result, steps(count))
+                }
+            }
+            None => format!("Parse error: Cannot parse `{}`.", src),
+        }
+    }
+
+    fn run_lambda(&mut self, statement: Statement) -> Option<String> {
+        match statement {
+            Statement::Def(def) => {
+                let name = def.name.name.clone();
+                match self.defs.define(def) {
+                    Ok(()) => None,
+                    Err(err) => Some(format!("Cannot define `{}`: {}", name, err)),
+                }
+            }
+            Statement::Eval(named) => Some(match self.defs.resolve(named) {
+                Ok(term) => match eval_count_with_fuel(term, self.strategy, self.fuel) {
+                    Ok((normal_form, count)) => format!(
+                        "{} [{}]",
+                        normal_form.into_unpositioned_named(),
+                        steps(count)
+                    ),
+                    Err(OutOfFuelErr { term, steps }) => format!(
+                        "Out of fuel after {} steps. Reached: {}",
+                        steps,
+                        term.into_unpositioned_named()
+                    ),
+                },
+                Err(err) => format!("Naming error: {}", err),
+            }),
+        }
+    }
+
+    /// Type checks and evaluates a term of the typed language,
+    /// printing the warnings about its `case`s first and the store last, unless it is empty,
+    /// followed by what the garbage collector did, if anything.
+    /// Evaluation gets stuck on `head` or `tail` of `nil` and on `case`s without a branch.
+    fn run_typed(&self, src: &str) -> String {
+        let (term, ty, warnings) = match resolve_typed(src, &self.exn_type) {
+            Ok(resolved) => resolved,
+            Err(message) => return message,
+        };
+        let mut lines: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        let mut store = self.new_store();
+        lines.push(
+            match chapter_11::evaluator::eval_count_with_fuel(term, &mut store, self.fuel) {
+                Ok((normal_form, count)) => match Outcome::of(normal_form) {
+                    Outcome::Value(value) => format!(
+                        "{} : {} [{}]",
+                        value.into_unpositioned_named(),
+                        ty,
+                        steps(count)
+                    ),
+                    Outcome::Uncaught { position, value } => format!(
+                        "{} at {} [{}]",
+                        describe_uncaught(value),
+                        position.start,
+                        steps(count)
+                    ),
+                    Outcome::Stuck(stuck) => format!(
+                        "Stuck: {} [{}]",
+                        stuck.into_unpositioned_named(),
+                        steps(count)
+                    ),
+                },
+                Err(chapter_11::evaluator::OutOfFuelErr { term, steps }) => format!(
+                    "Out of fuel after {} steps. Reached: {}",
+                    steps,
+                    term.into_unpositioned_named()
+                ),
+            },
+        );
+        if !store.is_empty() {
+            lines.push(format!("Store: {}", store));
+        }
+        if store.gc_stats().collections > 0 {
+            lines.push(format!("GC: {}", store.gc_stats()));
+        }
+        lines.join("\n")
+    }
+
+    /// Numbers the characters of `src` after those of every lambda source before it,
+    /// so that terms from different lines never share a position,
+    /// which `Breakpoint::Def` relies on to recognize the value of its definition.
+    fn claim_index(&self, src: &str) -> usize {
+        let index = self.next_index.get();
+        self.next_index.set(index + src.chars().count());
+        index
+    }
+
+    fn resolve_lambda(&self, src: &str) -> Result<Term, String> {
+        match parse_at(src, self.claim_index(src)) {
+            Ok(named) => self
+                .defs
+                .resolve(named)
+                .map_err(|err| format!("Naming error: {}", err)),
+            Err(err) => Err(format!("Parse error: {}", err)),
+        }
+    }
+
+    fn step(&self, src: &str) -> String {
+        match self.language {
+            Language::Arith => match chapter_4::parse(src) {
+                Some(term) => match chapter_4::evaluator::eval1(term.clone()) {
+                    Some(evaluated) => format!("{}\n-> {}", term, evaluated),
+                    None => format!("{} has no step.", term),
+                },
+                None => format!("Parse error: Cannot parse `{}`.", src),
+            },
+            Language::Lambda => match self.resolve_lambda(src) {
+                Ok(term) => match trace::step_with(&term, self.strategy) {
+                    Some(step) => format!(
+                        "{}\n-> {}",
+                        trace::render(&step),
+                        step.after.into_unpositioned_named()
+                    ),
+                    None => format!("{} has no step.", term.into_unpositioned_named()),
+                },
+                Err(message) => message,
+            },
+            Language::Typed => match resolve_typed(src, &self.exn_type) {
+                Ok((term, _, _)) => {
+                    let mut store = self.new_store();
+                    match chapter_11::eval1(term.clone(), &mut store) {
+                        Some(evaluated) => format!(
+                            "{}\n-> {}",
+                            term.into_unpositioned_named(),
+                            with_store(evaluated, &store)
+                        ),
+                        None => format!("{} has no step.", term.into_unpositioned_named()),
+                    }
+                }
+                Err(message) => message,
+            },
+        }
+    }
+
+    fn trace(&self, src: &str) -> String {
+        match self.language {
+            Language::Arith => match chapter_4::parse(src) {
+                Some(mut term) => {
+                    let mut lines = vec![term.to_string()];
+                    while let Some(evaluated) = chapter_4::evaluator::eval1(term) {
+                        lines.push(format!("-> {}", evaluated));
+                        term = evaluated;
+                    }
+                    lines.join("\n")
+                }
+                None => format!("Parse error: Cannot parse `{}`.", src),
+            },
+            Language::Lambda => match self.resolve_lambda(src) {
+                Ok(term) => {
+                    let steps: Vec<_> = trace::steps_with(term.clone(), self.strategy)
+                        .take(self.fuel + 1)
+                        .collect();
+                    if steps.is_empty() {
+                        term.into_unpositioned_named().to_string()
+                    } else if steps.len() > self.fuel {
+                        format!(
+                            "{}\nOut of fuel after {} steps.",
+                            trace::render_all(&steps[..self.fuel]),
+                            self.fuel
+                        )
+                    } else {
+                        trace::render_all(&steps)
+                    }
+                }
+                Err(message) => message,
+            },
+            Language::Typed => match resolve_typed(src, &self.exn_type) {
+                Ok((term, _, _)) => {
+                    let mut lines = vec![term.clone().into_unpositioned_named().to_string()];
+                    let mut store = self.new_store();
+                    // Each step is printed after collecting, which keeps freed locations
+                    // out of the store shown.
+                    let result = chapter_11::evaluator::eval_count_with_fuel_observing(
+                        term,
+                        &mut store,
+                        self.fuel,
+                        |evaluated, store, freed| {
+                            lines.push(format!("-> {}", with_store(evaluated.clone(), store)));
+                            if let Some(freed) = freed {
+                                lines.push(format!("GC: {}", describe_freed(freed)));
+                            }
+                        },
+                    );
+                    if let Err(err) = result {
+                        lines.push(format!("Out of fuel after {} steps.", err.steps));
+                    }
+                    lines.join("\n")
+                }
+                Err(message) => message,
+            },
+        }
+    }
+
+    fn tree(&self, src: &str) -> String {
+        match self.language {
+            Language::Arith | Language::Typed => {
+                "Syntax trees are only available for lambda terms.".to_string()
+            }
+            Language::Lambda => match parse(src) {
+                Ok(named) => dot::named_tree(&named),
+                Err(err) => format!("Parse error: {}", err),
+            },
+        }
+    }
+
+    fn graph(&self, src: &str) -> String {
+        match self.language {
+            Language::Arith | Language::Typed => {
+                "Reduction graphs are only available for lambda terms.".to_string()
+            }
+            Language::Lambda => match self.resolve_lambda(src) {
+                Ok(term) => ReductionGraph::new(term, GRAPH_LIMIT).to_dot(),
+                Err(message) => message,
+            },
+        }
+    }
+
+    fn latex(&self, src: &str) -> String {
+        match self.language {
+            Language::Arith => match chapter_4::parse(src) {
+                Some(term) => chapter_4::latex::trace(&term, LATEX_WIDTH),
+                None => format!("Parse error: Cannot parse `{}`.", src),
+            },
+            Language::Lambda => match self.resolve_lambda(src) {
+                Ok(term) => {
+                    let steps: Vec<_> = trace::steps_with(term.clone(), self.strategy)
+                        .take(self.fuel)
+                        .collect();
+                    latex::trace(&term, &steps, LATEX_WIDTH)
+                }
+                Err(message) => message,
+            },
+            Language::Typed => "LaTeX is only available for arith and lambda terms.".to_string(),
+        }
+    }
+
+    fn derive(&self, src: &str) -> String {
+        match self.language {
+            Language::Arith => match chapter_4::parse(src) {
+                Some(term) => match chapter_4::latex::step_derivation(&term) {
+                    Some(derivation) => derivation
+                        .to_latex(Style::Mathpartir)
+                        .unwrap_or_else(|err| err.to_string()),
+                    None => format!("{} has no step.", term),
+                },
+                None => format!("Parse error: Cannot parse `{}`.", src),
+            },
+            Language::Lambda => match self.resolve_lambda(src) {
+                Ok(term) => match trace::step_with(&term, self.strategy) {
+                    Some(step) => latex::step_derivation(&step)
+                        .to_latex(Style::Mathpartir)
+                        .unwrap_or_else(|err| err.to_string()),
+                    None => format!("{} has no step.", term.into_unpositioned_named()),
+                },
+                Err(message) => message,
+            },
+            Language::Typed => {
+                "Derivations are only available for arith and lambda terms.".to_string()
+            }
+        }
+    }
+
+    fn set_strategy(&mut self, arg: &str) -> String {
+        if arg.is_empty() {
+            return format!("Strategy: {}", self.strategy.name());
+        }
+        match Strategy::from_name(arg) {
+            Some(strategy) => {
+                self.strategy = strategy;
+                format!("Strategy: {}", strategy.name())
+            }
+            None => format!(
+                "Unknown strategy `{}`. Expected one of: {}.",
+                arg,
+                Strategy::ALL
+                    .iter()
+                    .map(|strategy| strategy.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn set_language(&mut self, arg: &str) -> String {
+        if arg.is_empty() {
+            return format!("Language: {}", self.language.name());
+        }
+        match Language::from_name(arg) {
+            Some(language) => {
+                self.language = language;
+                format!("Language: {}", language.name())
+            }
+            None => format!(
+                "Unknown language `{}`. Expected one of: arith, lambda, typed.",
+                arg
+            ),
+        }
+    }
+
+    fn set_fuel(&mut self, arg: &str) -> String {
+        if arg.is_empty() {
+            return format!("Fuel: {}", self.fuel);
+        }
+        match arg.parse() {
+            Ok(fuel) => {
+                self.fuel = fuel;
+                format!("Fuel: {}", fuel)
+            }
+            Err(_) => format!("Invalid fuel `{}`. Expected a number of steps.", arg),
+        }
+    }
+
+    fn set_gc_threshold(&mut self, arg: &str) -> String {
+        match arg {
+            "" => {}
+            "off" => self.gc_threshold = None,
+            _ => match arg.parse() {
+                Ok(threshold) => self.gc_threshold = Some(threshold),
+                Err(_) => {
+                    return format!(
+                        "Invalid GC threshold `{}`. Expected a number of locations or `off`.",
+                        arg
+                    )
+                }
+            },
+        }
+        match self.gc_threshold {
+            Some(threshold) => format!("GC threshold: {}", threshold),
+            None => "GC: off".to_string(),
+        }
+    }
+
+    fn set_exn_type(&mut self, arg: &str) -> String {
+        if !arg.is_empty() {
+            match chapter_11::parser::parse_type(arg) {
+                Ok(ty) => self.exn_type = ty,
+                Err(err) => return format!("Parse error: {}", err),
+            }
+        }
+        format!("Exception type: {}", self.exn_type)
+    }
+
+    /// An empty store for a typed term, collected at the threshold set with `:gc`.
+    fn new_store(&self) -> Store {
+        match self.gc_threshold {
+            Some(threshold) => Store::with_gc_threshold(threshold),
+            None => Store::new(),
+        }
+    }
+
+    fn load(&mut self, path: &str) -> String {
+        match fs::read_to_string(path) {
+            Ok(src) => self.run(&src),
+            Err(err) => format!("Cannot read `{}`: {}", path, err),
+        }
+    }
+
+    fn list_defs(&self) -> String {
+        self.defs
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value.clone().into_unpositioned_named()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn describe_debugger(debugger: &Debugger) -> String {
+    match debugger.next_step() {
+        Some(step) => format!("Step {}:\n{}", debugger.steps() + 1, trace::render(&step)),
+        None => format!(
+            "Normal form after {}: {}",
+            steps(debugger.steps()),
+            debugger.term().clone().into_unpositioned_named()
+        ),
+    }
+}
+
+fn describe_stop(stop: &Stop) -> String {
+    match stop {
+        Stop::Breakpoint(i) => format!("Stopped at breakpoint {}.", i),
+        Stop::Finished => "Finished.".to_string(),
+        Stop::OutOfFuel => "Out of fuel.".to_string(),
+        Stop::SteppedOver { steps: count } => format!("Stepped over {}.", steps(*count)),
+    }
+}
+
+fn describe_breakpoint(breakpoint: &Breakpoint) -> String {
+    match breakpoint {
+        Breakpoint::Def { name, .. } => format!("calls to `{}`", name),
+        Breakpoint::Range(range) => format!("redexes within {}-{}", range.start, range.end),
+    }
+}
+
+/// Parses a range like `1:1-1:10`, where columns are one-based.
+fn parse_range(s: &str) -> Option<FilePositionRange> {
+    fn parse_position(s: &str) -> Option<FilePosition> {
+        let mut parts = s.splitn(2, ':');
+        let line = parts.next()?.trim().parse().ok()?;
+        let column: usize = parts.next()?.trim().parse().ok()?;
+        Some(FilePosition {
+            line,
+            column: column.checked_sub(1)?,
+            index: 0,
+        })
+    }
+
+    let mut parts = s.splitn(2, '-');
+    let start = parse_position(parts.next()?)?;
+    let end = parse_position(parts.next()?)?;
+    Some(FilePositionRange { start, end })
+}
+
+fn split_command(command: &str) -> (&str, &str) {
+    match command.find(char::is_whitespace) {
+        Some(i) => (&command[..i], command[i..].trim()),
+        None => (command, ""),
+    }
+}
+
+fn paren_depth(src: &str) -> isize {
+    src.chars().fold(0, |depth, c| match c {
+        '(' => depth + 1,
+        ')' => depth - 1,
+        _ => depth,
+    })
+}
+
+/// Writes `term`, followed by `| store` unless `store` is empty.
+fn with_store(term: chapter_11::term::unnamed::Term, store: &Store) -> String {
+    if store.is_empty() {
+        term.into_unpositioned_named().to_string()
+    } else {
+        format!("{} | {}", term.into_unpositioned_named(), store)
+    }
+}
+
+/// The derivation of `S <: T` from `arg`, or why there is none.
+fn subtype(arg: &str) -> String {
+    let (sub, sup) = match arg.find("<:") {
+        Some(i) => (&arg[..i], &arg[i + 2..]),
+        None => return "Expected `<type> <: <type>`.".to_string(),
+    };
+    let parse = |src: &str| chapter_11::parser::parse_type(src.trim());
+    match (parse(sub), parse(sup)) {
+        (Ok(sub), Ok(sup)) => match subtyping::derive(&sub, &sup) {
+            Ok(derivation) => derivation.to_string(),
+            Err(err) => err.to_string(),
+        },
+        (Err(err), _) | (_, Err(err)) => format!("Parse error: {}", err),
+    }
+}
+
+/// Writes `freed #0, #2`, or `freed nothing`.
+fn describe_freed(freed: &[usize]) -> String {
+    if freed.is_empty() {
+        return "freed nothing".to_string();
+    }
+    let locations: Vec<_> = freed
+        .iter()
+        .map(|location| format!("#{}", location))
+        .collect();
+    format!("freed {}", locations.join(", "))
+}
+
+/// Writes ``Uncaught exception `v` `` for `raise v`, or `Uncaught error` for `error`.
+pub(crate) fn describe_uncaught(value: Option<chapter_11::term::unnamed::Term>) -> String {
+    match value {
+        Some(value) => format!("Uncaught exception `{}`", value.into_unpositioned_named()),
+        None => "Uncaught error".to_string(),
+    }
+}
+
+/// Parses, names and type checks a closed term of the typed language,
+/// in which `raise` takes values of type `exn_type`.
+fn resolve_typed(
+    src: &str,
+    exn_type: &chapter_11::Type,
+) -> Result<
+    (
+        chapter_11::term::unnamed::Term,
+        chapter_11::Type,
+        Vec<chapter_11::type_checker::Warning>,
+    ),
+    String,
+> {
+    let named = chapter_11::parse(src).map_err(|err| format!("Parse error: {}", err))?;
+    let term = chapter_11::term::unnamed::Term::from_named(named, &Context::empty())
+        .map_err(|err| format!("Naming error: {}", err))?;
+    let (ty, warnings) = chapter_15::type_checker::type_of_with_warnings(
+        &term,
+        &TypeContext::empty().with_exn_type(exn_type.clone()),
+    )
+    .map_err(|err| format!("Type error: {}", err))?;
+    Ok((term, ty, warnings))
+}
+
+pub(crate) fn steps(count: usize) -> String {
+    if count == 1 {
+        "1 step".to_string()
+    } else {
+        format!("{} steps", count)
+    }
+}
@@ -0,0 +1,401 @@
+use super::{Language, Session};
+use crate::chapter_7::evaluator::Strategy;
+
+#[test]
+fn eval_prints_normal_form_and_step_count() {
+    let mut session = Session::new();
+    let actual = session.handle_line(r"(\a. a \b. a) \b.b");
+    assert_eq!(actual, r"(\b. (\b'. b')) [2 steps]");
+}
+
+#[test]
+fn definitions_are_used_in_later_terms() {
+    let mut session = Session::new();
+    assert_eq!(session.handle_line(r"id = \x. x"), "");
+    assert_eq!(session.handle_line(r"k = \x. \y. x"), "");
+    let actual = session.handle_line(r"k id id");
+    assert_eq!(actual, r"(\x. x) [2 steps]");
+    assert_eq!(session.definitions().len(), 2);
+}
+
+#[test]
+fn defs_lists_definitions() {
+    let mut session = Session::new();
+    session.handle_line(r"id = \x. x; twice = \f. \x. f (f x)");
+    let actual = session.handle_line(":defs");
+    assert_eq!(actual, "id = (\\x. x)\ntwice = (\\f. (\\x. (f (f x))))");
+}
+
+#[test]
+fn unbalanced_parens_continue_on_next_line() {
+    let mut session = Session::new();
+    assert_eq!(session.handle_line(r"((\x. x)"), "");
+    assert_eq!(session.prompt(), "      | ");
+    let actual = session.handle_line(r"  \y. y)");
+    assert_eq!(actual, r"(\y. y) [1 step]");
+    assert_eq!(session.prompt(), "lambda> ");
+}
+
+#[test]
+fn step_underlines_redex() {
+    let mut session = Session::new();
+    let actual = session.handle_line(r":step (\x. x) ((\y. y) \z. z)");
+    assert_eq!(
+        actual,
+        "((\\x. x) ((\\y. y) (\\z. z)))\n         ^^^^^^^^^^^^^^^^^\n-> ((\\x. x) (\\z. z))"
+    );
+}
+
+#[test]
+fn trace_stops_when_out_of_fuel() {
+    let mut session = Session::new();
+    session.handle_line(":fuel 2");
+    let actual = session.handle_line(r":trace (\x. x x) \x. x x");
+    assert!(actual.ends_with("Out of fuel after 2 steps."));
+}
+
+#[test]
+fn divergent_term_runs_out_of_fuel() {
+    let mut session = Session::new();
+    session.handle_line(":fuel 10");
+    let actual = session.handle_line(r"(\x. x x) \x. x x");
+    assert!(actual.starts_with("Out of fuel after 10 steps."));
+}
+
+#[test]
+fn strategy_can_be_changed() {
+    let mut session = Session::new();
+    assert_eq!(session.handle_line(":strategy cbn"), "Strategy: cbn");
+    assert_eq!(session.strategy(), Strategy::CallByName);
+    let actual = session.handle_line(r"(\x. \y. y) ((\x. x x) \x. x x)");
+    assert_eq!(actual, r"(\y. y) [1 step]");
+    assert!(session
+        .handle_line(":strategy lazy")
+        .starts_with("Unknown strategy"));
+}
+
+#[test]
+fn arith_language() {
+    let mut session = Session::new();
+    assert_eq!(session.handle_line(":lang arith"), "Language: arith");
+    assert_eq!(session.language(), Language::Arith);
+    assert_eq!(session.prompt(), "arith> ");
+    let actual = session.handle_line("if iszero 0 then succ 0 else 0");
+    assert_eq!(actual, "succ 0 [2 steps]");
+    let actual = session.handle_line("succ true");
+    assert_eq!(actual, "Stuck: succ true [0 steps]");
+}
+
+#[test]
+fn errors_are_reported() {
+    let mut session = Session::new();
+    assert_eq!(
+        session.handle_line(r"\x. y"),
+        "Naming error: 1:5: Cannot find `y` in context."
+    );
+    assert_eq!(
+        session.handle_line(r"\x."),
+        "Parse error: Expected `\\` or `(` or identifier, but found end of input."
+    );
+    assert!(session
+        .handle_line(":frobnicate")
+        .starts_with("Unknown command"));
+}
+
+#[test]
+fn quit() {
+    let mut session = Session::new();
+    assert!(!session.has_quit());
+    session.handle_line(":quit");
+    assert!(session.has_quit());
+}
+
+#[test]
+fn debugger() {
+    let mut session = Session::new();
+    session.handle_line(r"id = \x. x");
+    let actual = session.handle_line(r":debug id (id \y. y)");
+    assert_eq!(
+        actual,
+        "Step 1:\n((\\x. x) ((\\x. x) (\\y. y)))\n         ^^^^^^^^^^^^^^^^^"
+    );
+    assert!(session.is_debugging());
+    assert_eq!(session.prompt(), "debug> ");
+
+    assert_eq!(
+        session.handle_line(":break id"),
+        "Breakpoint 0: calls to `id`"
+    );
+    assert_eq!(
+        session.handle_line(":c"),
+        "Stopped at breakpoint 0.\nStep 2:\n((\\x. x) (\\y. y))\n^^^^^^^^^^^^^^^^^"
+    );
+    assert_eq!(
+        session.handle_line(":s"),
+        "Normal form after 2 steps: (\\y. y)"
+    );
+    assert_eq!(
+        session.handle_line(":s"),
+        "The term is already in normal form."
+    );
+    assert!(session.handle_line(r"\x. x").starts_with("Cannot evaluate"));
+
+    assert_eq!(session.handle_line(":abort"), "Stopped debugging.");
+    assert!(!session.is_debugging());
+    assert_eq!(session.handle_line(r"id \z. z"), r"(\z. z) [1 step]");
+}
+
+#[test]
+fn def_breakpoints_ignore_equal_values_from_other_lines() {
+    let mut session = Session::new();
+    session.handle_line(r"a = \x. x");
+    session.handle_line(r"b = \y. y");
+    session.handle_line(r":debug b (a b)");
+    assert_eq!(
+        session.handle_line(":break a"),
+        "Breakpoint 0: calls to `a`"
+    );
+    assert_eq!(
+        session.handle_line(":c"),
+        "Finished.\nNormal form after 2 steps: (\\y. y)"
+    );
+}
+
+#[test]
+fn debugger_step_over_and_range_breakpoints() {
+    let mut session = Session::new();
+    session.handle_line(r":debug (\x. x) ((\y. y) ((\z. z) \w. w))");
+    assert_eq!(
+        session.handle_line(":break 1:20-1:36"),
+        "Breakpoint 0: redexes within 1:20-1:36"
+    );
+    assert_eq!(
+        session.handle_line(":breakpoints"),
+        "0: redexes within 1:20-1:36"
+    );
+    assert_eq!(
+        session.handle_line(":n"),
+        "Stepped over 1 step.\nStep 2:\n((\\x. x) ((\\y. y) (\\w. w)))\n         ^^^^^^^^^^^^^^^^^"
+    );
+    assert_eq!(
+        session.handle_line(":delete 0"),
+        "Deleted redexes within 1:20-1:36."
+    );
+    assert_eq!(session.handle_line(":delete 0"), "No breakpoint `0`.");
+    assert!(session
+        .handle_line(":break nope")
+        .starts_with("Expected a defined name"));
+}
+
+#[test]
+fn tree_and_graph_print_dot() {
+    let mut session = Session::new();
+    session.handle_line(r"id = \x. x");
+    let tree = session.handle_line(":tree id id");
+    assert!(tree.starts_with("digraph {"));
+    assert!(tree.contains(r#"n1 [label="id", shape=box, style=dashed];"#));
+
+    let graph = session.handle_line(":graph id id");
+    assert!(graph.contains(r#"t0 -> t1 [label="cbv, cbn, normal"];"#));
+    assert!(graph.contains(r#"t1 [label="(\\x. x)", peripheries=2];"#));
+}
+
+#[test]
+fn latex_and_derive() {
+    let mut session = Session::new();
+    assert_eq!(
+        session.handle_line(r":latex (\x. x) \y. y"),
+        "\\begin{align*}\n&\\underline{(\\lambda x.\\,x)\\;(\\lambda y.\\,y)} \\\\\n\\longrightarrow{} &\\lambda y.\\,y\n\\end{align*}"
+    );
+    assert_eq!(
+        session.handle_line(r":derive (\x. x) \y. y"),
+        "\\inferrule*[right=\\textsc{E-AppAbs}]{}{(\\lambda x.\\,x)\\;(\\lambda y.\\,y) \\longrightarrow \\lambda y.\\,y}"
+    );
+
+    session.handle_line(":lang arith");
+    assert_eq!(
+        session.handle_line(":derive succ pred 0"),
+        "\\inferrule*[right=\\textsc{E-Succ}]{\n  \\inferrule*[right=\\textsc{E-PredZero}]{}{\\mathsf{pred}\\;0 \\longrightarrow 0}\n}{\\mathsf{succ}\\;(\\mathsf{pred}\\;0) \\longrightarrow \\mathsf{succ}\\;0}"
+    );
+}
+
+#[test]
+fn typed_language() {
+    let mut session = Session::new();
+    assert_eq!(session.handle_line(":lang typed"), "Language: typed");
+    assert_eq!(session.language(), Language::Typed);
+    assert_eq!(
+        session.handle_line(r"(\x:Unit. x) unit; {1, unit}"),
+        "{1, unit} : {Nat, Unit} [2 steps]"
+    );
+    assert_eq!(
+        session.handle_line("succ true"),
+        "Type error: 1:6: Expected a term of type `Nat`, but found a term of type `Bool`."
+    );
+    assert_eq!(session.handle_line(":step pred 1"), "(pred 1)\n-> 0");
+    assert_eq!(
+        session.handle_line("case inl 0 as Nat + Nat of inl x => x"),
+        "1:1: Warning: `case` has no branch for `inr`.\n0 : Nat [1 step]"
+    );
+}
+
+#[test]
+fn divergent_typed_term_runs_out_of_fuel() {
+    let mut session = Session::new();
+    session.handle_line(":lang typed");
+    session.handle_line(":fuel 10");
+    let actual = session.handle_line(r"fix (\x:Nat. x)");
+    assert_eq!(
+        actual,
+        r"Out of fuel after 10 steps. Reached: (fix (\x:Nat. x))"
+    );
+    let actual = session.handle_line(r":trace fix (\x:Nat. x)");
+    assert!(actual.ends_with("Out of fuel after 10 steps."));
+    assert_eq!(actual.lines().count(), 12);
+}
+
+#[test]
+fn stuck_typed_term_is_reported() {
+    let mut session = Session::new();
+    session.handle_line(":lang typed");
+    assert_eq!(
+        session.handle_line("head[Nat] tail[Nat] ([1] as List Nat)"),
+        "Stuck: (head[Nat] nil[Nat]) [1 step]"
+    );
+}
+
+#[test]
+fn typed_store_is_printed() {
+    let mut session = Session::new();
+    session.handle_line(":lang typed");
+    assert_eq!(
+        session.handle_line("let r = ref 0 in r := succ (!r); r"),
+        "#0 : Ref Nat [5 steps]\nStore: #0 = 1"
+    );
+    assert_eq!(
+        session.handle_line("!(ref unit)"),
+        "unit : Unit [2 steps]\nStore: #0 = unit"
+    );
+    assert_eq!(
+        session.handle_line(":step ref 0"),
+        "(ref 0)\n-> #0 | #0 = 0"
+    );
+    assert_eq!(
+        session.handle_line(":trace ref 0 := 1"),
+        "((ref 0) := 1)\n-> (#0 := 1) | #0 = 0\n-> unit | #0 = 1"
+    );
+}
+
+#[test]
+fn typed_store_is_collected() {
+    let mut session = Session::new();
+    session.handle_line(":lang typed");
+    assert_eq!(session.handle_line(":gc"), "GC threshold: 1000");
+    assert_eq!(session.handle_line(":gc 1"), "GC threshold: 1");
+    assert_eq!(
+        session.handle_line("let a = ref 0 in let b = ref 1 in b"),
+        "#1 : Ref Nat [4 steps]\nStore: #1 = 1\nGC: 2 collections freed 1 location"
+    );
+    assert_eq!(
+        session.handle_line(":trace let a = ref 0 in ref 1"),
+        "(let a = (ref 0) in (ref 1))\n\
+         -> (let a = #0 in (ref 1)) | #0 = 0\n\
+         GC: freed nothing\n\
+         -> (ref 1) | #0 = 0\n\
+         -> #1 | #1 = 1\n\
+         GC: freed #0"
+    );
+    assert_eq!(session.handle_line(":fuel 2"), "Fuel: 2");
+    assert_eq!(
+        session.handle_line(":trace let a = ref 0 in ref 1"),
+        "(let a = (ref 0) in (ref 1))\n\
+         -> (let a = #0 in (ref 1)) | #0 = 0\n\
+         GC: freed nothing\n\
+         -> (ref 1) | #0 = 0\n\
+         Out of fuel after 2 steps."
+    );
+    session.handle_line(":fuel 10");
+    assert_eq!(session.handle_line(":gc off"), "GC: off");
+    assert_eq!(
+        session.handle_line("let a = ref 0 in ref 1"),
+        "#1 : Ref Nat [3 steps]\nStore: #0 = 0, #1 = 1"
+    );
+    assert_eq!(
+        session.handle_line(":gc some"),
+        "Invalid GC threshold `some`. Expected a number of locations or `off`."
+    );
+}
+
+#[test]
+fn uncaught_typed_exceptions_are_reported() {
+    let mut session = Session::new();
+    session.handle_line(":lang typed");
+    assert_eq!(
+        session.handle_line("succ (pred error)"),
+        "Uncaught error at 1:12 [2 steps]"
+    );
+    assert_eq!(
+        session.handle_line(r"(\x:Nat. x) (raise 7)"),
+        "Uncaught exception `7` at 1:14 [1 step]"
+    );
+    assert_eq!(
+        session.handle_line("try raise 3 with e => succ e"),
+        "4 : Nat [1 step]"
+    );
+
+    assert_eq!(session.handle_line(":exn"), "Exception type: Nat");
+    assert_eq!(session.handle_line(":exn Bool"), "Exception type: Bool");
+    assert_eq!(
+        session.handle_line("try raise true with e => if e then 1 else 0"),
+        "1 : Nat [2 steps]"
+    );
+    assert_eq!(
+        session.handle_line("raise 0"),
+        "Type error: 1:7: Expected a term of type `Bool`, but found a term of type `Nat`."
+    );
+    assert!(session.handle_line(":exn ->").starts_with("Parse error: "));
+}
+
+#[test]
+fn subtype_shows_derivations_and_failures() {
+    let mut session = Session::new();
+    assert_eq!(
+        session.handle_line(":subtype {x:Nat, y:Bool} <: {y:Bool}"),
+        "S-Trans: {x:Nat, y:Bool} <: {y:Bool}\n  \
+         S-RcdPerm: {x:Nat, y:Bool} <: {y:Bool, x:Nat}\n  \
+         S-RcdWidth: {y:Bool, x:Nat} <: {y:Bool}"
+    );
+    assert_eq!(
+        session.handle_line(":subtype {x:Nat} -> Nat <: {x:Nat, y:Nat} -> Top"),
+        "S-Arrow: {x:Nat} -> Nat <: {x:Nat, y:Nat} -> Top\n  \
+         S-RcdWidth: {x:Nat, y:Nat} <: {x:Nat}\n  \
+         S-Top: Nat <: Top"
+    );
+    assert_eq!(
+        session.handle_line(":subtype {x:Nat} <: {x:Nat, y:Nat}"),
+        "`{x:Nat}` is not a subtype of `{x:Nat, y:Nat}`: `{x:Nat}` lacks the label `y`."
+    );
+    assert_eq!(
+        session.handle_line(":subtype Nat"),
+        "Expected `<type> <: <type>`."
+    );
+    assert!(session
+        .handle_line(":subtype Nat <: ->")
+        .starts_with("Parse error: "));
+    session.handle_line(":lang typed");
+    assert_eq!(
+        session.handle_line(r"(\r:{x:Nat}. r.x) {x=0, y=true}"),
+        "0 : Nat [2 steps]"
+    );
+}
+
+#[test]
+fn large_numerals_do_not_overflow_the_stack() {
+    let mut session = Session::new();
+    session.handle_line(":lang typed");
+    assert_eq!(session.handle_line("100000"), "100000 : Nat [0 steps]");
+    assert_eq!(
+        session.handle_line("pred 100000000"),
+        "99999999 : Nat [1 step]"
+    );
+}